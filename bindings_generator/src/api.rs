@@ -2,6 +2,7 @@ use heck::CamelCase as _;
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
 use std::collections::{HashMap, HashSet};
+use std::fmt;
 
 use miniserde::Deserialize;
 miniserde::make_place!(Place);
@@ -22,13 +23,31 @@ impl Api {
     ///
     /// If the `data` is not valid JSON data the function will panic.
     pub fn new(data: &str) -> Self {
+        Self::from_classes(miniserde::json::from_str(data).expect("Invalid JSON data"))
+    }
+
+    /// Construct an `Api` instance from JSON data, and check that all types used in method
+    /// signatures are supported with `validate`.
+    ///
+    /// This is useful for API descriptions of engine forks or builds with custom modules, which
+    /// may not be supported by the generator.
+    pub fn try_new(data: &str) -> Result<Self, ApiError> {
+        let classes = miniserde::json::from_str(data).map_err(|_| ApiError::InvalidJson)?;
+        let api = Self::from_classes(classes);
+        api.validate()?;
+        Ok(api)
+    }
+
+    fn from_classes(classes: Vec<GodotClass>) -> Self {
         let mut api = Self {
-            classes: miniserde::json::from_str(data).expect("Invalid JSON data"),
+            classes,
             api_underscore: Default::default(),
         };
 
         api.strip_leading_underscores();
         api.generate_module_names();
+        crate::global_enums::add_global_enums(&mut api);
+        api.apply_enum_types();
 
         api.classes
             .iter_mut()
@@ -48,6 +67,75 @@ impl Api {
         None
     }
 
+    /// Checks that the types of all method arguments and return values are supported by the
+    /// generator: core types, classes in the API, or enums of classes in the API.
+    pub fn validate(&self) -> Result<(), ApiError> {
+        let mut unknown = Vec::new();
+
+        for class in &self.classes {
+            for method in &class.methods {
+                let types = method
+                    .arguments
+                    .iter()
+                    .map(|arg| &arg.ty)
+                    .chain(Some(&method.return_type));
+
+                for ty in types {
+                    if !self.is_known_type(ty) {
+                        unknown.push(UnknownType {
+                            class: class.name.clone(),
+                            method: method.name.clone(),
+                            ty: ty.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
+        if unknown.is_empty() {
+            Ok(())
+        } else {
+            Err(ApiError::UnknownTypes(unknown))
+        }
+    }
+
+    fn is_known_type(&self, ty: &str) -> bool {
+        let is_identifier = |name: &str| {
+            name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        };
+
+        // Enums of core types are translated by `Ty::from_src`. Other enums must be declared by
+        // their class.
+        if let Some(enum_ty) = ty.strip_prefix("enum.") {
+            if matches!(
+                enum_ty,
+                "Error" | "Variant::Type" | "Variant::Operator" | "Vector3::Axis"
+            ) {
+                return true;
+            }
+
+            let mut split = enum_ty.splitn(2, "::");
+            return match (split.next(), split.next()) {
+                // Leading underscores of class names are stripped, e.g. `_OS`.
+                (Some(class), Some(name)) => matches!(
+                    self.find_class(class.trim_start_matches('_')),
+                    Some(class) if class.enums.iter().any(|e| e.name == name)
+                ),
+                _ => false,
+            };
+        }
+
+        if !is_identifier(ty) {
+            return false;
+        }
+
+        match Ty::from_src(ty) {
+            Ty::Object(_) => self.find_class(ty).is_some(),
+            _ => true,
+        }
+    }
+
     pub fn class_inherits(&self, class: &GodotClass, base_class_name: &str) -> bool {
         if class.base_class == base_class_name {
             return true;
@@ -79,6 +167,41 @@ impl Api {
         }
     }
 
+    /// Changes the types of the method arguments and return values that take flags or values of
+    /// global enums from `int` to the corresponding enum.
+    fn apply_enum_types(&mut self) {
+        let arguments = crate::flags::FLAGS_ARGUMENTS
+            .iter()
+            .chain(crate::global_enums::GLOBAL_ENUM_ARGUMENTS);
+
+        for &(class_name, method_name, arg_name, ty) in arguments {
+            let method = self
+                .classes
+                .iter_mut()
+                .filter(|class| class.name == class_name)
+                .flat_map(|class| class.methods.iter_mut())
+                .find(|method| method.name == method_name);
+
+            let method = match method {
+                Some(method) => method,
+                None => continue,
+            };
+
+            let old_ty = match arg_name {
+                Some(arg_name) => method
+                    .arguments
+                    .iter_mut()
+                    .find(|arg| arg.name == arg_name)
+                    .map(|arg| &mut arg.ty),
+                None => Some(&mut method.return_type),
+            };
+
+            if let Some(old_ty) = old_ty.filter(|old_ty| old_ty.as_str() == "int") {
+                *old_ty = ty.to_string();
+            }
+        }
+    }
+
     fn generate_module_names(&mut self) {
         self.classes
             .iter_mut()
@@ -86,6 +209,48 @@ impl Api {
     }
 }
 
+/// Error returned by `Api::try_new` and `Api::validate`.
+#[derive(Clone, PartialEq, Debug)]
+pub enum ApiError {
+    /// The API description isn't valid JSON, or doesn't have the expected structure.
+    InvalidJson,
+    /// Method signatures use types that aren't supported by the generator.
+    UnknownTypes(Vec<UnknownType>),
+}
+
+/// A type in a method signature that isn't supported by the generator.
+#[derive(Clone, PartialEq, Debug)]
+pub struct UnknownType {
+    pub class: String,
+    pub method: String,
+    pub ty: String,
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiError::InvalidJson => write!(f, "the API description is not valid JSON data"),
+            ApiError::UnknownTypes(types) => {
+                write!(
+                    f,
+                    "the API description uses types that are neither core types, classes, \
+                    nor enums of classes in the API:"
+                )?;
+                for unknown in types {
+                    write!(
+                        f,
+                        "\n  {} in {}::{}",
+                        unknown.ty, unknown.class, unknown.method
+                    )?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ApiError {}
+
 #[derive(Deserialize, Debug)]
 pub struct GodotClass {
     pub name: String,
@@ -135,12 +300,29 @@ impl GodotClass {
         }
     }
 
-    pub fn is_singleton_thread_safe(&self) -> bool {
-        assert!(self.singleton, "class is not a singleton");
-        !matches!(
+    /// Returns whether the class can be used from threads other than the main thread, following
+    /// the official [thread-safety guidelines][thread-safety].
+    ///
+    /// [thread-safety]: https://docs.godotengine.org/en/stable/tutorials/threads/thread_safe_apis.html
+    pub fn thread_safety(&self, api: &Api) -> ThreadSafety {
+        if matches!(
             self.name.as_str(),
             "VisualServer" | "PhysicsServer" | "Physics3DServer" | "Physics2DServer"
-        )
+        ) {
+            return ThreadSafety::Server;
+        }
+
+        let is_main_thread_only = matches!(
+            self.name.as_str(),
+            "Node" | "MainLoop" | "TreeItem" | "SceneTreeTimer"
+        ) || api.class_inherits(self, "Node")
+            || api.class_inherits(self, "MainLoop");
+
+        if is_main_thread_only {
+            ThreadSafety::MainThreadOnly
+        } else {
+            ThreadSafety::ThreadSafe
+        }
     }
 
     /// Returns the base class from `api` if `base_class` is not empty. Returns `None` otherwise.
@@ -162,6 +344,19 @@ impl GodotClass {
     }
 }
 
+/// How an engine class can be used from multiple threads.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ThreadSafety {
+    /// The class can be used from any thread, e.g. resources and `File`.
+    ThreadSafe,
+    /// The class can only be used from the main thread, e.g. nodes and the scene tree.
+    MainThreadOnly,
+    /// A rendering or physics server, which can only be used from other threads if thread-safe
+    /// operations are enabled in the project settings. This can't be checked statically, so
+    /// their `godot_singleton` getters are unsafe.
+    Server,
+}
+
 pub type ConstantName = String;
 pub type ConstantValue = i64;
 
@@ -555,7 +750,7 @@ impl Ty {
                 quote! { ret as _ }
             }
             Ty::Enum(path) => {
-                quote! { #path(ret) }
+                quote! { #path::from(ret) }
             }
 
             Ty::Vector2
@@ -746,4 +941,73 @@ mod tests {
             assert_eq!(*expected, actual, "Input: {}", class_name);
         });
     }
+
+    #[test]
+    fn thread_safety() {
+        let api = Api::new(include_str!("../../gdnative-bindings/api.json"));
+        let thread_safety = |name: &str| api.find_class(name).unwrap().thread_safety(&api);
+
+        assert_eq!(ThreadSafety::MainThreadOnly, thread_safety("Node"));
+        assert_eq!(ThreadSafety::MainThreadOnly, thread_safety("Sprite"));
+        assert_eq!(ThreadSafety::MainThreadOnly, thread_safety("SceneTree"));
+        assert_eq!(ThreadSafety::MainThreadOnly, thread_safety("TreeItem"));
+        assert_eq!(ThreadSafety::Server, thread_safety("VisualServer"));
+        assert_eq!(ThreadSafety::Server, thread_safety("Physics2DServer"));
+        assert_eq!(ThreadSafety::ThreadSafe, thread_safety("Object"));
+        assert_eq!(ThreadSafety::ThreadSafe, thread_safety("Texture"));
+        assert_eq!(ThreadSafety::ThreadSafe, thread_safety("File"));
+        assert_eq!(ThreadSafety::ThreadSafe, thread_safety("Engine"));
+    }
+
+    #[test]
+    fn validate_types() {
+        let api = Api::new(include_str!("../../gdnative-bindings/api.json"));
+        assert_eq!(Ok(()), api.validate());
+
+        let data = r#"[{
+            "name": "Custom",
+            "base_class": "",
+            "api_type": "core",
+            "singleton": false,
+            "is_reference": false,
+            "instanciable": true,
+            "properties": [],
+            "signals": [],
+            "methods": [{
+                "name": "run",
+                "return_type": "enum.Custom::Missing",
+                "is_editor": false,
+                "is_noscript": false,
+                "is_const": false,
+                "is_reverse": false,
+                "is_virtual": false,
+                "has_varargs": false,
+                "arguments": [
+                    { "name": "a", "type": "int", "has_default_value": false, "default_value": "" },
+                    { "name": "b", "type": "CustomType", "has_default_value": false, "default_value": "" },
+                    { "name": "c", "type": "Array[int]", "has_default_value": false, "default_value": "" },
+                    { "name": "d", "type": "enum.Error", "has_default_value": false, "default_value": "" }
+                ]
+            }],
+            "enums": [],
+            "constants": {}
+        }]"#;
+
+        let unknown = |ty: &str| UnknownType {
+            class: "Custom".into(),
+            method: "run".into(),
+            ty: ty.into(),
+        };
+
+        assert_eq!(
+            Err(ApiError::UnknownTypes(vec![
+                unknown("CustomType"),
+                unknown("Array[int]"),
+                unknown("enum.Custom::Missing"),
+            ])),
+            Api::try_new(data).map(|_| ())
+        );
+
+        assert_eq!(Err(ApiError::InvalidJson), Api::try_new("{").map(|_| ()));
+    }
 }
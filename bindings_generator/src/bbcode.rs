@@ -0,0 +1,425 @@
+//! Translation of the BBCode markup used in the documentation of the engine to rustdoc.
+//!
+//! References to classes, methods, properties, signals, constants and enums are translated to
+//! intra-doc links to the generated items. References to items that aren't generated, e.g.
+//! virtual methods or classes that are excluded from the build, are kept as inline code.
+
+use crate::api::{Api, GodotClass};
+use crate::class_docs::{class_name_from_docs, GodotXMLDocs};
+use crate::classes::generate_enum_name;
+use crate::methods::{rename_property_getter, skip_method};
+
+use heck::CamelCase as _;
+
+/// Translates `bbcode`, from the documentation of `class`, to rustdoc.
+pub(crate) fn to_rustdoc(api: &Api, docs: &GodotXMLDocs, class: &str, bbcode: &str) -> String {
+    let links = Links { api, docs, class };
+
+    let mut output = String::with_capacity(bbcode.len());
+    let mut urls = Vec::new();
+    let mut in_codeblock = false;
+    let mut in_code = false;
+    let mut rest = bbcode;
+
+    if bbcode.contains("[codeblock]") {
+        output.push_str("_Sample code is GDScript unless otherwise noted._\n\n");
+    }
+
+    while !rest.is_empty() {
+        let (text, tag, next) = match next_tag(rest) {
+            Some((start, end)) => (
+                &rest[..start],
+                Some(&rest[start + 1..end - 1]),
+                &rest[end..],
+            ),
+            None => (rest, None, ""),
+        };
+        rest = next;
+
+        if in_codeblock || in_code {
+            output.push_str(text);
+        } else {
+            push_text(&mut output, text, urls.is_empty());
+        }
+
+        let tag = match tag {
+            Some(tag) => tag,
+            None => break,
+        };
+
+        if in_codeblock {
+            if tag == "/codeblock" {
+                in_codeblock = false;
+                output.truncate(output.trim_end().len());
+                output.push_str("\n```");
+            } else {
+                output.push('[');
+                output.push_str(tag);
+                output.push(']');
+            }
+            continue;
+        }
+
+        if in_code {
+            if tag == "/code" {
+                in_code = false;
+                output.push('`');
+            } else {
+                output.push('[');
+                output.push_str(tag);
+                output.push(']');
+            }
+            continue;
+        }
+
+        match tag {
+            "codeblock" => {
+                in_codeblock = true;
+                output.truncate(output.trim_end().len());
+                if !output.is_empty() {
+                    output.push_str("\n\n");
+                }
+                output.push_str("```gdscript");
+            }
+            "code" => {
+                in_code = true;
+                output.push('`');
+            }
+            "b" | "/b" => output.push_str("**"),
+            "i" | "/i" => output.push('*'),
+            "url" => {
+                urls.push(None);
+                output.push('<');
+            }
+            "/url" => match urls.pop() {
+                Some(Some(url)) => {
+                    output.push_str("](");
+                    output.push_str(url);
+                    output.push(')');
+                }
+                Some(None) => output.push('>'),
+                None => output.push_str("\\[/url\\]"),
+            },
+            tag if tag.starts_with("url=") => {
+                urls.push(Some(&tag[4..]));
+                output.push('[');
+            }
+            tag => output.push_str(&links.translate(tag)),
+        }
+    }
+
+    output.truncate(output.trim_end().len());
+    output
+}
+
+/// Pushes text outside of code to `output`. Line breaks separate paragraphs in BBCode, and URLs
+/// outside of `[url]` tags are turned into links.
+fn push_text(output: &mut String, text: &str, link_urls: bool) {
+    let text = text.replace('\n', "\n\n").replace('<', "\\<");
+
+    if !link_urls {
+        output.push_str(&text);
+        return;
+    }
+
+    let mut words = text.split(' ').peekable();
+    while let Some(word) = words.next() {
+        if word.starts_with("http://") || word.starts_with("https://") {
+            let url = word.trim_end_matches(|c: char| !c.is_alphanumeric() && c != '/');
+            output.push('<');
+            output.push_str(url);
+            output.push('>');
+            output.push_str(&word[url.len()..]);
+        } else {
+            output.push_str(word);
+        }
+
+        if words.peek().is_some() {
+            output.push(' ');
+        }
+    }
+}
+
+/// Returns the byte range of the next tag in `text`, including the brackets. Brackets that
+/// contain other brackets aren't tags.
+fn next_tag(text: &str) -> Option<(usize, usize)> {
+    let mut start = text.find('[')?;
+
+    loop {
+        let end = start + text[start..].find(']')?;
+
+        match text[start + 1..end].rfind('[') {
+            Some(nested) => start += 1 + nested,
+            None => return Some((start, end + 1)),
+        }
+    }
+}
+
+struct Links<'a> {
+    api: &'a Api,
+    docs: &'a GodotXMLDocs,
+    class: &'a str,
+}
+
+impl<'a> Links<'a> {
+    /// Translates a tag that isn't markup, which is either a reference or literal text in
+    /// brackets.
+    fn translate(&self, tag: &str) -> String {
+        let mut parts = tag.splitn(2, ' ');
+        let (kind, target) = match (parts.next(), parts.next()) {
+            (Some(kind), Some(target)) => (kind, target),
+            _ => return self.class_link(tag).unwrap_or_else(|| escape(tag)),
+        };
+
+        let (class, name) = match target.rfind('.') {
+            Some(dot) => (class_name_from_docs(&target[..dot]), &target[dot + 1..]),
+            None => (self.class, target),
+        };
+
+        let link = match kind {
+            "method" => self.method_link(class, name),
+            "member" => self.member_link(class, name),
+            "signal" => self.signal_link(class, name),
+            "constant" => self.constant_link(class, name),
+            "enum" => self.enum_link(class, name),
+            _ => return escape(tag),
+        };
+
+        link.unwrap_or_else(|| format!("`{}`", name))
+    }
+
+    fn class_link(&self, name: &str) -> Option<String> {
+        if !is_identifier(name) {
+            return None;
+        }
+
+        match self.api.find_class(name) {
+            Some(class) => Some(format!(
+                "[`{name}`](crate::{module}::{name})",
+                name = class.name,
+                module = class.module(),
+            )),
+            // Builtin types, and classes that aren't generated.
+            None if name.starts_with(char::is_uppercase) || is_primitive(name) => {
+                Some(format!("`{}`", name))
+            }
+            None => None,
+        }
+    }
+
+    fn method_link(&self, class: &str, name: &str) -> Option<String> {
+        let name = name.trim_start_matches('_');
+
+        self.find_in_hierarchy(class, |class| {
+            let method = class.methods.iter().find(|method| {
+                let rust_name = method.get_name().rust_name;
+                rust_name == name && !skip_method(method, rust_name)
+            })?;
+
+            Some(item_link(
+                class,
+                rename_property_getter(method.get_name().rust_name, class),
+            ))
+        })
+    }
+
+    fn member_link(&self, class: &str, name: &str) -> Option<String> {
+        self.find_in_hierarchy(class, |class| {
+            let property = class
+                .properties
+                .iter()
+                .find(|property| property.name == name)?;
+
+            let getter = class.methods.iter().find(|method| {
+                method.name == property.getter
+                    && method.arguments.is_empty()
+                    && !skip_method(method, method.get_name().rust_name)
+            });
+
+            Some(match getter {
+                Some(getter) => item_link(
+                    class,
+                    rename_property_getter(getter.get_name().rust_name, class),
+                ),
+                None => format!(
+                    "[`{name}`](crate::{module}::{class}Property::{variant})",
+                    name = name,
+                    module = class.module(),
+                    class = class.name,
+                    variant = name.replace('/', "_").to_camel_case(),
+                ),
+            })
+        })
+    }
+
+    fn signal_link(&self, class: &str, name: &str) -> Option<String> {
+        self.find_in_hierarchy(class, |class| {
+            if self.docs.has_signal(&class.name, name) {
+                Some(format!(
+                    "[`\"{name}\"`](crate::{module}::{class}#signals)",
+                    name = name,
+                    module = class.module(),
+                    class = class.name,
+                ))
+            } else {
+                None
+            }
+        })
+    }
+
+    fn constant_link(&self, class: &str, name: &str) -> Option<String> {
+        let find = |class: &GodotClass| {
+            if class.constants.contains_key(name) {
+                Some(item_link(class, name))
+            } else {
+                None
+            }
+        };
+
+        self.find_in_hierarchy(class, find)
+            .or_else(|| self.find_in_hierarchy("GlobalConstants", find))
+    }
+
+    fn enum_link(&self, class: &str, name: &str) -> Option<String> {
+        let find = |class: &GodotClass| {
+            let e = class.enums.iter().find(|e| e.name == name)?;
+            Some(format!(
+                "[`{name}`](crate::{module}::{name})",
+                name = generate_enum_name(&class.name, &e.name),
+                module = class.module(),
+            ))
+        };
+
+        self.find_in_hierarchy(class, find)
+            .or_else(|| self.find_in_hierarchy("GlobalConstants", find))
+    }
+
+    /// Calls `find` on `class` and its base classes until it returns `Some`.
+    fn find_in_hierarchy(
+        &self,
+        class: &str,
+        find: impl Fn(&GodotClass) -> Option<String>,
+    ) -> Option<String> {
+        let mut class = self.api.find_class(class);
+
+        while let Some(c) = class {
+            if let Some(link) = find(c) {
+                return Some(link);
+            }
+
+            class = c.base_class(self.api);
+        }
+
+        None
+    }
+}
+
+fn item_link(class: &GodotClass, name: &str) -> String {
+    format!(
+        "[`{name}`](crate::{module}::{class}::{name})",
+        name = name,
+        module = class.module(),
+        class = class.name,
+    )
+}
+
+fn is_identifier(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphabetic())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn is_primitive(name: &str) -> bool {
+    matches!(name, "bool" | "int" | "float")
+}
+
+fn escape(text: &str) -> String {
+    format!("\\[{}\\]", text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn translate(class: &str, bbcode: &str) -> String {
+        let api = Api::new(include_str!("../../gdnative-bindings/api.json"));
+        let docs = GodotXMLDocs::new("../gdnative-bindings/docs");
+        to_rustdoc(&api, &docs, class, bbcode)
+    }
+
+    #[test]
+    fn markup() {
+        assert_eq!(
+            "**Note:** Returns `null` and *not* `[1, 2]`.",
+            translate(
+                "Node",
+                "[b]Note:[/b] Returns [code]null[/code] and [i]not[/i] [code][1, 2][/code]."
+            ),
+        );
+        assert_eq!(
+            "First paragraph.\n\nSee [the tutorials](https://example.com) or <https://example.com>.",
+            translate(
+                "Node",
+                "First paragraph.\nSee [url=https://example.com]the tutorials[/url] or [url]https://example.com[/url].",
+            ),
+        );
+        assert_eq!(
+            "Within \\[0, 1\\] or \\[ \\].",
+            translate("Node", "Within [0, 1] or [ ]."),
+        );
+        assert_eq!(
+            "Calls \\<method> (see <https://example.com/a>).",
+            translate("Node", "Calls <method> (see https://example.com/a)."),
+        );
+    }
+
+    #[test]
+    fn codeblock() {
+        assert_eq!(
+            "_Sample code is GDScript unless otherwise noted._\n\n\
+            Example:\n\n```gdscript\nvar a = [method b]\nprint(a[0])\n```\n\nDone.",
+            translate(
+                "Node",
+                "Example:\n[codeblock]\nvar a = [method b]\nprint(a[0])\n[/codeblock]\nDone.",
+            ),
+        );
+    }
+
+    #[test]
+    fn links() {
+        assert_eq!(
+            "[`Node2D`](crate::node_2d::Node2D), `Vector2`, `int`",
+            translate("Node", "[Node2D], [Vector2], [int]"),
+        );
+
+        // Methods are linked to the class that defines them, and getters are renamed.
+        assert_eq!(
+            "[`add_child`](crate::node::Node::add_child) \
+            [`name`](crate::node::Node::name) \
+            [`position`](crate::node_2d::Node2D::position)",
+            translate(
+                "Node2D",
+                "[method add_child] [method get_name] [method get_position]"
+            ),
+        );
+
+        // Virtual methods aren't generated.
+        assert_eq!("`_ready`", translate("Node", "[method _ready]"));
+
+        assert_eq!(
+            "[`position`](crate::node_2d::Node2D::position) \
+            [`\"tree_entered\"`](crate::node::Node#signals)",
+            translate("Sprite", "[member Node2D.position] [signal tree_entered]"),
+        );
+
+        assert_eq!(
+            "[`NOTIFICATION_READY`](crate::node::Node::NOTIFICATION_READY) \
+            [`PauseMode`](crate::node::PauseMode) \
+            [`Margin`](crate::global_constants::Margin) \
+            [`KEY_A`](crate::global_constants::GlobalConstants::KEY_A)",
+            translate(
+                "Node",
+                "[constant NOTIFICATION_READY] [enum PauseMode] [enum Margin] [constant @GlobalScope.KEY_A]",
+            ),
+        );
+    }
+}
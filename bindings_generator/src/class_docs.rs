@@ -2,9 +2,32 @@ use std::{collections::HashMap, fs};
 
 use roxmltree::Node;
 
+use crate::api::Api;
+use crate::bbcode;
+
+/// Documentation of the classes in the Godot API, parsed from the XML files of the engine.
+///
+/// Descriptions are kept in the BBCode markup used by the engine, and translated to rustdoc with
+/// links to the generated items when they're retrieved.
 #[derive(Debug)]
 pub struct GodotXMLDocs {
+    class_desc: HashMap<String, String>,
     class_fn_desc: HashMap<(String, String), String>,
+    class_fn_default_args: HashMap<(String, String), Vec<(String, String)>>,
+    member_desc: HashMap<(String, String), String>,
+    signals: HashMap<String, Vec<SignalDoc>>,
+    constant_desc: HashMap<(String, String), String>,
+    enum_value_desc: HashMap<(String, String), Vec<(String, String)>>,
+}
+
+/// Documentation of a signal.
+#[derive(Debug)]
+pub(crate) struct SignalDoc {
+    pub name: String,
+    /// Names and types of the arguments.
+    pub arguments: Vec<(String, String)>,
+    /// Description in BBCode.
+    pub description: String,
 }
 
 impl GodotXMLDocs {
@@ -15,7 +38,13 @@ impl GodotXMLDocs {
             .collect::<Vec<_>>();
 
         let mut docs = GodotXMLDocs {
+            class_desc: HashMap::default(),
             class_fn_desc: HashMap::default(),
+            class_fn_default_args: HashMap::default(),
+            member_desc: HashMap::default(),
+            signals: HashMap::default(),
+            constant_desc: HashMap::default(),
+            enum_value_desc: HashMap::default(),
         };
 
         for entry in entries {
@@ -29,9 +58,92 @@ impl GodotXMLDocs {
         docs
     }
 
-    pub fn get_class_method_desc(&self, class: &str, method: &str) -> Option<&str> {
+    /// Returns the description of `class`, translated to rustdoc.
+    pub fn get_class_desc(&self, api: &Api, class: &str) -> Option<String> {
+        let desc = self.class_desc.get(class)?;
+        Some(bbcode::to_rustdoc(api, self, class, desc))
+    }
+
+    /// Returns the description of `method` in `class`, translated to rustdoc, followed by the
+    /// default values of its arguments. Property getters and setters are described by the
+    /// documentation of their property.
+    pub fn get_class_method_desc(&self, api: &Api, class: &str, method: &str) -> Option<String> {
         let key = (class.to_string(), method.to_string());
-        self.class_fn_desc.get(&key).map(|s| s.as_str())
+        let desc = self.class_fn_desc.get(&key);
+        let default_args = self.class_fn_default_args.get(&key);
+
+        if desc.is_none() && default_args.is_none() {
+            return None;
+        }
+
+        let mut doc = desc
+            .map(|desc| bbcode::to_rustdoc(api, self, class, desc))
+            .unwrap_or_default();
+
+        if let Some(default_args) = default_args {
+            doc.push_str("\n# Default Arguments");
+
+            for arg in default_args {
+                doc.push_str(format!("\n* `{}` - `{}`", arg.0, arg.1).as_str());
+            }
+        }
+
+        Some(doc)
+    }
+
+    /// Returns the description of the property `member` in `class`, translated to rustdoc.
+    pub fn get_member_desc(&self, api: &Api, class: &str, member: &str) -> Option<String> {
+        let desc = self
+            .member_desc
+            .get(&(class.to_string(), member.to_string()))?;
+        Some(bbcode::to_rustdoc(api, self, class, desc))
+    }
+
+    /// Returns the documentation of the signals of `class`.
+    pub(crate) fn get_signals(&self, class: &str) -> &[SignalDoc] {
+        self.signals.get(class).map_or(&[], |signals| &signals[..])
+    }
+
+    /// Returns the description of the constant `name` in `class`, translated to rustdoc.
+    /// Constants of the global scope are part of `GlobalConstants`.
+    pub fn get_constant_desc(&self, api: &Api, class: &str, name: &str) -> Option<String> {
+        let desc = self
+            .constant_desc
+            .get(&(class.to_string(), name.to_string()))?;
+        Some(bbcode::to_rustdoc(api, self, class, desc))
+    }
+
+    /// Returns the description of the value `name` of the enum `enum_name` in `class`,
+    /// translated to rustdoc. `name` is the name of the generated value, without the prefix
+    /// shared by all values of the enum.
+    pub fn get_enum_value_desc(
+        &self,
+        api: &Api,
+        class: &str,
+        enum_name: &str,
+        name: &str,
+    ) -> Option<String> {
+        let values = self
+            .enum_value_desc
+            .get(&(class.to_string(), enum_name.to_string()))?;
+
+        // Numeric names are prefixed with `_` by the generator. The shortest match is the one
+        // with only the common prefix, e.g. `KEY_0` rather than `KEY_KP_0` for `_0`.
+        let name = name.trim_start_matches('_');
+        let (_, desc) = values
+            .iter()
+            .filter(|(constant, _)| {
+                constant == name
+                    || (constant.ends_with(name)
+                        && constant[..constant.len() - name.len()].ends_with('_'))
+            })
+            .min_by_key(|(constant, _)| constant.len())?;
+
+        Some(bbcode::to_rustdoc(api, self, class, desc))
+    }
+
+    pub(crate) fn has_signal(&self, class: &str, signal: &str) -> bool {
+        self.get_signals(class).iter().any(|s| s.name == signal)
     }
 
     fn parse_file(&mut self, file_content: &str) {
@@ -42,6 +154,10 @@ impl GodotXMLDocs {
             .find(|node| node.tag_name().name() == "class")
         {
             if let Some(class_name) = class.attribute("name") {
+                let class_name = class_name_from_docs(class_name);
+
+                self.parse_class_desc(class_name, class);
+
                 let methods_node = class
                     .descendants()
                     .find(|node| node.tag_name().name() == "methods");
@@ -51,10 +167,42 @@ impl GodotXMLDocs {
                     .descendants()
                     .find(|node| node.tag_name().name() == "members");
                 self.parse_members(class_name, members_node);
+
+                let signals_node = class
+                    .descendants()
+                    .find(|node| node.tag_name().name() == "signals");
+                self.parse_signals(class_name, signals_node);
+
+                let constants_node = class
+                    .descendants()
+                    .find(|node| node.tag_name().name() == "constants");
+                self.parse_constants(class_name, constants_node);
             }
         }
     }
 
+    fn parse_class_desc(&mut self, class: &str, node: Node) {
+        let text_of = |tag: &str| {
+            node.children()
+                .find(|node| node.tag_name().name() == tag)
+                .and_then(|node| node.text())
+                .map(|text| unindent::unindent(text.trim()))
+                .unwrap_or_default()
+        };
+
+        let brief = text_of("brief_description");
+        let desc = text_of("description");
+
+        let doc = match (brief.is_empty(), desc.is_empty()) {
+            (true, true) => return,
+            (false, true) => brief,
+            (true, false) => desc,
+            (false, false) => format!("{}\n{}", brief, desc),
+        };
+
+        self.class_desc.insert(class.into(), doc);
+    }
+
     fn parse_members(&mut self, class: &str, members: Option<Node>) {
         if let Some(members) = members {
             for node in members.descendants() {
@@ -66,6 +214,9 @@ impl GodotXMLDocs {
                         if let Some(func) = node.attribute("getter") {
                             self.add_fn(class, func, desc, &[]);
                         }
+                        if let Some(name) = node.attribute("name") {
+                            add_desc(&mut self.member_desc, class, name, desc);
+                        }
                     }
                 }
             }
@@ -109,44 +260,95 @@ impl GodotXMLDocs {
         }
     }
 
-    fn add_fn(&mut self, class: &str, method: &str, desc: &str, default_args: &[(&str, &str)]) {
-        let mut doc = unindent::unindent(desc.trim());
+    fn parse_signals(&mut self, class: &str, signals: Option<Node>) {
+        if let Some(signals) = signals {
+            for node in signals.children() {
+                if node.tag_name().name() != "signal" {
+                    continue;
+                }
+
+                if let Some(name) = node.attribute("name") {
+                    let arguments = node
+                        .children()
+                        .filter(|node| node.tag_name().name() == "argument")
+                        .filter_map(|node| {
+                            let name = node.attribute("name")?;
+                            let ty = node.attribute("type")?;
+                            Some((name.to_string(), ty.to_string()))
+                        })
+                        .collect();
 
-        if doc.is_empty() && default_args.is_empty() {
-            return;
+                    let description = node
+                        .children()
+                        .find(|node| node.tag_name().name() == "description")
+                        .and_then(|node| node.text())
+                        .map(|text| unindent::unindent(text.trim()))
+                        .unwrap_or_default();
+
+                    self.signals
+                        .entry(class.into())
+                        .or_default()
+                        .push(SignalDoc {
+                            name: name.into(),
+                            arguments,
+                            description,
+                        });
+                }
+            }
         }
+    }
 
-        if !default_args.is_empty() {
-            doc.push_str("\n# Default Arguments");
+    fn parse_constants(&mut self, class: &str, constants: Option<Node>) {
+        if let Some(constants) = constants {
+            for node in constants.children() {
+                if node.tag_name().name() != "constant" {
+                    continue;
+                }
 
-            for arg in default_args {
-                doc.push_str(format!("\n* `{}` - `{}`", arg.0, arg.1).as_str());
+                if let (Some(name), Some(desc)) = (node.attribute("name"), node.text()) {
+                    add_desc(&mut self.constant_desc, class, name, desc);
+
+                    if let Some(enum_name) = node.attribute("enum") {
+                        let desc = unindent::unindent(desc.trim());
+                        if !desc.is_empty() {
+                            self.enum_value_desc
+                                .entry((class.into(), enum_name.into()))
+                                .or_default()
+                                .push((name.into(), desc));
+                        }
+                    }
+                }
             }
         }
+    }
 
-        self.class_fn_desc.insert(
-            (class.into(), method.into()),
-            Self::reformat_as_rustdoc(doc),
-        );
+    fn add_fn(&mut self, class: &str, method: &str, desc: &str, default_args: &[(&str, &str)]) {
+        add_desc(&mut self.class_fn_desc, class, method, desc);
+
+        if !default_args.is_empty() {
+            let default_args = default_args
+                .iter()
+                .map(|&(name, value)| (name.to_string(), value.to_string()))
+                .collect();
+
+            self.class_fn_default_args
+                .insert((class.into(), method.into()), default_args);
+        }
     }
+}
 
-    /// Takes the Godot documentation markup and transforms it to Rustdoc.
-    /// Very basic approach with limitations, but already helps readability quite a bit.
-    fn reformat_as_rustdoc(godot_doc: String) -> String {
-        let gdscript_note = if godot_doc.contains("[codeblock]") {
-            "_Sample code is GDScript unless otherwise noted._\n\n"
-        } else {
-            ""
-        };
+fn add_desc(descs: &mut HashMap<(String, String), String>, class: &str, name: &str, desc: &str) {
+    let desc = unindent::unindent(desc.trim());
 
-        let translated = godot_doc
-            .replace("[code]", "`")
-            .replace("[/code]", "`")
-            .replace("[codeblock]", "```gdscript")
-            .replace("[/codeblock]", "```")
-            .replace("[b]", "**")
-            .replace("[/b]", "**");
+    if !desc.is_empty() {
+        descs.insert((class.into(), name.into()), desc);
+    }
+}
 
-        format!("{}{}", gdscript_note, translated)
+/// Returns the name of the class in the API description for a class name used in the docs.
+pub(crate) fn class_name_from_docs(name: &str) -> &str {
+    match name {
+        "@GlobalScope" => "GlobalConstants",
+        name => name,
     }
 }
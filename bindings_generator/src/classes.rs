@@ -1,6 +1,7 @@
 use crate::api::*;
 use crate::class_docs::GodotXMLDocs;
 use crate::methods;
+use crate::properties;
 use crate::special_methods;
 
 use heck::CamelCase as _;
@@ -22,12 +23,13 @@ pub(crate) fn generate_class_struct(class: &GodotClass) -> TokenStream {
 }
 
 pub(crate) fn generate_class_impl(
+    api: &Api,
     class: &GodotClass,
     icalls: &mut HashMap<String, methods::MethodSig>,
     docs: Option<&GodotXMLDocs>,
 ) -> TokenStream {
     let class_singleton = if class.singleton {
-        special_methods::generate_singleton_getter(class)
+        special_methods::generate_singleton_getter(api, class)
     } else {
         Default::default()
     };
@@ -44,7 +46,9 @@ pub(crate) fn generate_class_impl(
         Default::default()
     };
 
-    let class_methods = methods::generate_methods(class, icalls, docs);
+    let class_methods = methods::generate_methods(api, class, icalls, docs);
+
+    let property_accessors = properties::generate_property_accessors(class);
 
     let class_name = format_ident!("{}", class.name);
     quote! {
@@ -53,11 +57,16 @@ pub(crate) fn generate_class_impl(
             #class_singleton_getter
             #class_instanciable
             #class_methods
+            #property_accessors
         }
     }
 }
 
-pub(crate) fn generate_class_constants(class: &GodotClass) -> TokenStream {
+pub(crate) fn generate_class_constants(
+    api: &Api,
+    class: &GodotClass,
+    docs: Option<&GodotXMLDocs>,
+) -> TokenStream {
     assert!(
         !class.constants.is_empty(),
         "Only call on class with constants."
@@ -70,8 +79,12 @@ pub(crate) fn generate_class_constants(class: &GodotClass) -> TokenStream {
     class_constants.sort_by(|a, b| a.0.cmp(&b.0));
 
     for (name, value) in &class_constants {
+        let doc = docs
+            .and_then(|docs| docs.get_constant_desc(api, &class.name, name))
+            .unwrap_or_default();
         let name = format_ident!("{}", name);
         let constant = quote! {
+            #[doc = #doc]
             pub const #name: i64 = #value;
         };
         constants.extend(constant);
@@ -88,7 +101,7 @@ pub(crate) fn generate_class_constants(class: &GodotClass) -> TokenStream {
     }
 }
 
-fn generate_enum_name(class_name: &str, enum_name: &str) -> String {
+pub(crate) fn generate_enum_name(class_name: &str, enum_name: &str) -> String {
     // In order to not pollute the API with more Result types,
     // rename the Result enum used by Search to SearchResult
     // to_camel_case() is used to make the enums more Rust like.
@@ -103,7 +116,11 @@ fn generate_enum_name(class_name: &str, enum_name: &str) -> String {
     }
 }
 
-pub(crate) fn generate_enums(class: &GodotClass) -> TokenStream {
+pub(crate) fn generate_enums(
+    api: &Api,
+    class: &GodotClass,
+    docs: Option<&GodotXMLDocs>,
+) -> TokenStream {
     // TODO: check whether the start of the variant name is
     // equal to the end of the enum name and if so don't repeat it
     // it. For example ImageFormat::Rgb8 instead of ImageFormat::FormatRgb8.
@@ -112,14 +129,29 @@ pub(crate) fn generate_enums(class: &GodotClass) -> TokenStream {
     let enums = enums.iter().map(|e| {
         let enum_name = generate_enum_name(&class.name, &e.name);
         let typ_name = format_ident!("{}", enum_name);
+        let variant_impls = generate_enum_variant_impls(&typ_name);
+
+        let mut values: Vec<_> = e
+            .values
+            .iter()
+            .map(|(key, val)| {
+                let doc = docs
+                    .and_then(|docs| docs.get_enum_value_desc(api, &class.name, &e.name, key))
+                    .unwrap_or_default();
+                (key, val, doc)
+            })
+            .collect();
+        values.sort_by(|a, b| a.1.cmp(b.1));
 
-        let mut values: Vec<_> = e.values.iter().collect();
-        values.sort_by(|a, b| a.1.cmp(&b.1));
+        if let Some(non_flag_values) = crate::flags::non_flag_values(&class.name, &e.name) {
+            return generate_flags(&typ_name, &values, non_flag_values, variant_impls);
+        }
 
-        let consts = values.iter().map(|(key, val)| {
+        let consts = values.iter().map(|(key, val, doc)| {
             let key = key.to_uppercase();
             let variant = format_ident!("{}", key);
             quote! {
+                #[doc = #doc]
                 pub const #variant: #typ_name = #typ_name(#val);
             }
         });
@@ -143,6 +175,7 @@ pub(crate) fn generate_enums(class: &GodotClass) -> TokenStream {
                     v.0
                 }
             }
+            #variant_impls
         }
     });
 
@@ -150,3 +183,84 @@ pub(crate) fn generate_enums(class: &GodotClass) -> TokenStream {
         #(#enums)*
     }
 }
+
+fn generate_flags(
+    typ_name: &proc_macro2::Ident,
+    values: &[(&String, &i64, String)],
+    non_flag_values: &[&str],
+    variant_impls: TokenStream,
+) -> TokenStream {
+    let (non_flags, flags): (Vec<_>, Vec<_>) = values
+        .iter()
+        .partition(|(key, _, _)| non_flag_values.contains(&key.as_str()));
+
+    let flags = flags.iter().map(|(key, val, doc)| {
+        let variant = format_ident!("{}", key.to_uppercase());
+        quote! {
+            #[doc = #doc]
+            const #variant = #val;
+        }
+    });
+
+    let non_flags = if non_flags.is_empty() {
+        TokenStream::new()
+    } else {
+        let consts = non_flags.iter().map(|(key, val, doc)| {
+            let variant = format_ident!("{}", key.to_uppercase());
+            quote! {
+                #[doc = #doc]
+                pub const #variant: i64 = #val;
+            }
+        });
+
+        quote! {
+            impl #typ_name {
+                #(#consts)*
+            }
+        }
+    };
+
+    quote! {
+        bitflags::bitflags! {
+            pub struct #typ_name: i64 {
+                #(#flags)*
+            }
+        }
+
+        #non_flags
+        /// Converts from the raw value. Bits that don't correspond to a flag are kept, so values
+        /// returned by the engine can be passed back unchanged.
+        impl From<i64> for #typ_name {
+            #[inline]
+            fn from(v: i64) -> Self {
+                unsafe { Self::from_bits_unchecked(v) }
+            }
+        }
+        impl From<#typ_name> for i64 {
+            #[inline]
+            fn from(v: #typ_name) -> Self {
+                v.bits()
+            }
+        }
+        #variant_impls
+    }
+}
+
+/// Implements the variant conversions of enums and flags through their raw `i64` values.
+fn generate_enum_variant_impls(typ_name: &proc_macro2::Ident) -> TokenStream {
+    quote! {
+        impl ToVariant for #typ_name {
+            #[inline]
+            fn to_variant(&self) -> Variant {
+                i64::from(*self).to_variant()
+            }
+        }
+        impl ToVariantEq for #typ_name {}
+        impl FromVariant for #typ_name {
+            #[inline]
+            fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+                i64::from_variant(variant).map(Self::from)
+            }
+        }
+    }
+}
@@ -23,6 +23,20 @@ pub fn strongly_connected_components(
     visited
 }
 
+/// Find the set of classes in `classes` and all classes they depend on, i.e. their base classes
+/// and the classes referenced in their methods, including the classes declaring enums used in
+/// method signatures.
+///
+/// Classes that don't exist in `api` are ignored.
+pub fn classes_with_dependencies<'a, I>(api: &Api, classes: I) -> HashSet<String>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    classes.into_iter().fold(HashSet::new(), |visited, class| {
+        strongly_connected_components(api, class, Some(visited))
+    })
+}
+
 fn visit(api: &Api, class: &GodotClass, visited: &mut HashSet<String>) {
     visited.insert(class.name.clone());
 
@@ -62,12 +76,12 @@ fn referenced_classes(api: &Api, class: &GodotClass) -> HashSet<String> {
 
     for method in &class.methods {
         // return
-        if let Some(ret_class) = api.find_class(&method.return_type) {
+        if let Some(ret_class) = class_of_type(api, &method.return_type) {
             classes.insert(ret_class.name.clone());
         }
 
         for arg in &method.arguments {
-            if let Some(ty) = api.find_class(&arg.ty) {
+            if let Some(ty) = class_of_type(api, &arg.ty) {
                 classes.insert(ty.name.clone());
             }
         }
@@ -75,3 +89,37 @@ fn referenced_classes(api: &Api, class: &GodotClass) -> HashSet<String> {
 
     classes
 }
+
+/// Returns the class `ty` refers to. Enums, e.g. `enum.Node::PauseMode`, refer to the class
+/// they're declared in, since they're generated in the module of that class.
+fn class_of_type<'a>(api: &'a Api, ty: &str) -> Option<&'a GodotClass> {
+    let name = match ty.strip_prefix("enum.") {
+        // Leading underscores aren't stripped from enum types, unlike class names.
+        Some(name) => name
+            .split("::")
+            .next()
+            .unwrap_or(name)
+            .trim_start_matches('_'),
+        None => ty,
+    };
+
+    api.find_class(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classes_with_dependencies_includes_enum_classes() {
+        let api = Api::new(include_str!("../../gdnative-bindings/api.json"));
+        let classes = classes_with_dependencies(&api, vec!["HTTPRequest", "Unknown"]);
+
+        // `HTTPClient` is only referenced by `enum.HTTPClient::Status`.
+        assert!(classes.contains("HTTPClient"));
+        assert!(classes.contains("Node"));
+        assert!(classes.contains("Object"));
+        assert!(!classes.contains("Unknown"));
+        assert!(!classes.contains("WebRTCPeerConnection"));
+    }
+}
@@ -1,4 +1,6 @@
 use crate::api::*;
+use crate::bbcode;
+use crate::class_docs::GodotXMLDocs;
 use crate::GeneratorResult;
 
 use proc_macro2::TokenStream;
@@ -17,7 +19,11 @@ pub fn official_doc_url(class: &GodotClass) -> String {
     )
 }
 
-pub fn generate_class_documentation(api: &Api, class: &GodotClass) -> TokenStream {
+pub fn generate_class_documentation(
+    api: &Api,
+    class: &GodotClass,
+    docs: Option<&GodotXMLDocs>,
+) -> TokenStream {
     let has_parent = class.base_class != "";
     let singleton_str = if class.singleton { "singleton " } else { "" };
     let ownership_type = if class.is_refcounted() {
@@ -49,11 +55,20 @@ pub fn generate_class_documentation(api: &Api, class: &GodotClass) -> TokenStrea
         )
     };
 
+    let description_docs = docs
+        .and_then(|docs| docs.get_class_desc(api, &class.name))
+        .map(|desc| format!("## Description\n\n{}", desc))
+        .unwrap_or_default();
+
+    let signals_docs = docs
+        .map(|docs| signals_documentation(api, docs, class))
+        .unwrap_or_default();
+
     let official_docs = format!(
         r#"## Official documentation
 
 See the [documentation of this class]({url}) in the Godot engine's official documentation.
-The method descriptions are generated from it and typically contain code samples in GDScript, not Rust."#,
+The descriptions are generated from it and typically contain code samples in GDScript, not Rust."#,
         url = official_doc_url(class),
     );
 
@@ -122,16 +137,67 @@ see the type-level documentation on `Ref`.
 
 [thread-safety]: https://docs.godotengine.org/en/stable/tutorials/threads/thread_safe_apis.html"#;
 
+    let thread_safety_doc = match class.thread_safety(api) {
+        ThreadSafety::ThreadSafe => None,
+        ThreadSafety::MainThreadOnly => Some(
+            r#"
+This class should only be used from the main thread, and implements the `MainThreadOnly`
+marker trait. Shared references to it can only be used with `Ref::assume_safe_on_main_thread`,
+which takes a `MainThreadToken`."#,
+        ),
+        ThreadSafety::Server => Some(
+            r#"
+This server can only be used from the main thread, unless thread-safe operations are enabled
+in the project settings. `godot_singleton_main_thread` can be used safely from the main thread."#,
+        ),
+    }
+    .map(|doc| quote! { #[doc=#doc] });
+
     quote! {
         #[doc=#summary_doc]
+        #[doc=#description_docs]
+        #[doc=#signals_docs]
         #[doc=#official_docs]
         #[doc=#memory_management_docs]
         #[doc=#base_class_docs]
         #[doc=#tools_docs]
         #[doc=#safety_doc]
+        #thread_safety_doc
     }
 }
 
+fn signals_documentation(api: &Api, docs: &GodotXMLDocs, class: &GodotClass) -> String {
+    let signals = docs.get_signals(&class.name);
+    if signals.is_empty() {
+        return String::new();
+    }
+
+    let mut doc = String::from("## Signals\n\nSignals can be connected to with `Object::connect`.");
+
+    for signal in signals {
+        let arguments = signal
+            .arguments
+            .iter()
+            .map(|(name, ty)| format!("{}: {}", name, ty))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        doc.push_str(&format!("\n\n### `\"{}\"` ({})", signal.name, arguments));
+
+        if !signal.description.is_empty() {
+            doc.push_str("\n\n");
+            doc.push_str(&bbcode::to_rustdoc(
+                api,
+                docs,
+                &class.name,
+                &signal.description,
+            ));
+        }
+    }
+
+    doc
+}
+
 fn list_base_classes(output: &mut impl Write, api: &Api, parent_name: &str) -> GeneratorResult {
     if let Some(parent) = api.find_class(parent_name) {
         let class_link = class_doc_link(&parent);
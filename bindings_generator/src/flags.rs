@@ -0,0 +1,312 @@
+//! Enums that are sets of flags, which are generated as `bitflags` types.
+//!
+//! The API description doesn't distinguish flags from other enums, and declares arguments and
+//! return values that take flags as plain `int`s, so both are listed here.
+
+/// Enums that are sets of flags, as `(class, enum, values)`, where `values` are the names of
+/// values that aren't flags, after stripping their common prefix. These are generated as
+/// associated `i64` constants instead.
+const FLAGS_ENUMS: &[(&str, &str, &[&str])] = &[
+    ("ARVRInterface", "Capabilities", &[]),
+    ("Control", "SizeFlags", &[]),
+    ("CubeMap", "Flags", &[]),
+    ("GlobalConstants", "KeyModifierMask", &[]),
+    ("GlobalConstants", "MethodFlags", &[]),
+    ("GlobalConstants", "PropertyUsageFlags", &[]),
+    ("Mesh", "ArrayFormat", &["COMPRESS_BASE"]),
+    ("Node", "DuplicateFlags", &[]),
+    ("Object", "ConnectFlags", &[]),
+    ("PhysicsServer", "BodyAxis", &[]),
+    ("ResourceSaver", "SaverFlags", &[]),
+    ("SceneTree", "GroupCallFlags", &[]),
+    ("TextEdit", "SearchFlags", &[]),
+    ("Texture", "Flags", &[]),
+    ("TextureLayered", "Flags", &[]),
+    ("TileSet", "AutotileBindings", &[]),
+    ("Tree", "DropModeFlags", &[]),
+    ("VisualServer", "ArrayFormat", &[]),
+    ("VisualServer", "TextureFlags", &[]),
+];
+
+/// Method arguments that take flags, as `(class, method, argument, flags)`, where `argument` is
+/// `None` for the return value, and `flags` is the type of the flags in the form used by the
+/// API description, e.g. `enum.Object::ConnectFlags`.
+pub(crate) const FLAGS_ARGUMENTS: &[(&str, &str, Option<&str>, &str)] = &[
+    (
+        "ARVRInterface",
+        "get_capabilities",
+        None,
+        "enum.ARVRInterface::Capabilities",
+    ),
+    (
+        "ArrayMesh",
+        "add_surface_from_arrays",
+        Some("compress_flags"),
+        "enum.Mesh::ArrayFormat",
+    ),
+    (
+        "ArrayMesh",
+        "surface_get_format",
+        None,
+        "enum.Mesh::ArrayFormat",
+    ),
+    (
+        "Control",
+        "get_h_size_flags",
+        None,
+        "enum.Control::SizeFlags",
+    ),
+    (
+        "Control",
+        "get_v_size_flags",
+        None,
+        "enum.Control::SizeFlags",
+    ),
+    (
+        "Control",
+        "set_h_size_flags",
+        Some("flags"),
+        "enum.Control::SizeFlags",
+    ),
+    (
+        "Control",
+        "set_v_size_flags",
+        Some("flags"),
+        "enum.Control::SizeFlags",
+    ),
+    ("CubeMap", "get_flags", None, "enum.CubeMap::Flags"),
+    ("CubeMap", "set_flags", Some("flags"), "enum.CubeMap::Flags"),
+    (
+        "ImageTexture",
+        "create",
+        Some("flags"),
+        "enum.Texture::Flags",
+    ),
+    (
+        "ImageTexture",
+        "create_from_image",
+        Some("flags"),
+        "enum.Texture::Flags",
+    ),
+    (
+        "KinematicBody",
+        "get_axis_lock",
+        Some("axis"),
+        "enum.PhysicsServer::BodyAxis",
+    ),
+    (
+        "KinematicBody",
+        "set_axis_lock",
+        Some("axis"),
+        "enum.PhysicsServer::BodyAxis",
+    ),
+    ("MeshDataTool", "get_format", None, "enum.Mesh::ArrayFormat"),
+    (
+        "Node",
+        "duplicate",
+        Some("flags"),
+        "enum.Node::DuplicateFlags",
+    ),
+    (
+        "Object",
+        "connect",
+        Some("flags"),
+        "enum.Object::ConnectFlags",
+    ),
+    (
+        "PhysicsServer",
+        "body_is_axis_locked",
+        Some("axis"),
+        "enum.PhysicsServer::BodyAxis",
+    ),
+    (
+        "PhysicsServer",
+        "body_set_axis_lock",
+        Some("axis"),
+        "enum.PhysicsServer::BodyAxis",
+    ),
+    (
+        "ResourceSaver",
+        "save",
+        Some("flags"),
+        "enum.ResourceSaver::SaverFlags",
+    ),
+    (
+        "RigidBody",
+        "get_axis_lock",
+        Some("axis"),
+        "enum.PhysicsServer::BodyAxis",
+    ),
+    (
+        "RigidBody",
+        "set_axis_lock",
+        Some("axis"),
+        "enum.PhysicsServer::BodyAxis",
+    ),
+    (
+        "SceneTree",
+        "call_group_flags",
+        Some("flags"),
+        "enum.SceneTree::GroupCallFlags",
+    ),
+    (
+        "SceneTree",
+        "notify_group_flags",
+        Some("call_flags"),
+        "enum.SceneTree::GroupCallFlags",
+    ),
+    (
+        "SceneTree",
+        "set_group_flags",
+        Some("call_flags"),
+        "enum.SceneTree::GroupCallFlags",
+    ),
+    (
+        "SurfaceTool",
+        "commit",
+        Some("flags"),
+        "enum.Mesh::ArrayFormat",
+    ),
+    (
+        "TextEdit",
+        "search",
+        Some("flags"),
+        "enum.TextEdit::SearchFlags",
+    ),
+    ("Texture", "get_flags", None, "enum.Texture::Flags"),
+    ("Texture", "set_flags", Some("flags"), "enum.Texture::Flags"),
+    (
+        "TextureLayered",
+        "create",
+        Some("flags"),
+        "enum.TextureLayered::Flags",
+    ),
+    (
+        "TextureLayered",
+        "get_flags",
+        None,
+        "enum.TextureLayered::Flags",
+    ),
+    (
+        "TextureLayered",
+        "set_flags",
+        Some("flags"),
+        "enum.TextureLayered::Flags",
+    ),
+    (
+        "TileSet",
+        "autotile_get_bitmask",
+        None,
+        "enum.TileSet::AutotileBindings",
+    ),
+    (
+        "TileSet",
+        "autotile_set_bitmask",
+        Some("flag"),
+        "enum.TileSet::AutotileBindings",
+    ),
+    (
+        "Tree",
+        "get_drop_mode_flags",
+        None,
+        "enum.Tree::DropModeFlags",
+    ),
+    (
+        "Tree",
+        "set_drop_mode_flags",
+        Some("flags"),
+        "enum.Tree::DropModeFlags",
+    ),
+    (
+        "VisualServer",
+        "mesh_add_surface_from_arrays",
+        Some("compress_format"),
+        "enum.VisualServer::ArrayFormat",
+    ),
+    (
+        "VisualServer",
+        "mesh_surface_get_format",
+        None,
+        "enum.VisualServer::ArrayFormat",
+    ),
+    (
+        "VisualServer",
+        "texture_allocate",
+        Some("flags"),
+        "enum.VisualServer::TextureFlags",
+    ),
+    (
+        "VisualServer",
+        "texture_create_from_image",
+        Some("flags"),
+        "enum.VisualServer::TextureFlags",
+    ),
+    (
+        "VisualServer",
+        "texture_get_flags",
+        None,
+        "enum.VisualServer::TextureFlags",
+    ),
+    (
+        "VisualServer",
+        "texture_set_flags",
+        Some("flags"),
+        "enum.VisualServer::TextureFlags",
+    ),
+];
+
+/// Returns the names of the values that aren't flags if `enum_name` in `class_name` is a set of
+/// flags, or `None` otherwise.
+pub(crate) fn non_flag_values(
+    class_name: &str,
+    enum_name: &str,
+) -> Option<&'static [&'static str]> {
+    FLAGS_ENUMS
+        .iter()
+        .find(|(class, name, _)| *class == class_name && *name == enum_name)
+        .map(|(_, _, values)| *values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::Api;
+
+    #[test]
+    fn flags_arguments_exist() {
+        let api = Api::new(include_str!("../../gdnative-bindings/api.json"));
+
+        for &(class_name, method_name, arg_name, ty) in FLAGS_ARGUMENTS {
+            let method = api
+                .find_class(class_name)
+                .and_then(|class| class.methods.iter().find(|m| m.name == method_name))
+                .unwrap_or_else(|| panic!("{}::{} not found", class_name, method_name));
+
+            let actual = match arg_name {
+                Some(arg_name) => method
+                    .arguments
+                    .iter()
+                    .find(|arg| arg.name == arg_name)
+                    .map(|arg| arg.ty.as_str()),
+                None => Some(method.return_type.as_str()),
+            };
+
+            assert_eq!(Some(ty), actual, "{}::{}", class_name, method_name);
+
+            let mut enum_path = ty["enum.".len()..].split("::");
+            let (enum_class, enum_name) = (enum_path.next().unwrap(), enum_path.next().unwrap());
+            assert!(non_flag_values(enum_class, enum_name).is_some(), "{}", ty);
+        }
+
+        for &(class_name, enum_name, values) in FLAGS_ENUMS {
+            let e = api
+                .find_class(class_name)
+                .and_then(|class| class.enums.iter().find(|e| e.name == enum_name))
+                .unwrap_or_else(|| panic!("{}::{} not found", class_name, enum_name));
+
+            for value in values {
+                assert!(e.values.contains_key(*value), "{}", value);
+            }
+        }
+    }
+}
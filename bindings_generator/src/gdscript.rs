@@ -0,0 +1,450 @@
+//! A minimal parser for the declarations in GDScript files.
+//!
+//! Only the top level of a script is read: `extends`, `class_name`, signals, member variables
+//! and functions. Function bodies, inner classes and anything else that isn't needed to call
+//! into the script from Rust are skipped.
+
+use heck::CamelCase as _;
+
+use crate::script_bindings::{
+    ScriptArgument, ScriptClass, ScriptMethod, ScriptProperty, ScriptSignal,
+};
+
+/// Parses the declarations of the GDScript file at `path`, with the contents `source`.
+pub(crate) fn parse(path: &str, source: &str) -> ScriptClass {
+    let mut class = ScriptClass {
+        name: default_class_name(path),
+        base_class: "Reference".into(),
+        path: path.into(),
+        properties: Vec::new(),
+        methods: Vec::new(),
+        signals: Vec::new(),
+    };
+
+    for statement in top_level_statements(source) {
+        let mut words = statement.splitn(2, char::is_whitespace);
+        let keyword = words.next().unwrap_or_default();
+        let rest = words.next().unwrap_or_default().trim();
+
+        match keyword {
+            "extends" => class.base_class = parse_extends(rest),
+            "class_name" => {
+                let name = rest.split(',').next().unwrap_or_default().trim();
+                if !name.is_empty() {
+                    class.name = name.into();
+                }
+            }
+            "signal" => class.signals.push(parse_signal(rest)),
+            _ => {
+                if let Some(property) = parse_var(&statement) {
+                    class.properties.push(property);
+                } else if let Some(method) = parse_func(&statement) {
+                    class.methods.push(method);
+                }
+            }
+        }
+    }
+
+    class
+}
+
+/// Returns the class name used for scripts without `class_name`, from the name of the file.
+fn default_class_name(path: &str) -> String {
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    let stem = file_name.split('.').next().unwrap_or(file_name);
+    stem.to_camel_case()
+}
+
+/// Splits `source` into statements at the top level of the script, without comments.
+/// Statements spanning multiple lines within brackets are joined.
+fn top_level_statements(source: &str) -> Vec<String> {
+    let mut statements = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0_i32;
+
+    for line in source.lines() {
+        let line = strip_comment(line);
+
+        if depth == 0 {
+            if line.starts_with(char::is_whitespace) || line.trim().is_empty() {
+                continue;
+            }
+            current.clear();
+        } else {
+            current.push(' ');
+        }
+
+        current.push_str(line.trim());
+        depth += bracket_depth(line);
+
+        if depth <= 0 {
+            depth = 0;
+            statements.push(current.clone());
+        }
+    }
+
+    statements
+}
+
+/// Removes the comment from `line`, if any.
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+
+    for (i, c) in line.char_indices() {
+        match (quote, c) {
+            (None, '"') | (None, '\'') => quote = Some(c),
+            (Some(q), c) if c == q => quote = None,
+            (None, '#') => return &line[..i],
+            _ => {}
+        }
+    }
+
+    line
+}
+
+/// Returns the change of bracket nesting depth over `line`, ignoring brackets in strings.
+fn bracket_depth(line: &str) -> i32 {
+    let mut quote = None;
+    let mut depth = 0;
+
+    for c in line.chars() {
+        match (quote, c) {
+            (None, '"') | (None, '\'') => quote = Some(c),
+            (Some(q), c) if c == q => quote = None,
+            (None, '(') | (None, '[') | (None, '{') => depth += 1,
+            (None, ')') | (None, ']') | (None, '}') => depth -= 1,
+            _ => {}
+        }
+    }
+
+    depth
+}
+
+/// Splits `text` at commas that aren't in brackets or strings.
+fn split_top_level(text: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut quote = None;
+    let mut depth = 0;
+    let mut start = 0;
+
+    for (i, c) in text.char_indices() {
+        match (quote, c) {
+            (None, '"') | (None, '\'') => quote = Some(c),
+            (Some(q), c) if c == q => quote = None,
+            (None, '(') | (None, '[') | (None, '{') => depth += 1,
+            (None, ')') | (None, ']') | (None, '}') => depth -= 1,
+            (None, ',') if depth == 0 => {
+                parts.push(text[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+
+    let last = text[start..].trim();
+    if !last.is_empty() {
+        parts.push(last);
+    }
+
+    parts
+}
+
+/// Returns the contents of the outermost brackets after the start of `text`, and the text
+/// after the closing bracket.
+fn split_brackets(text: &str) -> Option<(&str, &str)> {
+    let open = text.find('(')?;
+    let mut depth = 0;
+
+    for (i, c) in text[open..].char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    let close = open + i;
+                    return Some((&text[open + 1..close], &text[close + 1..]));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+fn parse_extends(rest: &str) -> String {
+    let rest = rest.trim();
+
+    // `extends "res://base.gd"`, or an inner class of it, which is treated as the script.
+    if let Some(path) = rest.strip_prefix('"') {
+        return path.split('"').next().unwrap_or_default().into();
+    }
+
+    rest.split(|c: char| c.is_whitespace() || c == '.')
+        .next()
+        .unwrap_or_default()
+        .into()
+}
+
+fn parse_signal(rest: &str) -> ScriptSignal {
+    let (name, arguments) = match split_brackets(rest) {
+        Some((arguments, _)) => (&rest[..rest.find('(').unwrap()], parse_arguments(arguments)),
+        None => (rest, Vec::new()),
+    };
+
+    ScriptSignal {
+        name: name.trim().into(),
+        arguments,
+    }
+}
+
+/// Parses a member variable declaration, e.g. `export(int, 0, 10) onready var health := 10`.
+fn parse_var(statement: &str) -> Option<ScriptProperty> {
+    let mut export_ty = None;
+    let mut rest = statement;
+
+    loop {
+        let word = rest.split(|c: char| c.is_whitespace() || c == '(').next()?;
+
+        match word {
+            "var" => {
+                rest = rest[3..].trim_start();
+                break;
+            }
+            "export" => {
+                rest = &rest[6..];
+                if rest.trim_start().starts_with('(') {
+                    let (hint, after) = split_brackets(rest)?;
+                    export_ty = split_top_level(hint).first().map(|ty| ty.to_string());
+                    rest = after;
+                }
+                rest = rest.trim_start();
+            }
+            "onready" | "remote" | "master" | "puppet" | "remotesync" | "mastersync"
+            | "puppetsync" => rest = rest[word.len()..].trim_start(),
+            _ => return None,
+        }
+    }
+
+    // Accessors declared with `setget` don't change how the property is accessed.
+    let declaration = rest.split(" setget").next().unwrap_or(rest);
+    let (declaration, value) = match declaration.find('=') {
+        Some(i) => (&declaration[..i], Some(declaration[i + 1..].trim())),
+        None => (declaration, None),
+    };
+
+    let mut parts = declaration.splitn(2, ':');
+    let name = parts.next()?.trim();
+    let hint = parts.next().map(str::trim).filter(|hint| !hint.is_empty());
+
+    let ty = hint
+        .map(String::from)
+        .or(export_ty)
+        .or_else(|| value.and_then(literal_type))
+        .unwrap_or_else(|| "Variant".into());
+
+    Some(ScriptProperty {
+        name: name.into(),
+        ty,
+    })
+}
+
+/// Parses a function declaration, e.g. `static func add(a: int, b := 1) -> int:`.
+fn parse_func(statement: &str) -> Option<ScriptMethod> {
+    let start = statement.find("func ")?;
+    let qualifiers = &statement[..start];
+    if !qualifiers.split_whitespace().all(|word| {
+        matches!(
+            word,
+            "static" | "remote" | "master" | "puppet" | "remotesync" | "mastersync" | "puppetsync"
+        )
+    }) {
+        return None;
+    }
+
+    let declaration = &statement[start + 5..];
+    let name = declaration[..declaration.find('(')?].trim();
+    let (arguments, rest) = split_brackets(declaration)?;
+
+    let return_type = match rest.find("->") {
+        Some(i) => rest[i + 2..].trim().trim_end_matches(':').trim(),
+        None => "Variant",
+    };
+
+    Some(ScriptMethod {
+        name: name.into(),
+        return_type: return_type.into(),
+        arguments: parse_arguments(arguments),
+    })
+}
+
+/// Parses the arguments of a function or signal, e.g. `a, b: int, c := 2.0`.
+fn parse_arguments(arguments: &str) -> Vec<ScriptArgument> {
+    split_top_level(arguments)
+        .into_iter()
+        .map(|argument| {
+            let (declaration, value) = match argument.find('=') {
+                Some(i) => (&argument[..i], Some(argument[i + 1..].trim())),
+                None => (argument, None),
+            };
+
+            let mut parts = declaration.splitn(2, ':');
+            let name = parts.next().unwrap_or_default().trim();
+            let ty = parts
+                .next()
+                .map(str::trim)
+                .filter(|ty| !ty.is_empty())
+                .map(String::from)
+                .or_else(|| value.and_then(literal_type))
+                .unwrap_or_else(|| "Variant".into());
+
+            ScriptArgument {
+                name: name.into(),
+                ty,
+            }
+        })
+        .collect()
+}
+
+/// Returns the type of a literal value, used for inferred types like `var a := 1`.
+fn literal_type(value: &str) -> Option<String> {
+    let ty = match value {
+        "true" | "false" => "bool",
+        value if value.starts_with('"') || value.starts_with('\'') => "String",
+        value if value.parse::<i64>().is_ok() => "int",
+        value if value.parse::<f64>().is_ok() => "float",
+        _ => return None,
+    };
+
+    Some(ty.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn arg(name: &str, ty: &str) -> ScriptArgument {
+        ScriptArgument {
+            name: name.into(),
+            ty: ty.into(),
+        }
+    }
+
+    fn property(name: &str, ty: &str) -> ScriptProperty {
+        ScriptProperty {
+            name: name.into(),
+            ty: ty.into(),
+        }
+    }
+
+    #[test]
+    fn parse_declarations() {
+        let source = r#"
+tool
+extends KinematicBody2D
+class_name Enemy, "res://enemy.svg"
+
+signal died
+signal hit(by: Node, damage) # Emitted when hit.
+
+const SPEED = 10.0
+
+export(int, 0, 100) var health = 100
+export var label := "Enemy"
+onready var sprite: Sprite = $Sprite
+var _private = 1
+var velocity = Vector2() setget set_velocity
+
+func _ready():
+    var unused = 0
+
+func take_damage(amount: int, source = null) -> void:
+    health -= amount
+
+static func lerp_health(
+    from: float,
+    to: float, # Comments are skipped.
+    weight := 0.5
+) -> float:
+    return from + (to - from) * weight
+
+func set_velocity(value):
+    velocity = value
+
+class Inner:
+    var skipped
+"#;
+
+        let class = parse("res://enemies/enemy.gd", source);
+
+        assert_eq!("Enemy", class.name);
+        assert_eq!("KinematicBody2D", class.base_class);
+        assert_eq!("res://enemies/enemy.gd", class.path);
+
+        assert_eq!(
+            vec![
+                ScriptSignal {
+                    name: "died".into(),
+                    arguments: vec![],
+                },
+                ScriptSignal {
+                    name: "hit".into(),
+                    arguments: vec![arg("by", "Node"), arg("damage", "Variant")],
+                },
+            ],
+            class.signals
+        );
+
+        assert_eq!(
+            vec![
+                property("health", "int"),
+                property("label", "String"),
+                property("sprite", "Sprite"),
+                property("_private", "int"),
+                property("velocity", "Variant"),
+            ],
+            class.properties
+        );
+
+        assert_eq!(
+            vec![
+                ScriptMethod {
+                    name: "_ready".into(),
+                    return_type: "Variant".into(),
+                    arguments: vec![],
+                },
+                ScriptMethod {
+                    name: "take_damage".into(),
+                    return_type: "void".into(),
+                    arguments: vec![arg("amount", "int"), arg("source", "Variant")],
+                },
+                ScriptMethod {
+                    name: "lerp_health".into(),
+                    return_type: "float".into(),
+                    arguments: vec![
+                        arg("from", "float"),
+                        arg("to", "float"),
+                        arg("weight", "float")
+                    ],
+                },
+                ScriptMethod {
+                    name: "set_velocity".into(),
+                    return_type: "Variant".into(),
+                    arguments: vec![arg("value", "Variant")],
+                },
+            ],
+            class.methods
+        );
+    }
+
+    #[test]
+    fn parse_defaults() {
+        let class = parse("res://player_controller.gd", "var speed\n");
+        assert_eq!("PlayerController", class.name);
+        assert_eq!("Reference", class.base_class);
+        assert_eq!(vec![property("speed", "Variant")], class.properties);
+
+        let class = parse("res://child.gd", "extends \"res://base.gd\".Inner\n");
+        assert_eq!("res://base.gd", class.base_class);
+    }
+}
@@ -0,0 +1,414 @@
+//! Enums of `@GlobalScope`, which are generated in the module of `GlobalConstants`.
+//!
+//! The API description only contains the values of these enums as loose constants of the
+//! `GlobalConstants` pseudo-class, so the constants belonging to each enum are listed here.
+//! `Error`, `Variant.Type` and `Variant.Operator` are left out, since they're already provided
+//! by `GodotError`, `VariantType` and `VariantOperator` in `gdnative-core`.
+
+use crate::api::{Api, Enum};
+
+/// Enums of `@GlobalScope`, as `(name, prefixes, excluded)`, where `prefixes` are prefixes
+/// of the names of the constants belonging to the enum, and `excluded` are prefixes of the
+/// names of constants that match `prefixes`, but belong to another enum.
+const GLOBAL_ENUMS: &[(&str, &[&str], &[&str])] = &[
+    ("ButtonList", &["BUTTON_"], &[]),
+    ("Corner", &["CORNER_"], &[]),
+    ("HAlign", &["HALIGN_"], &[]),
+    ("JoystickList", &["JOY_"], &[]),
+    (
+        "KeyList",
+        &["KEY_"],
+        &["KEY_MASK_", "KEY_CODE_MASK", "KEY_MODIFIER_MASK"],
+    ),
+    (
+        "KeyModifierMask",
+        &["KEY_MASK_", "KEY_CODE_MASK", "KEY_MODIFIER_MASK"],
+        &[],
+    ),
+    ("Margin", &["MARGIN_"], &[]),
+    ("MethodFlags", &["METHOD_FLAG"], &[]),
+    ("MidiMessageList", &["MIDI_MESSAGE_"], &[]),
+    ("Orientation", &["HORIZONTAL", "VERTICAL"], &[]),
+    ("PropertyHint", &["PROPERTY_HINT_"], &[]),
+    ("PropertyUsageFlags", &["PROPERTY_USAGE_"], &[]),
+    ("VAlign", &["VALIGN_"], &[]),
+];
+
+/// Method arguments that take values of global enums, as `(class, method, argument, enum)`,
+/// where `argument` is `None` for the return value.
+pub(crate) const GLOBAL_ENUM_ARGUMENTS: &[(&str, &str, Option<&str>, &str)] = &[
+    (
+        "ARVRController",
+        "get_joystick_axis",
+        Some("axis"),
+        "enum.GlobalConstants::JoystickList",
+    ),
+    (
+        "ARVRController",
+        "is_button_pressed",
+        Some("button"),
+        "enum.GlobalConstants::JoystickList",
+    ),
+    (
+        "Camera2D",
+        "get_drag_margin",
+        Some("margin"),
+        "enum.GlobalConstants::Margin",
+    ),
+    (
+        "Camera2D",
+        "get_limit",
+        Some("margin"),
+        "enum.GlobalConstants::Margin",
+    ),
+    (
+        "Camera2D",
+        "set_drag_margin",
+        Some("margin"),
+        "enum.GlobalConstants::Margin",
+    ),
+    (
+        "Camera2D",
+        "set_limit",
+        Some("margin"),
+        "enum.GlobalConstants::Margin",
+    ),
+    (
+        "Control",
+        "get_anchor",
+        Some("margin"),
+        "enum.GlobalConstants::Margin",
+    ),
+    (
+        "Control",
+        "get_focus_neighbour",
+        Some("margin"),
+        "enum.GlobalConstants::Margin",
+    ),
+    (
+        "Control",
+        "get_margin",
+        Some("margin"),
+        "enum.GlobalConstants::Margin",
+    ),
+    (
+        "Control",
+        "set_anchor",
+        Some("margin"),
+        "enum.GlobalConstants::Margin",
+    ),
+    (
+        "Control",
+        "set_anchor_and_margin",
+        Some("margin"),
+        "enum.GlobalConstants::Margin",
+    ),
+    (
+        "Control",
+        "set_focus_neighbour",
+        Some("margin"),
+        "enum.GlobalConstants::Margin",
+    ),
+    (
+        "Control",
+        "set_margin",
+        Some("margin"),
+        "enum.GlobalConstants::Margin",
+    ),
+    (
+        "Input",
+        "get_joy_axis",
+        Some("axis"),
+        "enum.GlobalConstants::JoystickList",
+    ),
+    (
+        "Input",
+        "get_joy_axis_index_from_string",
+        None,
+        "enum.GlobalConstants::JoystickList",
+    ),
+    (
+        "Input",
+        "get_joy_axis_string",
+        Some("axis_index"),
+        "enum.GlobalConstants::JoystickList",
+    ),
+    (
+        "Input",
+        "get_joy_button_index_from_string",
+        None,
+        "enum.GlobalConstants::JoystickList",
+    ),
+    (
+        "Input",
+        "get_joy_button_string",
+        Some("button_index"),
+        "enum.GlobalConstants::JoystickList",
+    ),
+    (
+        "Input",
+        "is_joy_button_pressed",
+        Some("button"),
+        "enum.GlobalConstants::JoystickList",
+    ),
+    (
+        "Input",
+        "is_key_pressed",
+        Some("scancode"),
+        "enum.GlobalConstants::KeyList",
+    ),
+    (
+        "Input",
+        "is_mouse_button_pressed",
+        Some("button"),
+        "enum.GlobalConstants::ButtonList",
+    ),
+    (
+        "InputEventJoypadButton",
+        "get_button_index",
+        None,
+        "enum.GlobalConstants::JoystickList",
+    ),
+    (
+        "InputEventJoypadButton",
+        "set_button_index",
+        Some("button_index"),
+        "enum.GlobalConstants::JoystickList",
+    ),
+    (
+        "InputEventJoypadMotion",
+        "get_axis",
+        None,
+        "enum.GlobalConstants::JoystickList",
+    ),
+    (
+        "InputEventJoypadMotion",
+        "set_axis",
+        Some("axis"),
+        "enum.GlobalConstants::JoystickList",
+    ),
+    (
+        "InputEventKey",
+        "get_scancode",
+        None,
+        "enum.GlobalConstants::KeyList",
+    ),
+    (
+        "InputEventKey",
+        "set_scancode",
+        Some("scancode"),
+        "enum.GlobalConstants::KeyList",
+    ),
+    (
+        "InputEventMIDI",
+        "get_message",
+        None,
+        "enum.GlobalConstants::MidiMessageList",
+    ),
+    (
+        "InputEventMIDI",
+        "set_message",
+        Some("message"),
+        "enum.GlobalConstants::MidiMessageList",
+    ),
+    (
+        "InputEventMouseButton",
+        "get_button_index",
+        None,
+        "enum.GlobalConstants::ButtonList",
+    ),
+    (
+        "InputEventMouseButton",
+        "set_button_index",
+        Some("button_index"),
+        "enum.GlobalConstants::ButtonList",
+    ),
+    (
+        "NinePatchRect",
+        "get_patch_margin",
+        Some("margin"),
+        "enum.GlobalConstants::Margin",
+    ),
+    (
+        "NinePatchRect",
+        "set_patch_margin",
+        Some("margin"),
+        "enum.GlobalConstants::Margin",
+    ),
+    (
+        "OS",
+        "find_scancode_from_string",
+        None,
+        "enum.GlobalConstants::KeyList",
+    ),
+    (
+        "OS",
+        "get_scancode_string",
+        Some("code"),
+        "enum.GlobalConstants::KeyList",
+    ),
+    (
+        "StyleBox",
+        "get_default_margin",
+        Some("margin"),
+        "enum.GlobalConstants::Margin",
+    ),
+    (
+        "StyleBox",
+        "get_margin",
+        Some("margin"),
+        "enum.GlobalConstants::Margin",
+    ),
+    (
+        "StyleBox",
+        "set_default_margin",
+        Some("margin"),
+        "enum.GlobalConstants::Margin",
+    ),
+    (
+        "StyleBoxFlat",
+        "get_border_width",
+        Some("margin"),
+        "enum.GlobalConstants::Margin",
+    ),
+    (
+        "StyleBoxFlat",
+        "get_corner_radius",
+        Some("corner"),
+        "enum.GlobalConstants::Corner",
+    ),
+    (
+        "StyleBoxFlat",
+        "get_expand_margin",
+        Some("margin"),
+        "enum.GlobalConstants::Margin",
+    ),
+    (
+        "StyleBoxFlat",
+        "set_border_width",
+        Some("margin"),
+        "enum.GlobalConstants::Margin",
+    ),
+    (
+        "StyleBoxFlat",
+        "set_corner_radius",
+        Some("corner"),
+        "enum.GlobalConstants::Corner",
+    ),
+    (
+        "StyleBoxFlat",
+        "set_expand_margin",
+        Some("margin"),
+        "enum.GlobalConstants::Margin",
+    ),
+    (
+        "StyleBoxTexture",
+        "get_expand_margin_size",
+        Some("margin"),
+        "enum.GlobalConstants::Margin",
+    ),
+    (
+        "StyleBoxTexture",
+        "get_margin_size",
+        Some("margin"),
+        "enum.GlobalConstants::Margin",
+    ),
+    (
+        "StyleBoxTexture",
+        "set_expand_margin_size",
+        Some("margin"),
+        "enum.GlobalConstants::Margin",
+    ),
+    (
+        "StyleBoxTexture",
+        "set_margin_size",
+        Some("margin"),
+        "enum.GlobalConstants::Margin",
+    ),
+    (
+        "TextureProgress",
+        "get_stretch_margin",
+        Some("margin"),
+        "enum.GlobalConstants::Margin",
+    ),
+    (
+        "TextureProgress",
+        "set_stretch_margin",
+        Some("margin"),
+        "enum.GlobalConstants::Margin",
+    ),
+];
+
+/// Adds the enums of `@GlobalScope` to `GlobalConstants`. The constants are kept.
+pub(crate) fn add_global_enums(api: &mut Api) {
+    let class = match api
+        .classes
+        .iter_mut()
+        .find(|class| class.name == "GlobalConstants")
+    {
+        Some(class) => class,
+        None => return,
+    };
+
+    for &(name, prefixes, excluded) in GLOBAL_ENUMS {
+        let values = class
+            .constants
+            .iter()
+            .filter(|(constant, _)| {
+                prefixes.iter().any(|prefix| constant.starts_with(prefix))
+                    && !excluded.iter().any(|prefix| constant.starts_with(prefix))
+            })
+            .map(|(constant, value)| (constant.clone(), *value))
+            .collect();
+
+        class.enums.push(Enum {
+            name: name.to_string(),
+            values,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn global_enums() {
+        let api = Api::new(include_str!("../../gdnative-bindings/api.json"));
+        let class = api.find_class("GlobalConstants").unwrap();
+        let find_enum = |name: &str| class.enums.iter().find(|e| e.name == name).unwrap();
+
+        for &(name, _, _) in GLOBAL_ENUMS {
+            assert!(!find_enum(name).values.is_empty(), "{}", name);
+        }
+
+        let keys = &find_enum("KeyList").values;
+        assert_eq!(Some(&65), keys.get("A"));
+        assert_eq!(Some(&48), keys.get("_0"));
+        assert!(!keys.contains_key("MASK_SHIFT"));
+        assert!(find_enum("KeyModifierMask")
+            .values
+            .contains_key("MASK_SHIFT"));
+
+        // The constants are kept.
+        assert_eq!(Some(&65), class.constants.get("KEY_A"));
+
+        for &(class_name, method_name, arg_name, ty) in GLOBAL_ENUM_ARGUMENTS {
+            let method = api
+                .find_class(class_name)
+                .and_then(|class| class.methods.iter().find(|m| m.name == method_name))
+                .unwrap_or_else(|| panic!("{}::{} not found", class_name, method_name));
+
+            let actual = match arg_name {
+                Some(arg_name) => method
+                    .arguments
+                    .iter()
+                    .find(|arg| arg.name == arg_name)
+                    .map(|arg| arg.ty.as_str()),
+                None => Some(method.return_type.as_str()),
+            };
+
+            assert_eq!(Some(ty), actual, "{}::{}", class_name, method_name);
+        }
+    }
+}
@@ -8,6 +8,29 @@
 //!
 //! `/path/to/godot --gdnative-generate-json-api /path/to/api.json`
 //!
+//! `Api::try_new` checks that the types used in the API are supported by the generator, and
+//! reports the unsupported ones. The `gdnative-bindings` crate uses the JSON data from the
+//! `GODOT_API_JSON` environment variable if it's set, so custom bindings can often be
+//! generated without a custom crate.
+//!
+//! # Bindings for script classes
+//!
+//! Typed wrappers for classes written in GDScript or other scripting languages can be generated
+//! with `generate_script_bindings`, e.g. in a build script. Script classes are described by
+//! `ScriptClass`, which can be parsed from `.gd` files with `ScriptClass::from_gdscript` or
+//! loaded from JSON with `ScriptClass::from_json`:
+//!
+//! ```ignore
+//! let api = Api::new(&std::fs::read_to_string("api.json")?);
+//! let enemy = ScriptClass::from_gdscript("res://enemy.gd", &std::fs::read_to_string("godot/enemy.gd")?);
+//! let code = generate_script_bindings(&api, &[enemy]);
+//! std::fs::write(out_dir.join("scripts.rs"), code.to_string())?;
+//! ```
+//!
+//! The generated code can then be included with `include!(concat!(env!("OUT_DIR"), "/scripts.rs"))`.
+//! Wrappers that can run script code, i.e. methods, property accessors and `emit_*`, are
+//! `unsafe` with the same contract as `TRef::call_typed`.
+//!
 //! *Please note that The generator is an internal dependency.* As such, it is not covered
 //! by semver guarantees of the main `gdnative` crate. When using custom binding crates, care
 //! must be taken to ensure that the version of the generator matches the one specified in
@@ -18,12 +41,19 @@ use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
 
 pub mod api;
+mod bbcode;
 mod class_docs;
 mod classes;
 pub mod dependency;
 mod documentation;
+mod flags;
+mod gdscript;
+mod global_enums;
 mod methods;
+mod properties;
+mod script_bindings;
 mod special_methods;
+mod virtual_methods;
 
 pub use crate::api::*;
 pub use crate::class_docs::*;
@@ -31,7 +61,10 @@ use crate::classes::*;
 pub use crate::dependency::*;
 use crate::documentation::*;
 use crate::methods::*;
+use crate::properties::*;
+pub use crate::script_bindings::*;
 use crate::special_methods::*;
+use crate::virtual_methods::*;
 
 use std::collections::HashMap;
 use std::io;
@@ -41,6 +74,7 @@ pub type GeneratorResult<T = ()> = Result<T, io::Error>;
 pub struct BindingResult {
     pub class_bindings: HashMap<String, TokenStream>,
     pub icalls: TokenStream,
+    pub virtual_methods: TokenStream,
 }
 
 pub fn generate_bindings(api: &Api, docs: Option<&GodotXMLDocs>) -> BindingResult {
@@ -62,9 +96,12 @@ pub fn generate_bindings(api: &Api, docs: Option<&GodotXMLDocs>) -> BindingResul
         .map(|(name, sig)| generate_icall(name, sig))
         .collect();
 
+    let virtual_methods = generate_virtual_method_checks(api);
+
     BindingResult {
         class_bindings,
         icalls,
+        virtual_methods,
     }
 }
 
@@ -84,26 +121,32 @@ fn generate_class_bindings(
 ) -> TokenStream {
     // types and methods
     let types_and_methods = {
-        let documentation = generate_class_documentation(&api, class);
+        let documentation = generate_class_documentation(&api, class, docs);
 
         let class_struct = generate_class_struct(class);
 
-        let enums = generate_enums(class);
+        let enums = generate_enums(api, class, docs);
+
+        let property_enum = generate_property_enum(api, class, docs);
 
         let constants = if !class.constants.is_empty() {
-            generate_class_constants(class)
+            generate_class_constants(api, class, docs)
         } else {
             Default::default()
         };
 
-        let class_impl = generate_class_impl(class, icalls, docs);
+        let class_impl = generate_class_impl(api, class, icalls, docs);
+
+        let virtual_trait = generate_virtual_trait(api, class);
 
         quote! {
             #documentation
             #class_struct
             #enums
+            #property_enum
             #constants
             #class_impl
+            #virtual_trait
         }
     };
 
@@ -128,12 +171,14 @@ fn generate_class_bindings(
             Default::default()
         };
 
-        let send_sync = if class.singleton && class.is_singleton_thread_safe() {
-            generate_send_sync_impls(class)
+        let send_sync = if class.singleton {
+            generate_send_sync_impls(api, class)
         } else {
             Default::default()
         };
 
+        let thread_safety = generate_thread_safety_impl(api, class);
+
         quote! {
             #object_impl
             #free_impl
@@ -141,6 +186,7 @@ fn generate_class_bindings(
             #sub_class
             #instantiable
             #send_sync
+            #thread_safety
         }
     };
 
@@ -200,7 +246,7 @@ pub(crate) mod test_prelude {
         for class in &api.classes {
             let mut icalls = HashMap::new();
 
-            let code = generate_class_documentation(&api, &class);
+            let code = generate_class_documentation(&api, &class, None);
             write!(&mut buffer, "{}", code).unwrap();
             write!(&mut buffer, "{}", quote! { struct Docs {} }).unwrap();
             validate_and_clear_buffer!(buffer);
@@ -209,17 +255,25 @@ pub(crate) mod test_prelude {
             write!(&mut buffer, "{}", code).unwrap();
             validate_and_clear_buffer!(buffer);
 
-            let code = generate_enums(&class);
+            let code = generate_enums(&api, &class, None);
+            write!(&mut buffer, "{}", code).unwrap();
+            validate_and_clear_buffer!(buffer);
+
+            let code = generate_property_enum(&api, &class, None);
             write!(&mut buffer, "{}", code).unwrap();
             validate_and_clear_buffer!(buffer);
 
             if !class.constants.is_empty() {
-                let code = generate_class_constants(&class);
+                let code = generate_class_constants(&api, &class, None);
                 write!(&mut buffer, "{}", code).unwrap();
                 validate_and_clear_buffer!(buffer);
             }
 
-            let code = generate_class_impl(&class, &mut icalls, None);
+            let code = generate_class_impl(&api, &class, &mut icalls, None);
+            write!(&mut buffer, "{}", code).unwrap();
+            validate_and_clear_buffer!(buffer);
+
+            let code = generate_virtual_trait(&api, &class);
             write!(&mut buffer, "{}", code).unwrap();
             validate_and_clear_buffer!(buffer);
 
@@ -245,6 +299,16 @@ pub(crate) mod test_prelude {
                 validate_and_clear_buffer!(buffer);
             }
 
+            if class.singleton {
+                let code = generate_send_sync_impls(&api, &class);
+                write!(&mut buffer, "{}", code).unwrap();
+                validate_and_clear_buffer!(buffer);
+            }
+
+            let code = generate_thread_safety_impl(&api, &class);
+            write!(&mut buffer, "{}", code).unwrap();
+            validate_and_clear_buffer!(buffer);
+
             // icalls and method table
             let code = generate_method_table(&api, &class);
             write!(&mut buffer, "{}", code).unwrap();
@@ -256,5 +320,9 @@ pub(crate) mod test_prelude {
                 validate_and_clear_buffer!(buffer);
             }
         }
+
+        let code = generate_virtual_method_checks(&api);
+        write!(&mut buffer, "{}", code).unwrap();
+        validate_and_clear_buffer!(buffer);
     }
 }
@@ -135,7 +135,7 @@ impl MethodSig {
     }
 }
 
-fn skip_method(method: &GodotMethod, name: &str) -> bool {
+pub(crate) fn skip_method(method: &GodotMethod, name: &str) -> bool {
     const METHODS: &[&str] = &["free", "reference", "unreference"];
     METHODS.contains(&name) || method.is_virtual
 }
@@ -246,7 +246,7 @@ pub fn generate_method_table(api: &Api, class: &GodotClass) -> TokenStream {
 }
 
 /// Removes 'get_' from the beginning of `name` if `name` is a property getter on `class`.
-fn rename_property_getter<'a>(name: &'a str, class: &GodotClass) -> &'a str {
+pub(crate) fn rename_property_getter<'a>(name: &'a str, class: &GodotClass) -> &'a str {
     if name.starts_with("get_") && class.is_getter(name) {
         &name[4..]
     } else {
@@ -260,7 +260,16 @@ const UNSAFE_OBJECT_METHODS: &[(&str, &str)] = &[
     ("Object", "call_deferred"),
 ];
 
+/// Methods that give access to the scene tree, which take a `MainThreadToken` since they can
+/// only be called from the main thread.
+const MAIN_THREAD_METHODS: &[(&str, &str)] = &[
+    ("Node", "get_tree"),
+    ("Node", "get_viewport"),
+    ("Engine", "get_main_loop"),
+];
+
 pub(crate) fn generate_methods(
+    api: &Api,
     class: &GodotClass,
     icalls: &mut HashMap<String, MethodSig>,
     docs: Option<&GodotXMLDocs>,
@@ -276,7 +285,7 @@ pub(crate) fn generate_methods(
 
             Ty::String | Ty::NodePath => quote! { #name.into() },
 
-            Ty::Enum(_) => quote! { #name.0 },
+            Ty::Enum(_) => quote! { i64::from(#name) },
 
             Ty::Object(_) => quote! { #name.as_arg_ptr() },
 
@@ -309,6 +318,16 @@ pub(crate) fn generate_methods(
 
         let mut params_decl = TokenStream::new();
         let mut params_use = TokenStream::new();
+        let mut main_thread_doc = TokenStream::new();
+        if MAIN_THREAD_METHODS.contains(&(&class.name, method_name)) {
+            params_decl.extend(quote! {
+                , _token: thread_access::MainThreadToken
+            });
+            main_thread_doc = quote! {
+                #[doc = "\n# Main thread\nThis method can only be called from the main thread, \
+                    which is proven with a `MainThreadToken`."]
+            };
+        }
         for argument in &method.arguments {
             let ty = argument.get_type();
             let rust_ty = ty.to_rust_arg();
@@ -353,7 +372,8 @@ pub(crate) fn generate_methods(
         if UNSAFE_OBJECT_METHODS.contains(&(&class.name, method_name)) {
             maybe_unsafe = quote! { unsafe };
             maybe_unsafe_reason = "\n# Safety\nThis function bypasses Rust's static type checks \
-                (aliasing, thread boundaries, calls to free(), ...).";
+                (aliasing, thread boundaries, calls to free(), ...). `TRef::call_typed` and \
+                `TRef::call_deferred_typed` additionally check the arguments and return values.";
         } else {
             maybe_unsafe = TokenStream::default();
             maybe_unsafe_reason = "";
@@ -369,14 +389,15 @@ pub(crate) fn generate_methods(
         };
 
         let doc_comment = docs
-            .and_then(|docs| docs.get_class_method_desc(class.name.as_str(), method_name))
-            .unwrap_or("");
+            .and_then(|docs| docs.get_class_method_desc(api, &class.name, method_name))
+            .unwrap_or_default();
 
         let recover = ret_recover(&ret_type, icall_ty);
 
         let output = quote! {
             #[doc = #doc_comment]
             #[doc = #maybe_unsafe_reason]
+            #main_thread_doc
             #[inline]
             pub #maybe_unsafe fn #rusty_name(&self #params_decl) -> #rust_ret_type {
                 unsafe {
@@ -0,0 +1,243 @@
+use crate::api::*;
+use crate::class_docs::GodotXMLDocs;
+use crate::methods::{rename_property_getter, skip_method};
+
+use heck::CamelCase as _;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+use std::collections::HashSet;
+
+/// Generates named accessors for indexed properties, e.g. `SpatialMaterial::albedo_texture`,
+/// which are otherwise only accessible through getters and setters taking the index, like
+/// `SpatialMaterial::texture`. Other properties are accessed through their own getters and
+/// setters, which aren't always named after the property: boolean getters are often named
+/// `is_*` or `has_*`, e.g. `CanvasItem::is_visible` for `visible`, and some accessors have
+/// unrelated names, e.g. `AcceptDialog::text` for `dialog_text`. No accessors are generated for
+/// them. Properties with names that can't be used as identifiers, or that would conflict with
+/// existing methods, are skipped.
+pub(crate) fn generate_property_accessors(class: &GodotClass) -> TokenStream {
+    let mut method_names: HashSet<String> = class
+        .methods
+        .iter()
+        .filter_map(|method| {
+            let name = method.get_name().rust_name;
+            if skip_method(method, name) {
+                None
+            } else {
+                Some(rename_property_getter(name, class).to_string())
+            }
+        })
+        .collect();
+
+    let find_method = |name: &str| {
+        class
+            .methods
+            .iter()
+            .find(|method| method.name == name && !skip_method(method, method.get_name().rust_name))
+    };
+
+    let mut result = TokenStream::new();
+
+    for property in &class.properties {
+        if property.index < 0 || property.name.contains('/') {
+            continue;
+        }
+
+        let getter = match find_method(&property.getter) {
+            Some(getter) if getter.arguments.len() == 1 => getter,
+            _ => continue,
+        };
+
+        let index = match index_arg(&getter.arguments[0].get_type(), property.index) {
+            Some(index) => index,
+            None => continue,
+        };
+
+        let setter_name = format!("set_{}", property.name);
+        if method_names.contains(&property.name) || method_names.contains(&setter_name) {
+            continue;
+        }
+
+        let name = format_ident!("{}", property.name);
+        let getter_name = rename_property_getter(getter.get_name().rust_name, class);
+        let getter_ident = format_ident!("{}", getter_name);
+        let ret_type = getter.get_return_type().to_rust();
+        let getter_doc = format!(
+            "Returns the `{}` property, i.e. `{}({})`.",
+            property.name, getter_name, property.index
+        );
+
+        result.extend(quote! {
+            #[doc = #getter_doc]
+            #[inline]
+            pub fn #name(&self) -> #ret_type {
+                self.#getter_ident(#index)
+            }
+        });
+        method_names.insert(property.name.clone());
+
+        let setter = match find_method(&property.setter) {
+            Some(setter) if setter.arguments.len() == 2 => setter,
+            _ => continue,
+        };
+
+        let setter_ident = format_ident!("{}", setter.get_name().rust_name);
+        let setter_name_ident = format_ident!("{}", setter_name);
+        let value_type = setter.arguments[1].get_type().to_rust_arg();
+        let setter_doc = format!(
+            "Sets the `{}` property, i.e. `{}({}, value)`.",
+            property.name,
+            setter.get_name().rust_name,
+            property.index
+        );
+
+        result.extend(quote! {
+            #[doc = #setter_doc]
+            #[inline]
+            pub fn #setter_name_ident(&self, value: #value_type) {
+                self.#setter_ident(#index, value)
+            }
+        });
+        method_names.insert(setter_name);
+    }
+
+    result
+}
+
+/// Returns the index argument of an indexed property getter or setter taking `ty`.
+fn index_arg(ty: &Ty, index: i64) -> Option<TokenStream> {
+    match ty {
+        Ty::I64 => Some(quote! { #index }),
+        Ty::Enum(path) => Some(quote! { #path::from(#index) }),
+        _ => None,
+    }
+}
+
+/// Generates an enum of the properties of `class`, e.g. `SpriteProperty`, which converts into
+/// the property names accepted by `Object::get`, `Object::set` or `Tween::interpolate_property`.
+/// Inherited properties are part of the enums of the base classes.
+pub(crate) fn generate_property_enum(
+    api: &Api,
+    class: &GodotClass,
+    docs: Option<&GodotXMLDocs>,
+) -> TokenStream {
+    let mut variant_names = HashSet::new();
+    let properties: Vec<_> = class
+        .properties
+        .iter()
+        .filter_map(|property| {
+            let variant = property.name.replace('/', "_").to_camel_case();
+            if variant_names.insert(variant.clone()) {
+                Some((format_ident!("{}", variant), property.name.as_str()))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    if properties.is_empty() {
+        return TokenStream::new();
+    }
+
+    let enum_name = format_ident!("{}Property", class.name);
+    let doc = format!(
+        "Properties of `{}`, for use with `Object::get`, `Object::set` or \
+        `Tween::interpolate_property`. Inherited properties are part of the enums of the base \
+        classes.",
+        class.name
+    );
+
+    let variants = properties.iter().map(|(variant, name)| {
+        let variant_doc = match docs.and_then(|docs| docs.get_member_desc(api, &class.name, name)) {
+            Some(desc) => format!("`{}`\n\n{}", name, desc),
+            None => format!("`{}`", name),
+        };
+        quote! {
+            #[doc = #variant_doc]
+            #variant
+        }
+    });
+
+    let names = properties.iter().map(|(variant, name)| {
+        quote! {
+            #enum_name::#variant => #name
+        }
+    });
+
+    quote! {
+        #[doc = #doc]
+        #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+        pub enum #enum_name {
+            #(#variants),*
+        }
+
+        impl #enum_name {
+            /// Returns the name of the property.
+            #[inline]
+            pub fn as_str(self) -> &'static str {
+                match self {
+                    #(#names),*
+                }
+            }
+        }
+        impl std::fmt::Display for #enum_name {
+            #[inline]
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+        impl From<#enum_name> for GodotString {
+            #[inline]
+            fn from(property: #enum_name) -> Self {
+                GodotString::from_str(property.as_str())
+            }
+        }
+        impl From<#enum_name> for NodePath {
+            #[inline]
+            fn from(property: #enum_name) -> Self {
+                NodePath::from_str(property.as_str())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indexed_property_accessors() {
+        let api = Api::new(include_str!("../../gdnative-bindings/api.json"));
+
+        let material = api.find_class("SpatialMaterial").unwrap();
+        let code = generate_property_accessors(material).to_string();
+        assert!(code.contains("pub fn albedo_texture"));
+        assert!(code.contains("pub fn set_albedo_texture"));
+        // Non-indexed properties have their own accessors, even if they're named differently.
+        assert!(!code.contains("pub fn albedo_color"));
+        let canvas_item = api.find_class("CanvasItem").unwrap();
+        let code = generate_property_accessors(canvas_item).to_string();
+        assert!(!code.contains("pub fn visible"));
+
+        // The setter of the anchors is virtual, and not generated.
+        let control = api.find_class("Control").unwrap();
+        let code = generate_property_accessors(control).to_string();
+        assert!(code.contains("pub fn anchor_left"));
+        assert!(!code.contains("pub fn set_anchor_left"));
+    }
+
+    #[test]
+    fn property_enum() {
+        let api = Api::new(include_str!("../../gdnative-bindings/api.json"));
+
+        let texture = api.find_class("AnimatedTexture").unwrap();
+        let code = generate_property_enum(&api, texture, None).to_string();
+        assert!(code.contains("pub enum AnimatedTextureProperty"));
+        assert!(code.contains("Frame0Texture => \"frame_0/texture\""));
+
+        let object = api.find_class("Object").unwrap();
+        assert!(object.properties.is_empty());
+        assert!(generate_property_enum(&api, object, None).is_empty());
+    }
+}
@@ -0,0 +1,654 @@
+//! Generation of typed Rust wrappers for script classes, e.g. classes written in GDScript.
+//!
+//! Script classes are described by `ScriptClass`, which can be parsed from GDScript files or
+//! loaded from JSON. The generated wrappers call into the scripts dynamically, using
+//! `TRef::call_typed` for methods and `Object::get` and `Object::set` for properties.
+//!
+//! Getting or setting a property can run script code through `setget` functions, and emitting
+//! a signal runs the connected methods, so property accessors and `emit_*` methods are
+//! `unsafe` with the same contract as the method wrappers, i.e. `TRef::call_typed`. Connecting
+//! to a signal doesn't run script code, and is safe.
+
+use std::collections::HashSet;
+
+use miniserde::Deserialize;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+use crate::api::{Api, Ty};
+use crate::rust_safe_name;
+
+/// Description of a script class, i.e. a class written in GDScript or another scripting
+/// language, or a NativeScript class.
+///
+/// Types are named as in the engine's API description, e.g. `int`, `String`, `Vector2` or
+/// `Node`. Untyped values have the type `Variant`, and functions without return values
+/// `void`.
+#[derive(Deserialize, Clone, PartialEq, Debug)]
+pub struct ScriptClass {
+    /// Name of the generated wrapper type, e.g. the `class_name` of a GDScript class.
+    pub name: String,
+    /// Name of the engine class or script class the script extends, or the path of the
+    /// script it extends.
+    pub base_class: String,
+    /// Resource path of the script, e.g. `res://enemy.gd`.
+    pub path: String,
+    pub properties: Vec<ScriptProperty>,
+    pub methods: Vec<ScriptMethod>,
+    pub signals: Vec<ScriptSignal>,
+}
+
+#[derive(Deserialize, Clone, PartialEq, Debug)]
+pub struct ScriptProperty {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: String,
+}
+
+#[derive(Deserialize, Clone, PartialEq, Debug)]
+pub struct ScriptMethod {
+    pub name: String,
+    pub return_type: String,
+    pub arguments: Vec<ScriptArgument>,
+}
+
+#[derive(Deserialize, Clone, PartialEq, Debug)]
+pub struct ScriptSignal {
+    pub name: String,
+    pub arguments: Vec<ScriptArgument>,
+}
+
+#[derive(Deserialize, Clone, PartialEq, Debug)]
+pub struct ScriptArgument {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: String,
+}
+
+impl ScriptClass {
+    /// Parses the declarations of the GDScript file at the resource path `path`, with the
+    /// contents `source`.
+    ///
+    /// The class is named after its `class_name`, or after the file if it has none. Only
+    /// the top level of the script is read, so inner classes aren't included.
+    pub fn from_gdscript(path: &str, source: &str) -> Self {
+        crate::gdscript::parse(path, source)
+    }
+
+    /// Loads descriptions of script classes from JSON data, containing an array of objects
+    /// with the same fields as `ScriptClass`.
+    ///
+    /// # Panics
+    ///
+    /// If the `data` is not valid JSON data the function will panic.
+    pub fn from_json(data: &str) -> Vec<Self> {
+        miniserde::json::from_str(data).expect("Invalid JSON data")
+    }
+}
+
+/// Generates wrapper types for the script classes in `classes`, which use engine classes from
+/// `api`.
+///
+/// The wrappers refer to items in the `gdnative` crate with absolute paths, so the code can be
+/// included anywhere in a crate depending on `gdnative`. A wrapper for a class extending
+/// another script class in `classes` dereferences to the wrapper of its base class.
+///
+/// # Panics
+///
+/// If the base class of a script isn't an engine class in `api` or another script in
+/// `classes` the function will panic.
+pub fn generate_script_bindings(api: &Api, classes: &[ScriptClass]) -> TokenStream {
+    classes
+        .iter()
+        .map(|class| generate_script_class(api, classes, class))
+        .collect()
+}
+
+/// The base class of a script class.
+enum ScriptBase<'a> {
+    Engine(&'a str),
+    Script(&'a ScriptClass),
+}
+
+fn find_base<'a>(api: &'a Api, classes: &'a [ScriptClass], class: &ScriptClass) -> ScriptBase<'a> {
+    let base = class.base_class.as_str();
+
+    if let Some(script) = classes
+        .iter()
+        .find(|script| script.path == base || script.name == base)
+    {
+        return ScriptBase::Script(script);
+    }
+
+    match api.find_class(base) {
+        Some(engine) => ScriptBase::Engine(&engine.name),
+        None => panic!(
+            "base class {} of script class {} not found",
+            base, class.name
+        ),
+    }
+}
+
+/// Returns the name of the engine class that `class` ultimately extends.
+fn engine_base<'a>(api: &'a Api, classes: &'a [ScriptClass], class: &ScriptClass) -> &'a str {
+    let mut class = class;
+    let mut visited = HashSet::new();
+
+    loop {
+        if !visited.insert(class.path.as_str()) {
+            panic!("script class {} inherits from itself", class.name);
+        }
+
+        match find_base(api, classes, class) {
+            ScriptBase::Engine(name) => return name,
+            ScriptBase::Script(base) => class = base,
+        }
+    }
+}
+
+fn generate_script_class(api: &Api, classes: &[ScriptClass], class: &ScriptClass) -> TokenStream {
+    let name = format_ident!("{}", class.name);
+    let class_name = &class.name;
+    let path = &class.path;
+    let engine_base = format_ident!("{}", engine_base(api, classes, class));
+
+    let (base_ty, base_init, object, deref) = match find_base(api, classes, class) {
+        ScriptBase::Engine(_) => (
+            quote! { ::gdnative::TRef<'a, ::gdnative::api::#engine_base> },
+            quote! { object },
+            quote! { self.base },
+            quote! {
+                type Target = ::gdnative::api::#engine_base;
+
+                #[inline]
+                fn deref(&self) -> &Self::Target {
+                    self.base.as_ref()
+                }
+            },
+        ),
+        ScriptBase::Script(base) => {
+            let base_name = format_ident!("{}", base.name);
+            (
+                quote! { #base_name<'a> },
+                quote! { #base_name::new_unchecked(object) },
+                quote! { self.base.object() },
+                quote! {
+                    type Target = #base_name<'a>;
+
+                    #[inline]
+                    fn deref(&self) -> &Self::Target {
+                        &self.base
+                    }
+                },
+            )
+        }
+    };
+
+    let doc = format!(
+        "Wrapper for the script class `{}` (`{}`), which calls into the script dynamically.",
+        class_name, path
+    );
+
+    let method_names: HashSet<&str> = class.methods.iter().map(|m| m.name.as_str()).collect();
+
+    let methods = class
+        .methods
+        .iter()
+        .filter(|method| !method.name.starts_with('_'))
+        .map(|method| generate_script_method(api, method));
+
+    let properties = class
+        .properties
+        .iter()
+        .filter(|property| !property.name.starts_with('_'))
+        .map(|property| generate_script_property(api, property, &method_names));
+
+    let signals = class
+        .signals
+        .iter()
+        .map(|signal| generate_script_signal(api, signal, &method_names));
+
+    quote! {
+        #[doc = #doc]
+        #[derive(Copy, Clone, Debug)]
+        pub struct #name<'a> {
+            base: #base_ty,
+        }
+
+        // Method names come from the script, and may be confused with standard methods. Crates
+        // rarely use every accessor of a script.
+        #[allow(dead_code, clippy::style)]
+        impl<'a> #name<'a> {
+            /// Name of the script class.
+            pub const CLASS_NAME: &'static str = #class_name;
+
+            /// Resource path of the script.
+            pub const PATH: &'static str = #path;
+
+            /// Returns a wrapper for `object` if the script, or a script extending it, is
+            /// attached to `object`. Returns `None` otherwise.
+            #[inline]
+            pub fn cast<T>(object: ::gdnative::TRef<'a, T>) -> Option<Self>
+            where
+                T: ::gdnative::GodotObject,
+                ::gdnative::api::#engine_base: ::gdnative::object::SubClass<T>,
+            {
+                let object = object.cast::<::gdnative::api::#engine_base>()?;
+
+                let mut script = object
+                    .get_script()
+                    .and_then(|script| script.cast::<::gdnative::api::Script>());
+
+                while let Some(current) = script {
+                    let current = unsafe { current.assume_safe() };
+                    if current.path().to_string() == Self::PATH {
+                        return Some(Self::new_unchecked(object));
+                    }
+                    script = current.get_base_script();
+                }
+
+                None
+            }
+
+            #[inline]
+            fn new_unchecked(object: ::gdnative::TRef<'a, ::gdnative::api::#engine_base>) -> Self {
+                #name { base: #base_init }
+            }
+
+            /// Returns the object the script is attached to.
+            #[inline]
+            pub fn object(self) -> ::gdnative::TRef<'a, ::gdnative::api::#engine_base> {
+                #object
+            }
+
+            #(#methods)*
+            #(#properties)*
+            #(#signals)*
+        }
+
+        impl<'a> std::ops::Deref for #name<'a> {
+            #deref
+        }
+    }
+}
+
+/// Returns the Rust type of values of the script type `ty`. Enums and script classes are
+/// represented by their underlying values.
+fn script_type(api: &Api, ty: &str) -> Ty {
+    // Types that aren't identifiers can't be classes, e.g. typed arrays of newer versions.
+    if !ty.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Ty::Variant;
+    }
+
+    match Ty::from_src(ty) {
+        Ty::Object(_) if api.find_class(ty).is_none() => Ty::Variant,
+        Ty::Enum(_) | Ty::Result | Ty::VariantType | Ty::VariantOperator | Ty::Vector3Axis => {
+            Ty::I64
+        }
+        ty => ty,
+    }
+}
+
+/// Returns the absolute path of the Rust type of values of the script type `ty`.
+fn rust_type(api: &Api, ty: &str) -> TokenStream {
+    match script_type(api, ty) {
+        Ty::Void => quote! { () },
+        Ty::F64 | Ty::I64 | Ty::Bool => {
+            let ty = script_type(api, ty).to_rust();
+            quote! { #ty }
+        }
+        Ty::Object(_) => {
+            let class = format_ident!("{}", ty);
+            quote! { Option<::gdnative::Ref<::gdnative::api::#class, ::gdnative::thread_access::Shared>> }
+        }
+        other => {
+            let ty = other.to_rust();
+            quote! { ::gdnative::core_types::#ty }
+        }
+    }
+}
+
+/// Returns the Rust type of arguments of the script type `ty`, and an expression converting
+/// the argument `name` to a `Variant`.
+fn rust_argument(api: &Api, name: &syn::Ident, ty: &str) -> (TokenStream, TokenStream) {
+    match script_type(api, ty) {
+        Ty::Object(_) => {
+            let class = format_ident!("{}", ty);
+            (
+                quote! { impl ::gdnative::object::AsArg<::gdnative::api::#class> },
+                quote! { unsafe { ::gdnative::object::AsArg::to_arg_variant(&#name) } },
+            )
+        }
+        Ty::String => (
+            quote! { impl Into<::gdnative::core_types::GodotString> },
+            quote! {
+                ::gdnative::core_types::OwnedToVariant::owned_to_variant(
+                    Into::<::gdnative::core_types::GodotString>::into(#name)
+                )
+            },
+        ),
+        Ty::NodePath => (
+            quote! { impl Into<::gdnative::core_types::NodePath> },
+            quote! {
+                ::gdnative::core_types::OwnedToVariant::owned_to_variant(
+                    Into::<::gdnative::core_types::NodePath>::into(#name)
+                )
+            },
+        ),
+        Ty::Variant => (
+            quote! { impl ::gdnative::core_types::OwnedToVariant },
+            quote! { ::gdnative::core_types::OwnedToVariant::owned_to_variant(#name) },
+        ),
+        _ => (
+            rust_type(api, ty),
+            quote! { ::gdnative::core_types::OwnedToVariant::owned_to_variant(#name) },
+        ),
+    }
+}
+
+/// Returns the parameters and the `Variant` conversions of `arguments`.
+fn rust_arguments(api: &Api, arguments: &[ScriptArgument]) -> (Vec<TokenStream>, Vec<TokenStream>) {
+    arguments
+        .iter()
+        .map(|argument| {
+            let name = rust_safe_name(&argument.name);
+            let (ty, variant) = rust_argument(api, &name, &argument.ty);
+            (quote! { #name: #ty }, variant)
+        })
+        .unzip()
+}
+
+fn generate_script_method(api: &Api, method: &ScriptMethod) -> TokenStream {
+    let name = rust_safe_name(&method.name);
+    let method_name = &method.name;
+    let return_type = rust_type(api, &method.return_type);
+    let (params, variants) = rust_arguments(api, &method.arguments);
+
+    let doc = format!(
+        "Calls the script method `{}`.\n\n# Safety\n\nSee `TRef::call_typed`.",
+        method_name
+    );
+
+    quote! {
+        #[doc = #doc]
+        #[inline]
+        pub unsafe fn #name(self, #(#params),*) -> Result<#return_type, ::gdnative::object::MethodCallError> {
+            let args: Vec<::gdnative::core_types::Variant> = vec![#(#variants),*];
+            self.object().call_typed(#method_name, args)
+        }
+    }
+}
+
+/// Generates the getter and setter of `property`, unless the script defines methods with the
+/// same names.
+fn generate_script_property(
+    api: &Api,
+    property: &ScriptProperty,
+    method_names: &HashSet<&str>,
+) -> TokenStream {
+    let property_name = &property.name;
+    let ty = rust_type(api, &property.ty);
+
+    let getter = if !method_names.contains(property_name.as_str()) {
+        let name = rust_safe_name(property_name);
+        let doc = format!(
+            "Returns the value of the script property `{}`.\n\n# Safety\n\nSee `TRef::call_typed`.",
+            property_name
+        );
+
+        quote! {
+            #[doc = #doc]
+            #[inline]
+            pub unsafe fn #name(self) -> Result<#ty, ::gdnative::core_types::FromVariantError> {
+                ::gdnative::core_types::FromVariant::from_variant(&self.object().get(#property_name))
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    let setter_name = format!("set_{}", property_name);
+    let setter = if !method_names.contains(setter_name.as_str()) {
+        let name = format_ident!("{}", setter_name);
+        let value = format_ident!("value");
+        let (param_ty, variant) = rust_argument(api, &value, &property.ty);
+        let doc = format!(
+            "Sets the value of the script property `{}`.\n\n# Safety\n\nSee `TRef::call_typed`.",
+            property_name
+        );
+
+        quote! {
+            #[doc = #doc]
+            #[inline]
+            pub unsafe fn #name(self, value: #param_ty) {
+                self.object().set(#property_name, #variant);
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    quote! {
+        #getter
+        #setter
+    }
+}
+
+/// Generates methods to emit and connect to `signal`, unless the script defines methods
+/// with the same names.
+fn generate_script_signal(
+    api: &Api,
+    signal: &ScriptSignal,
+    method_names: &HashSet<&str>,
+) -> TokenStream {
+    let signal_name = &signal.name;
+    let (params, variants) = rust_arguments(api, &signal.arguments);
+
+    let const_name = format_ident!("SIGNAL_{}", signal_name.to_uppercase());
+    let const_doc = format!("Name of the signal `{}`.", signal_name);
+
+    let emit_name = format!("emit_{}", signal_name);
+    let emit = if !method_names.contains(emit_name.as_str()) {
+        let name = format_ident!("{}", emit_name);
+        let doc = format!(
+            "Emits the signal `{}`.\n\n# Safety\n\nSee `TRef::call_typed`.",
+            signal_name
+        );
+
+        quote! {
+            #[doc = #doc]
+            #[inline]
+            pub unsafe fn #name(self, #(#params),*) {
+                self.object().emit_signal(#signal_name, &[#(#variants),*]);
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    let connect_name = format!("connect_{}", signal_name);
+    let connect = if !method_names.contains(connect_name.as_str()) {
+        let name = format_ident!("{}", connect_name);
+        let doc = format!(
+            "Connects the signal `{}` to the method `method` of `target`.",
+            signal_name
+        );
+
+        quote! {
+            #[doc = #doc]
+            #[inline]
+            pub fn #name(
+                self,
+                target: impl ::gdnative::object::AsArg<::gdnative::api::Object>,
+                method: impl Into<::gdnative::core_types::GodotString>,
+                flags: ::gdnative::api::object::ConnectFlags,
+            ) -> ::gdnative::GodotResult {
+                self.object().connect(
+                    #signal_name,
+                    target,
+                    method,
+                    ::gdnative::core_types::VariantArray::new_shared(),
+                    flags,
+                )
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    quote! {
+        #[doc = #const_doc]
+        pub const #const_name: &'static str = #signal_name;
+
+        #emit
+        #connect
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn api() -> Api {
+        Api::new(include_str!("../../gdnative-bindings/api.json"))
+    }
+
+    #[test]
+    fn from_json() {
+        let classes = ScriptClass::from_json(
+            r#"[{
+                "name": "Enemy",
+                "base_class": "Node2D",
+                "path": "res://enemy.gd",
+                "properties": [{ "name": "health", "type": "int" }],
+                "methods": [{
+                    "name": "take_damage",
+                    "return_type": "void",
+                    "arguments": [{ "name": "amount", "type": "int" }]
+                }],
+                "signals": [{ "name": "died", "arguments": [] }]
+            }]"#,
+        );
+
+        assert_eq!(
+            vec![ScriptClass {
+                name: "Enemy".into(),
+                base_class: "Node2D".into(),
+                path: "res://enemy.gd".into(),
+                properties: vec![ScriptProperty {
+                    name: "health".into(),
+                    ty: "int".into(),
+                }],
+                methods: vec![ScriptMethod {
+                    name: "take_damage".into(),
+                    return_type: "void".into(),
+                    arguments: vec![ScriptArgument {
+                        name: "amount".into(),
+                        ty: "int".into(),
+                    }],
+                }],
+                signals: vec![ScriptSignal {
+                    name: "died".into(),
+                    arguments: vec![],
+                }],
+            }],
+            classes
+        );
+    }
+
+    #[test]
+    fn generate() {
+        let base = ScriptClass::from_gdscript(
+            "res://enemy.gd",
+            r#"
+extends KinematicBody2D
+class_name Enemy
+
+signal hit(by: Node, damage: int)
+
+export var health := 100
+var target: Node
+var state setget set_state
+
+func take_damage(amount: int, type: String) -> bool:
+    pass
+
+func set_state(value):
+    pass
+
+func find(path: NodePath) -> Node:
+    pass
+"#,
+        );
+        let boss = ScriptClass::from_gdscript(
+            "res://boss.gd",
+            "extends \"res://enemy.gd\"\n\nfunc enrage(loop):\n    pass\n",
+        );
+
+        let code = generate_script_bindings(&api(), &[base, boss]).to_string();
+        let file = syn::parse_file(&code).expect("generated code should parse");
+
+        let structs: Vec<_> = file
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                syn::Item::Struct(s) => Some(s.ident.to_string()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(vec!["Enemy", "Boss"], structs);
+
+        let functions: Vec<_> = file
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                syn::Item::Impl(i) if i.trait_.is_none() => Some(i),
+                _ => None,
+            })
+            .map(|i| {
+                i.items
+                    .iter()
+                    .filter_map(|item| match item {
+                        syn::ImplItem::Method(m) => Some(m.sig.ident.to_string()),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        assert_eq!(
+            vec![
+                vec![
+                    "cast",
+                    "new_unchecked",
+                    "object",
+                    "take_damage",
+                    "set_state",
+                    "find",
+                    "health",
+                    "set_health",
+                    "target",
+                    "set_target",
+                    "state",
+                    "emit_hit",
+                    "connect_hit",
+                ],
+                vec!["cast", "new_unchecked", "object", "enrage"],
+            ],
+            functions
+        );
+
+        assert!(code.contains("type Target = Enemy < 'a >"));
+        assert!(code.contains(
+            "pub unsafe fn find (self , path : impl Into < :: gdnative :: core_types :: NodePath >) \
+            -> Result < Option < :: gdnative :: Ref < :: gdnative :: api :: Node"
+        ));
+        assert!(code.contains("pub unsafe fn health (self)"));
+        assert!(code.contains("pub unsafe fn set_health (self , value : i64)"));
+        assert!(code.contains("pub unsafe fn emit_hit (self ,"));
+        assert!(code.contains("pub fn connect_hit (self ,"));
+        assert!(code.contains(
+            "pub unsafe fn enrage (self , _loop : impl :: gdnative :: core_types :: OwnedToVariant)"
+        ));
+    }
+}
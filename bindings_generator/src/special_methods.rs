@@ -98,7 +98,7 @@ pub fn generate_queue_free_impl(api: &Api, class: &GodotClass) -> TokenStream {
     }
 }
 
-pub fn generate_singleton_getter(class: &GodotClass) -> TokenStream {
+pub fn generate_singleton_getter(api: &Api, class: &GodotClass) -> TokenStream {
     assert!(class.singleton, "class should be a singleton");
 
     let s_name = if class.name.starts_with('_') {
@@ -114,35 +114,50 @@ pub fn generate_singleton_getter(class: &GodotClass) -> TokenStream {
         "singleton_name should be null terminated"
     );
 
-    let (maybe_unsafe, doc) = if class.is_singleton_thread_safe() {
-        (
-            TokenStream::new(),
-            "Returns a reference to the singleton instance.",
-        )
-    } else {
-        let maybe_unsafe = quote! { unsafe };
-        let doc = r#"Returns a reference to the singleton instance.
+    let body = quote! {
+        unsafe {
+            let this = (get_api().godot_global_get_singleton)(#singleton_name.as_ptr() as *mut _);
+            let this = ptr::NonNull::new(this).expect("singleton should not be null");
+            let this = RawObject::from_sys_ref_unchecked::<'static>(this);
+            Self::cast_ref(this)
+        }
+    };
+
+    if class.thread_safety(api) != ThreadSafety::Server {
+        return quote! {
+            #[doc="Returns a reference to the singleton instance."]
+            #[inline]
+            pub fn godot_singleton() -> &'static Self {
+                #body
+            }
+        };
+    }
+
+    let unsafe_doc = r#"Returns a reference to the singleton instance.
 
 # Safety
 
 This singleton server is only safe to access from outside the main thread if thread-safe
 operations are enabled in the project settings. See the official
-[thread-safety guidelines][thread-safety] for more information.
+[thread-safety guidelines][thread-safety] for more information. `godot_singleton_main_thread`
+can be used safely from the main thread.
 
 [thread-safety]: https://docs.godotengine.org/en/stable/tutorials/threads/thread_safe_apis.html"#;
-        (maybe_unsafe, doc)
-    };
+
+    let main_thread_doc = "Returns a reference to the singleton instance, which can be used \
+        safely from the main thread.";
 
     quote! {
-        #[doc=#doc]
+        #[doc=#unsafe_doc]
         #[inline]
-        pub #maybe_unsafe fn godot_singleton() -> &'static Self {
-            unsafe {
-                let this = (get_api().godot_global_get_singleton)(#singleton_name.as_ptr() as *mut _);
-                let this = ptr::NonNull::new(this).expect("singleton should not be null");
-                let this = RawObject::from_sys_ref_unchecked::<'static>(this);
-                Self::cast_ref(this)
-            }
+        pub unsafe fn godot_singleton() -> &'static Self {
+            #body
+        }
+
+        #[doc=#main_thread_doc]
+        #[inline]
+        pub fn godot_singleton_main_thread(_token: thread_access::MainThreadToken) -> &'static Self {
+            #body
         }
     }
 }
@@ -203,13 +218,29 @@ pub fn generate_sub_class_impls<'a>(api: &'a Api, mut class: &'a GodotClass) ->
     tokens
 }
 
-pub fn generate_send_sync_impls(class: &GodotClass) -> TokenStream {
-    assert!(class.is_singleton_thread_safe());
+pub fn generate_send_sync_impls(api: &Api, class: &GodotClass) -> TokenStream {
+    assert!(class.singleton, "class should be a singleton");
+    let class_name = format_ident!("{}", class.name);
+
+    match class.thread_safety(api) {
+        ThreadSafety::ThreadSafe | ThreadSafety::MainThreadOnly => quote! {
+            unsafe impl Send for #class_name {}
+            unsafe impl Sync for #class_name {}
+        },
+        ThreadSafety::Server => Default::default(),
+    }
+}
+
+pub fn generate_thread_safety_impl(api: &Api, class: &GodotClass) -> TokenStream {
     let class_name = format_ident!("{}", class.name);
 
-    quote! {
-        unsafe impl Send for #class_name {}
-        unsafe impl Sync for #class_name {}
+    match class.thread_safety(api) {
+        ThreadSafety::ThreadSafe | ThreadSafety::Server => quote! {
+            impl thread_access::AnyThread for #class_name {}
+        },
+        ThreadSafety::MainThreadOnly => quote! {
+            impl thread_access::MainThreadOnly for #class_name {}
+        },
     }
 }
 
@@ -0,0 +1,172 @@
+use crate::api::*;
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+use std::collections::BTreeSet;
+
+/// Returns the virtual methods declared by `class` that aren't already declared by one of its
+/// base classes.
+fn own_virtual_methods<'a>(api: &'a Api, class: &'a GodotClass) -> Vec<&'a GodotMethod> {
+    class
+        .methods
+        .iter()
+        .filter(|method| method.is_virtual && syn::parse_str::<syn::Ident>(&method.name).is_ok())
+        .filter(|method| {
+            let mut base_name = &class.base_class;
+            while let Some(base) = api.find_class(base_name) {
+                let redeclared = base
+                    .methods
+                    .iter()
+                    .any(|m| m.is_virtual && m.name == method.name);
+
+                if redeclared {
+                    return false;
+                }
+
+                base_name = &base.base_class;
+            }
+
+            true
+        })
+        .collect()
+}
+
+pub fn generate_virtual_trait(api: &Api, class: &GodotClass) -> TokenStream {
+    let methods = own_virtual_methods(api, class);
+    if methods.is_empty() {
+        return Default::default();
+    }
+
+    let class_name = format_ident!("{}", class.name);
+    let trait_name = format_ident!("{}Virtuals", class.name);
+
+    let trait_doc = format!(
+        "Virtual methods declared by `{}`, which scripts can override by exporting methods with \
+        the same names.\n\n\
+        This trait is implemented for `VirtualProbe<T>` for all subclasses `T` of `{}`. It is used \
+        by `#[methods]` to check the signatures of exported methods at compile time, and is not \
+        meant to be called directly.",
+        class.name, class.name,
+    );
+
+    let methods = methods.into_iter().map(|method| {
+        let method_name = format_ident!("{}", method.name);
+
+        let engine_args = method.arguments.iter().map(|arg| arg.get_type().to_rust());
+        let engine_ret = method.get_return_type().to_rust();
+
+        let method_doc = format!(
+            "`{}({}) -> {}`",
+            method.name,
+            method
+                .arguments
+                .iter()
+                .map(|arg| format!("{}: {}", arg.name, arg.ty))
+                .collect::<Vec<_>>()
+                .join(", "),
+            method.return_type,
+        );
+
+        quote! {
+            #[doc = #method_doc]
+            #[inline]
+            fn #method_name<A, R>(
+                &self,
+                _signature: gdnative_core::nativescript::virtuals::VirtualSignature<A, R>,
+            )
+            where
+                A: gdnative_core::nativescript::virtuals::VirtualArgs<(#(#engine_args,)*)>,
+                R: gdnative_core::nativescript::virtuals::VirtualReturn<#engine_ret>,
+            {
+            }
+        }
+    });
+
+    quote! {
+        #[doc = #trait_doc]
+        pub trait #trait_name {
+            #(#methods)*
+        }
+
+        impl<T: SubClass<#class_name>> #trait_name
+            for gdnative_core::nativescript::virtuals::VirtualProbe<T>
+        {
+        }
+    }
+}
+
+/// Generates the `virtual_methods` module re-exporting all `*Virtuals` traits, and the macro
+/// used by `#[methods]` to check exported methods against them.
+pub fn generate_virtual_method_checks(api: &Api) -> TokenStream {
+    let mut names = BTreeSet::new();
+    let mut traits = Vec::new();
+
+    for class in &api.classes {
+        let methods = own_virtual_methods(api, class);
+        if methods.is_empty() {
+            continue;
+        }
+
+        names.extend(methods.into_iter().map(|method| method.name.as_str()));
+
+        let module_name = format_ident!("{}", module_name_from_class_name(&class.name));
+        let trait_name = format_ident!("{}Virtuals", class.name);
+        traits.push(quote! {
+            pub use crate::generated::#module_name::#trait_name;
+        });
+    }
+
+    let names = names
+        .into_iter()
+        .map(|name| format_ident!("{}", name))
+        .collect::<Vec<_>>();
+
+    let fallback_methods = names.iter().map(|name| {
+        quote! {
+            #[inline]
+            fn #name<A, R>(
+                &self,
+                _signature: gdnative_core::nativescript::virtuals::VirtualSignature<A, R>,
+            ) {
+            }
+        }
+    });
+
+    quote! {
+        /// Traits describing the signatures of virtual methods, re-exported from the class
+        /// modules.
+        pub mod virtual_methods {
+            #(#traits)*
+
+            /// Accepts any signature for method names that are virtual in classes that the
+            /// checked base class doesn't inherit.
+            #[doc(hidden)]
+            pub trait VirtualFallback {
+                #(#fallback_methods)*
+            }
+
+            impl<'a, T> VirtualFallback for &'a gdnative_core::nativescript::virtuals::VirtualProbe<T> {}
+        }
+
+        /// Checks the signature of an exported method named `$name` against the virtual method
+        /// of the same name in the base class, if any. Used by `#[methods]`.
+        #[doc(hidden)]
+        #[macro_export]
+        macro_rules! __gdnative_check_virtual_method {
+            (@check $name:ident, $probe:expr, $signature:expr) => {
+                {
+                    #[allow(unused_imports)]
+                    use $crate::virtual_methods::*;
+                    (&$probe).$name($signature);
+                }
+            };
+            #(
+                (#names, $probe:expr, $signature:expr) => {
+                    $crate::__gdnative_check_virtual_method!(@check #names, $probe, $signature)
+                };
+            )*
+            ($name:ident, $probe:expr, $signature:expr) => {};
+        }
+    }
+}
@@ -1,7 +1,7 @@
-use crate::extensions::NodeExt as _;
 use crate::hud;
 use crate::mob;
 use crate::player;
+use gdnative::api::object::ConnectFlags;
 use gdnative::api::{Area2D, PathFollow2D, Position2D, RigidBody2D};
 use gdnative::prelude::*;
 use rand::*;
@@ -27,13 +27,13 @@ impl Main {
 
     #[export]
     fn game_over(&self, owner: &Node) {
-        let score_timer = unsafe { owner.get_typed_node::<Timer, _>("score_timer") };
-        let mob_timer = unsafe { owner.get_typed_node::<Timer, _>("mob_timer") };
+        let score_timer = unsafe { owner.get_node_as::<Timer>("score_timer").unwrap() };
+        let mob_timer = unsafe { owner.get_node_as::<Timer>("mob_timer").unwrap() };
 
         score_timer.stop();
         mob_timer.stop();
 
-        let hud_node = unsafe { owner.get_typed_node::<CanvasLayer, _>("hud") };
+        let hud_node = unsafe { owner.get_node_as::<CanvasLayer>("hud").unwrap() };
         hud_node
             .cast_instance::<hud::HUD>()
             .and_then(|hud| hud.map(|x, o| x.show_game_over(&*o)).ok())
@@ -42,9 +42,9 @@ impl Main {
 
     #[export]
     fn new_game(&mut self, owner: &Node) {
-        let start_position = unsafe { owner.get_typed_node::<Position2D, _>("start_position") };
-        let player = unsafe { owner.get_typed_node::<Area2D, _>("player") };
-        let start_timer = unsafe { owner.get_typed_node::<Timer, _>("start_timer") };
+        let start_position = unsafe { owner.get_node_as::<Position2D>("start_position").unwrap() };
+        let player = unsafe { owner.get_node_as::<Area2D>("player").unwrap() };
+        let start_timer = unsafe { owner.get_node_as::<Timer>("start_timer").unwrap() };
 
         self.score = 0;
 
@@ -59,7 +59,7 @@ impl Main {
 
         start_timer.start(0.0);
 
-        let hud_node = unsafe { owner.get_typed_node::<CanvasLayer, _>("hud") };
+        let hud_node = unsafe { owner.get_node_as::<CanvasLayer>("hud").unwrap() };
         hud_node
             .cast_instance::<hud::HUD>()
             .and_then(|hud| {
@@ -74,8 +74,8 @@ impl Main {
 
     #[export]
     fn on_start_timer_timeout(&self, owner: &Node) {
-        let mob_timer = unsafe { owner.get_typed_node::<Timer, _>("mob_timer") };
-        let score_timer = unsafe { owner.get_typed_node::<Timer, _>("score_timer") };
+        let mob_timer = unsafe { owner.get_node_as::<Timer>("mob_timer").unwrap() };
+        let score_timer = unsafe { owner.get_node_as::<Timer>("score_timer").unwrap() };
         mob_timer.start(0.0);
         score_timer.start(0.0);
     }
@@ -84,7 +84,7 @@ impl Main {
     fn on_score_timer_timeout(&mut self, owner: &Node) {
         self.score += 1;
 
-        let hud_node = unsafe { owner.get_typed_node::<CanvasLayer, _>("hud") };
+        let hud_node = unsafe { owner.get_node_as::<CanvasLayer>("hud").unwrap() };
         hud_node
             .cast_instance::<hud::HUD>()
             .and_then(|hud| hud.map(|x, o| x.update_score(&*o, self.score)).ok())
@@ -93,10 +93,15 @@ impl Main {
 
     #[export]
     fn on_mob_timer_timeout(&self, owner: &Node) {
-        let mob_spawn_location =
-            unsafe { owner.get_typed_node::<PathFollow2D, _>("mob_path/mob_spawn_locations") };
+        let mob_spawn_location = unsafe {
+            owner
+                .get_node_as::<PathFollow2D>("mob_path/mob_spawn_locations")
+                .unwrap()
+        };
 
-        let mob_scene: Ref<RigidBody2D, _> = instance_scene(&self.mob);
+        let mob_scene = unsafe { self.mob.assume_safe() }
+            .instance_as::<RigidBody2D>()
+            .expect("mob scene should have a RigidBody2D root");
 
         let mut rng = rand::thread_rng();
         let offset = rng.gen_range(std::u32::MIN, std::u32::MAX);
@@ -111,7 +116,8 @@ impl Main {
         mob_scene.set_rotation(direction);
         let d = direction as f32;
 
-        let mob_scene = unsafe { mob_scene.into_shared().assume_safe() };
+        let token = MainThreadToken::try_get().expect("signals are emitted on the main thread");
+        let mob_scene = unsafe { mob_scene.into_shared().assume_safe_on_main_thread(token) };
         owner.add_child(mob_scene, false);
 
         let mob = mob_scene.cast_instance::<mob::Mob>().unwrap();
@@ -123,7 +129,7 @@ impl Main {
             mob_owner
                 .set_linear_velocity(mob_owner.linear_velocity().rotated(Angle { radians: d }));
 
-            let hud_node = unsafe { owner.get_typed_node::<CanvasLayer, _>("hud") };
+            let hud_node = unsafe { owner.get_node_as::<CanvasLayer>("hud").unwrap() };
             let hud = hud_node.cast_instance::<hud::HUD>().unwrap();
 
             hud.map(|_, o| {
@@ -132,7 +138,7 @@ impl Main {
                     mob_owner,
                     "on_start_game",
                     VariantArray::new_shared(),
-                    0,
+                    ConnectFlags::empty(),
                 )
                 .unwrap();
             })
@@ -141,22 +147,3 @@ impl Main {
         .unwrap();
     }
 }
-
-/// Root here is needs to be the same type (or a parent type) of the node that you put in the child
-///   scene as the root. For instance Spatial is used for this example.
-fn instance_scene<Root>(scene: &Ref<PackedScene, Shared>) -> Ref<Root, Unique>
-where
-    Root: gdnative::GodotObject<RefKind = ManuallyManaged> + SubClass<Node>,
-{
-    let scene = unsafe { scene.assume_safe() };
-
-    let instance = scene
-        .instance(PackedScene::GEN_EDIT_STATE_DISABLED)
-        .expect("should be able to instance scene");
-
-    let instance = unsafe { instance.assume_unique() };
-
-    instance
-        .try_cast::<Root>()
-        .expect("root node type should be correct")
-}
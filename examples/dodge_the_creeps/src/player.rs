@@ -1,4 +1,3 @@
-use crate::extensions::NodeExt as _;
 use gdnative::api::{AnimatedSprite, Area2D, CollisionShape2D, PhysicsBody2D};
 use gdnative::prelude::*;
 
@@ -32,15 +31,24 @@ impl Player {
 
     #[export]
     fn _ready(&mut self, owner: &Area2D) {
-        let viewport = unsafe { owner.get_viewport().unwrap().assume_safe() };
+        let token = MainThreadToken::try_get().expect("_ready is called on the main thread");
+        let viewport = unsafe {
+            owner
+                .get_viewport(token)
+                .unwrap()
+                .assume_safe_on_main_thread(token)
+        };
         self.screen_size = viewport.size();
         owner.hide();
     }
 
     #[export]
     fn _process(&mut self, owner: &Area2D, delta: f32) {
-        let animated_sprite =
-            unsafe { owner.get_typed_node::<AnimatedSprite, _>("animated_sprite") };
+        let animated_sprite = unsafe {
+            owner
+                .get_node_as::<AnimatedSprite>("animated_sprite")
+                .unwrap()
+        };
 
         let input = Input::godot_singleton();
         let mut velocity = Vector2::new(0.0, 0.0);
@@ -90,8 +98,11 @@ impl Player {
         owner.hide();
         owner.emit_signal("hit", &[]);
 
-        let collision_shape =
-            unsafe { owner.get_typed_node::<CollisionShape2D, _>("collision_shape_2d") };
+        let collision_shape = unsafe {
+            owner
+                .get_node_as::<CollisionShape2D>("collision_shape_2d")
+                .unwrap()
+        };
 
         collision_shape.set_deferred("disabled", true);
     }
@@ -101,8 +112,11 @@ impl Player {
         owner.set_global_position(pos);
         owner.show();
 
-        let collision_shape =
-            unsafe { owner.get_typed_node::<CollisionShape2D, _>("collision_shape_2d") };
+        let collision_shape = unsafe {
+            owner
+                .get_node_as::<CollisionShape2D>("collision_shape_2d")
+                .unwrap()
+        };
 
         collision_shape.set_disabled(false);
     }
@@ -1,17 +1,12 @@
 use euclid::vec3;
+use gdnative::api::utils::load;
 use gdnative::prelude::*;
 
-#[derive(Debug, Clone, PartialEq)]
-pub enum ManageErrs {
-    CouldNotMakeInstance,
-    RootClassNotSpatial(String),
-}
-
 #[derive(gdnative::NativeClass)]
 #[inherit(Spatial)]
 struct SceneCreate {
     // Store the loaded scene for a very slight performance boost but mostly to show you how.
-    template: Option<Ref<PackedScene, ThreadLocal>>,
+    template: Option<Ref<PackedScene>>,
     children_spawned: u32,
 }
 
@@ -36,10 +31,12 @@ impl SceneCreate {
 
     #[export]
     fn _ready(&mut self, _owner: &Spatial) {
-        self.template = load_scene("res://Child_scene.tscn");
-        match &self.template {
-            Some(_scene) => godot_print!("Loaded child scene successfully!"),
-            None => godot_print!("Could not load child scene. Check name."),
+        match load::<PackedScene>("res://Child_scene.tscn") {
+            Ok(scene) => {
+                self.template = Some(scene);
+                godot_print!("Loaded child scene successfully!");
+            }
+            Err(err) => godot_print!("Could not load child scene: {}", err),
         }
     }
 
@@ -48,7 +45,7 @@ impl SceneCreate {
         godot_print!("Called spawn_one({})", message.to_string());
 
         let template = if let Some(template) = &self.template {
-            template
+            unsafe { template.assume_safe() }
         } else {
             godot_print!("Cannot spawn a child because we couldn't load the template scene");
             return;
@@ -56,7 +53,7 @@ impl SceneCreate {
 
         // Create the scene here. Note that we are hardcoding that the parent must at least be a
         //   child of Spatial in the template argument here...
-        match instance_scene::<Spatial>(template) {
+        match template.instance_as::<Spatial>() {
             Ok(spatial) => {
                 // Here is how you rename the child...
                 let key_str = format!("child_{}", self.children_spawned);
@@ -71,7 +68,7 @@ impl SceneCreate {
                 owner.add_child(spatial.into_shared(), false);
                 self.children_spawned += 1;
             }
-            Err(err) => godot_print!("Could not instance Child : {:?}", err),
+            Err(err) => godot_print!("Could not instance Child : {}", err),
         }
 
         let num_children = owner.get_child_count();
@@ -105,40 +102,20 @@ fn init(handle: InitHandle) {
     handle.add_class::<SceneCreate>();
 }
 
-pub fn load_scene(path: &str) -> Option<Ref<PackedScene, ThreadLocal>> {
-    let scene = ResourceLoader::godot_singleton().load(path, "PackedScene", false)?;
-
-    let scene = unsafe { scene.assume_thread_local() };
-
-    scene.cast::<PackedScene>()
-}
-
-/// Root here is needs to be the same type (or a parent type) of the node that you put in the child
-///   scene as the root. For instance Spatial is used for this example.
-fn instance_scene<Root>(scene: &PackedScene) -> Result<Ref<Root, Unique>, ManageErrs>
-where
-    Root: gdnative::GodotObject<RefKind = ManuallyManaged> + SubClass<Node>,
-{
-    let instance = scene
-        .instance(PackedScene::GEN_EDIT_STATE_DISABLED)
-        .ok_or(ManageErrs::CouldNotMakeInstance)?;
-    let instance = unsafe { instance.assume_unique() };
-
-    instance
-        .try_cast::<Root>()
-        .map_err(|instance| ManageErrs::RootClassNotSpatial(instance.name().to_string()))
-}
-
 fn update_panel(owner: &Spatial, num_children: i64) {
+    // Nodes can only be used on the main thread, where the exported methods are called from the
+    // UI.
+    let token = MainThreadToken::try_get().expect("should be called on the main thread");
+
     // Here is how we call into the panel. First we get its node (we might have saved it
     //   from earlier)
     let panel_node_opt = owner.get_parent().and_then(|parent| {
-        let parent = unsafe { parent.assume_safe() };
+        let parent = unsafe { parent.assume_safe_on_main_thread(token) };
         parent.find_node("Panel", true, false)
     });
 
     if let Some(panel_node) = panel_node_opt {
-        let panel_node = unsafe { panel_node.assume_safe() };
+        let panel_node = unsafe { panel_node.assume_safe_on_main_thread(token) };
 
         // Put the Node
         let mut as_variant = Variant::from_object(panel_node);
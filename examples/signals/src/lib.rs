@@ -1,3 +1,4 @@
+use gdnative::api::object::ConnectFlags;
 use gdnative::prelude::*;
 
 #[derive(NativeClass)]
@@ -67,11 +68,18 @@ impl SignalSubscriber {
 
     #[export]
     fn _ready(&mut self, owner: TRef<Label>) {
+        let token = MainThreadToken::try_get().expect("_ready is called on the main thread");
         let emitter = &mut owner.get_node("../SignalEmitter").unwrap();
-        let emitter = unsafe { emitter.assume_safe() };
+        let emitter = unsafe { emitter.assume_safe_on_main_thread(token) };
 
         emitter
-            .connect("tick", owner, "notify", VariantArray::new_shared(), 0)
+            .connect(
+                "tick",
+                owner,
+                "notify",
+                VariantArray::new_shared(),
+                ConnectFlags::empty(),
+            )
             .unwrap();
         emitter
             .connect(
@@ -79,7 +87,7 @@ impl SignalSubscriber {
                 owner,
                 "notify_with_data",
                 VariantArray::new_shared(),
-                0,
+                ConnectFlags::empty(),
             )
             .unwrap();
     }
@@ -7,21 +7,40 @@ use std::path::PathBuf;
 use std::process::Command;
 
 fn main() {
-    let api_data = std::fs::read_to_string("api.json").expect("Unable to read api.json");
+    let api_path = env_path(API_JSON_ENV).unwrap_or_else(|| PathBuf::from("api.json"));
+    let docs_path = env_path(API_DOCS_ENV).unwrap_or_else(|| PathBuf::from("docs"));
+
+    let api_data = std::fs::read_to_string(&api_path)
+        .unwrap_or_else(|err| panic!("Unable to read {}: {}", api_path.display(), err));
 
     let out_path = PathBuf::from(env::var("OUT_DIR").unwrap());
 
     let generated_rs = out_path.join("generated.rs");
     let icalls_rs = out_path.join("icalls.rs");
 
-    let api = Api::new(&api_data);
-    let docs = GodotXMLDocs::new("docs");
+    let mut api = Api::try_new(&api_data)
+        .unwrap_or_else(|err| panic!("Unable to use {}: {}", api_path.display(), err));
+    check_required_classes(&api, &api_path);
+
+    if let Some(classes) = class_allowlist() {
+        retain_classes(&mut api, &classes);
+    }
+
+    if !docs_path.is_dir() {
+        panic!(
+            "{} is not a folder with the class documentation",
+            docs_path.display()
+        );
+    }
+    let docs = GodotXMLDocs::new(&docs_path.to_string_lossy());
     let binding_res = generate_bindings(&api, Some(&docs));
 
     {
         let mut output = BufWriter::new(File::create(&generated_rs).unwrap());
 
         generate(&out_path, &mut output, &binding_res);
+
+        write!(&mut output, "{}", binding_res.virtual_methods).unwrap();
     }
 
     {
@@ -38,9 +57,106 @@ fn main() {
     // build.rs will automatically be recompiled and run if it's dependencies are updated.
     // Ignoring all but build.rs will keep from needless rebuilds.
     // Manually rebuilding the crate will ignore this.
-    println!("cargo:rerun-if-changed=docs/");
-    println!("cargo:rerun-if-changed=api.json");
+    println!("cargo:rerun-if-changed={}", docs_path.display());
+    println!("cargo:rerun-if-changed={}", api_path.display());
     println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed={}", CLASSES_ENV);
+    println!("cargo:rerun-if-env-changed={}", API_JSON_ENV);
+    println!("cargo:rerun-if-env-changed={}", API_DOCS_ENV);
+}
+
+/// Environment variable with the path of the `api.json` to generate the bindings from, e.g.
+/// one generated by an engine fork or a build with custom modules. The bundled `api.json` is
+/// used if it's unset.
+const API_JSON_ENV: &str = "GODOT_API_JSON";
+
+/// Environment variable with the path of the folder with the class documentation in XML
+/// (`doc/classes` in the engine repository). The bundled documentation is used if it's unset.
+const API_DOCS_ENV: &str = "GODOT_API_DOCS";
+
+fn env_path(name: &str) -> Option<PathBuf> {
+    env::var_os(name)
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+}
+
+/// Environment variable with the names of the classes to generate, separated by commas or
+/// whitespace. All classes are generated if it's unset.
+const CLASSES_ENV: &str = "GODOT_BINDINGS_CLASSES";
+
+/// Classes that are always generated when `GODOT_BINDINGS_CLASSES` is set, since they're used by
+/// the crate itself or re-exported by the `gdnative` prelude.
+const REQUIRED_CLASSES: &[&str] = &[
+    "Button",
+    "CanvasItem",
+    "CanvasLayer",
+    "ColorRect",
+    "Control",
+    "GlobalConstants",
+    "Image",
+    "Input",
+    "InputEvent",
+    "InputEventKey",
+    "KinematicBody",
+    "KinematicBody2D",
+    "Label",
+    "Node",
+    "Node2D",
+    "Object",
+    "PackedScene",
+    "Reference",
+    "ResourceLoader",
+    "SceneTree",
+    "Shader",
+    "Spatial",
+    "Sprite",
+    "Texture",
+    "Timer",
+    "Tween",
+    "Viewport",
+];
+
+/// Checks that a custom `api.json` contains the classes used by the crate itself.
+fn check_required_classes(api: &Api, api_path: &std::path::Path) {
+    let missing: Vec<_> = REQUIRED_CLASSES
+        .iter()
+        .filter(|name| api.find_class(name).is_none())
+        .collect();
+
+    if !missing.is_empty() {
+        panic!(
+            "{} is missing the classes {:?}, which are required by gdnative-bindings",
+            api_path.display(),
+            missing
+        );
+    }
+}
+
+fn class_allowlist() -> Option<Vec<String>> {
+    let classes = env::var(CLASSES_ENV).ok()?;
+    let classes = classes
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|name| !name.is_empty())
+        .map(String::from)
+        .collect();
+
+    Some(classes)
+}
+
+/// Removes all classes from `api`, except for `classes`, the required classes, and the classes
+/// they depend on.
+fn retain_classes(api: &mut Api, classes: &[String]) {
+    for name in classes {
+        if api.find_class(name).is_none() {
+            panic!("{} contains unknown class {:?}", CLASSES_ENV, name);
+        }
+    }
+
+    let requested = classes.iter().map(String::as_str);
+    let retained =
+        classes_with_dependencies(api, requested.chain(REQUIRED_CLASSES.iter().copied()));
+
+    api.classes.retain(|class| retained.contains(&class.name));
 }
 
 /// Output all the class bindings into the `generated.rs` file.
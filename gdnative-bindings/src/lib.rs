@@ -9,3 +9,5 @@ mod generated;
 pub use generated::*;
 
 pub(crate) mod icalls;
+
+pub mod utils;
@@ -0,0 +1,583 @@
+//! Utility functions and extension traits that depend on generated bindings.
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use parking_lot::Mutex;
+
+use gdnative_core::core_types::NodePath;
+use gdnative_core::nativescript::{Instance, NativeClass, RefInstance};
+use gdnative_core::object::{GodotObject, Ref, SubClass, TRef};
+use gdnative_core::ref_kind::{ManuallyManaged, RefCounted};
+use gdnative_core::thread_access::{MainThreadToken, Shared, Unique};
+use gdnative_core::NewRef;
+
+use crate::generated::{Node, PackedScene, Resource, ResourceLoader};
+
+/// Error returned by the `try_` methods of `NodeExt`.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub enum GetNodeError {
+    /// No node exists at the path.
+    NotFound { path: String },
+    /// The node at the path isn't of the expected class.
+    WrongClass {
+        path: String,
+        class: String,
+        expected: &'static str,
+    },
+    /// The node at the path doesn't have a script of the expected class attached.
+    WrongScript {
+        path: String,
+        expected: &'static str,
+    },
+}
+
+impl fmt::Display for GetNodeError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GetNodeError::NotFound { path } => write!(f, "no node found at {:?}", path),
+            GetNodeError::WrongClass {
+                path,
+                class,
+                expected,
+            } => write!(
+                f,
+                "node at {:?} is of class {}, expected {}",
+                path, class, expected
+            ),
+            GetNodeError::WrongScript { path, expected } => write!(
+                f,
+                "node at {:?} does not have a {} script attached",
+                path, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GetNodeError {}
+
+/// Extension methods for `Node` and its subclasses, to get nodes in the scene tree as specific
+/// types or script instances.
+///
+/// Paths are relative to `self`, like in `Node::get_node`. Unlike `get_node`, nothing is
+/// reported to the engine if no node exists at the path.
+pub trait NodeExt {
+    /// Gets a node at `path`, assumes that it's safe to use, and casts it to `T`. Returns
+    /// `None` if no node exists at the path, or if it isn't of the class `T`.
+    ///
+    /// # Safety
+    ///
+    /// Must be called from the main thread. See `Ref::assume_safe_on_main_thread`.
+    unsafe fn get_node_as<'a, T>(&self, path: impl Into<NodePath>) -> Option<TRef<'a, T, Shared>>
+    where
+        T: GodotObject + SubClass<Node>,
+    {
+        self.try_get_node_as(path).ok()
+    }
+
+    /// Gets a node at `path`, assumes that it's safe to use, and casts it to `T`, returning an
+    /// error describing the failure if not possible.
+    ///
+    /// # Safety
+    ///
+    /// Must be called from the main thread. See `Ref::assume_safe_on_main_thread`.
+    unsafe fn try_get_node_as<'a, T>(
+        &self,
+        path: impl Into<NodePath>,
+    ) -> Result<TRef<'a, T, Shared>, GetNodeError>
+    where
+        T: GodotObject + SubClass<Node>;
+
+    /// Gets a node at `path`, assumes that it's safe to use, and casts it to an instance of the
+    /// script class `C`. Returns `None` if no node exists at the path, if it isn't of the class
+    /// `C::Base`, or if it doesn't have a script of the class `C` attached.
+    ///
+    /// # Safety
+    ///
+    /// Must be called from the main thread. See `Ref::assume_safe_on_main_thread`.
+    unsafe fn get_node_as_instance<'a, C>(
+        &self,
+        path: impl Into<NodePath>,
+    ) -> Option<RefInstance<'a, C, Shared>>
+    where
+        C: NativeClass,
+        C::Base: SubClass<Node>,
+    {
+        self.try_get_node_as_instance(path).ok()
+    }
+
+    /// Gets a node at `path`, assumes that it's safe to use, and casts it to an instance of the
+    /// script class `C`, returning an error describing the failure if not possible.
+    ///
+    /// # Safety
+    ///
+    /// Must be called from the main thread. See `Ref::assume_safe_on_main_thread`.
+    unsafe fn try_get_node_as_instance<'a, C>(
+        &self,
+        path: impl Into<NodePath>,
+    ) -> Result<RefInstance<'a, C, Shared>, GetNodeError>
+    where
+        C: NativeClass,
+        C::Base: SubClass<Node>;
+}
+
+impl<N: SubClass<Node>> NodeExt for N {
+    #[inline]
+    unsafe fn try_get_node_as<'a, T>(
+        &self,
+        path: impl Into<NodePath>,
+    ) -> Result<TRef<'a, T, Shared>, GetNodeError>
+    where
+        T: GodotObject + SubClass<Node>,
+    {
+        let path = path.into();
+        let node = self
+            .upcast::<Node>()
+            .get_node_or_null(path.new_ref())
+            .ok_or_else(|| GetNodeError::NotFound {
+                path: path.to_string(),
+            })?
+            .assume_safe_on_main_thread(MainThreadToken::assume_main_thread());
+
+        node.cast().ok_or_else(|| GetNodeError::WrongClass {
+            path: path.to_string(),
+            class: node.get_class().to_string(),
+            expected: T::class_name(),
+        })
+    }
+
+    #[inline]
+    unsafe fn try_get_node_as_instance<'a, C>(
+        &self,
+        path: impl Into<NodePath>,
+    ) -> Result<RefInstance<'a, C, Shared>, GetNodeError>
+    where
+        C: NativeClass,
+        C::Base: SubClass<Node>,
+    {
+        let path = path.into();
+        let node = self.try_get_node_as::<C::Base>(path.new_ref())?;
+
+        RefInstance::try_from_base(node).ok_or_else(|| GetNodeError::WrongScript {
+            path: path.to_string(),
+            expected: C::class_name(),
+        })
+    }
+}
+
+/// Types of fields that can be resolved with the `#[node]` attribute of the `NativeClass`
+/// derive macro.
+///
+/// Implemented for `Option<Ref<T, Shared>>` of node classes, and `Option<Instance<C, Shared>>`
+/// of script classes extending nodes.
+pub trait NodeField: Sized {
+    /// Gets the node at `path` relative to `owner`.
+    ///
+    /// # Safety
+    ///
+    /// Must be called from the main thread. See `Ref::assume_safe_on_main_thread`.
+    unsafe fn get_node(owner: &Node, path: NodePath) -> Result<Self, GetNodeError>;
+}
+
+impl<T> NodeField for Option<Ref<T, Shared>>
+where
+    T: GodotObject + SubClass<Node>,
+{
+    #[inline]
+    unsafe fn get_node(owner: &Node, path: NodePath) -> Result<Self, GetNodeError> {
+        owner
+            .try_get_node_as::<T>(path)
+            .map(|node| Some(node.claim()))
+    }
+}
+
+impl<C> NodeField for Option<Instance<C, Shared>>
+where
+    C: NativeClass,
+    C::Base: SubClass<Node>,
+{
+    #[inline]
+    unsafe fn get_node(owner: &Node, path: NodePath) -> Result<Self, GetNodeError> {
+        owner
+            .try_get_node_as_instance::<C>(path)
+            .map(|instance| Some(instance.claim()))
+    }
+}
+
+/// Error returned by `load` and `Preload::get`.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub enum LoadError {
+    /// No resource exists at the path.
+    NotFound { path: String },
+    /// The resource at the path exists, but couldn't be loaded.
+    Failed { path: String },
+    /// The resource at the path isn't of the expected class.
+    WrongClass {
+        path: String,
+        class: String,
+        expected: &'static str,
+    },
+}
+
+impl fmt::Display for LoadError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::NotFound { path } => write!(f, "no resource found at {:?}", path),
+            LoadError::Failed { path } => write!(f, "failed to load resource at {:?}", path),
+            LoadError::WrongClass {
+                path,
+                class,
+                expected,
+            } => write!(
+                f,
+                "resource at {:?} is of class {}, expected {}",
+                path, class, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+/// Loads the resource at `path` with `ResourceLoader`, and casts it to `T`.
+///
+/// Resources are cached by the engine, so loading the same path again returns the same
+/// resource unless it has been freed in the meantime.
+///
+/// # Examples
+///
+/// ```ignore
+/// let scene = load::<PackedScene>("res://Player.tscn")?;
+/// ```
+#[inline]
+pub fn load<T>(path: &str) -> Result<Ref<T, Shared>, LoadError>
+where
+    T: GodotObject<RefKind = RefCounted> + SubClass<Resource>,
+{
+    let loader = ResourceLoader::godot_singleton();
+
+    if !loader.exists(path, "") {
+        return Err(LoadError::NotFound { path: path.into() });
+    }
+
+    let resource = loader
+        .load(path, "", false)
+        .ok_or_else(|| LoadError::Failed { path: path.into() })?;
+
+    resource
+        .try_cast()
+        .map_err(|resource| LoadError::WrongClass {
+            path: path.into(),
+            class: resource_class(&resource),
+            expected: T::class_name(),
+        })
+}
+
+fn resource_class(resource: &Ref<Resource, Shared>) -> String {
+    // The class of an object never changes, so it can be read from any thread.
+    unsafe { resource.assume_safe() }.get_class().to_string()
+}
+
+/// A resource that is loaded the first time it's used, and kept afterwards, similar to
+/// `preload` in GDScript. It's meant to be used in statics.
+///
+/// Unlike `preload`, the path isn't checked at compile time, and the resource is only loaded
+/// when `get` is first called. Failures aren't kept, so a failed load is retried next time.
+///
+/// Statics are never dropped, so the resource is kept until `clear` is called. It must be
+/// called before the library is terminated, e.g. in the callback of `godot_gdnative_terminate!`,
+/// since the reference can't be released once the API is unbound. Otherwise, the resource is
+/// leaked, which the engine reports when it exits.
+///
+/// # Examples
+///
+/// ```ignore
+/// static PLAYER: Preload<PackedScene> = Preload::new("res://Player.tscn");
+///
+/// let scene = PLAYER.get()?;
+///
+/// fn terminate(_info: &TerminateInfo) {
+///     PLAYER.clear();
+/// }
+///
+/// godot_gdnative_terminate!(terminate);
+/// ```
+pub struct Preload<T> {
+    path: &'static str,
+    resource: Mutex<Option<Ref<Resource, Shared>>>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Preload<T> {
+    /// Creates a `Preload` for the resource at `path`, without loading it.
+    #[inline]
+    pub const fn new(path: &'static str) -> Self {
+        Preload {
+            path,
+            resource: parking_lot::const_mutex(None),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the path of the resource.
+    #[inline]
+    pub fn path(&self) -> &'static str {
+        self.path
+    }
+
+    /// Returns `true` if the resource is currently loaded.
+    #[inline]
+    pub fn is_loaded(&self) -> bool {
+        self.resource.lock().is_some()
+    }
+
+    /// Releases the resource, if it's loaded. The next call to `get` loads it again.
+    #[inline]
+    pub fn clear(&self) {
+        // The reference is dropped after the lock is released.
+        let _resource = self.resource.lock().take();
+    }
+}
+
+impl<T> Preload<T>
+where
+    T: GodotObject<RefKind = RefCounted> + SubClass<Resource>,
+{
+    /// Returns the resource, loading it with `load` if this is the first successful call.
+    ///
+    /// The lock isn't held while loading, so loading the resource can call `get` again, e.g.
+    /// from the scripts of resources it depends on. If the resource is stored by another call
+    /// in the meantime, that one is returned, and the one loaded by this call is released.
+    #[inline]
+    pub fn get(&self) -> Result<Ref<T, Shared>, LoadError> {
+        if let Some(resource) = self.loaded() {
+            return Ok(resource);
+        }
+
+        let loaded = load::<T>(self.path)?;
+
+        // The reference that isn't kept is dropped after the lock is released.
+        let mut resource = self.resource.lock();
+        if let Some(resource) = &*resource {
+            return Ok(Self::cast_loaded(resource));
+        }
+
+        *resource = Some(loaded.clone().upcast());
+        Ok(loaded)
+    }
+
+    fn loaded(&self) -> Option<Ref<T, Shared>> {
+        self.resource.lock().as_ref().map(Self::cast_loaded)
+    }
+
+    fn cast_loaded(resource: &Ref<Resource, Shared>) -> Ref<T, Shared> {
+        resource
+            .clone()
+            .cast()
+            .expect("class should be checked when loading")
+    }
+}
+
+impl<T> fmt::Debug for Preload<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Preload")
+            .field("path", &self.path)
+            .field("loaded", &self.is_loaded())
+            .finish()
+    }
+}
+
+/// Error returned by the methods of `PackedSceneExt`.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub enum InstanceError {
+    /// The scene couldn't be instanced, e.g. because it's empty.
+    Failed { path: String },
+    /// The root node of the scene isn't of the expected class.
+    WrongClass {
+        path: String,
+        class: String,
+        expected: &'static str,
+    },
+    /// The root node of the scene doesn't have a script of the expected class attached.
+    WrongScript {
+        path: String,
+        expected: &'static str,
+    },
+}
+
+impl fmt::Display for InstanceError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InstanceError::Failed { path } => write!(f, "failed to instance scene {:?}", path),
+            InstanceError::WrongClass {
+                path,
+                class,
+                expected,
+            } => write!(
+                f,
+                "root node of scene {:?} is of class {}, expected {}",
+                path, class, expected
+            ),
+            InstanceError::WrongScript { path, expected } => write!(
+                f,
+                "root node of scene {:?} does not have a {} script attached",
+                path, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for InstanceError {}
+
+/// Extension methods for `PackedScene`, to instance scenes with roots of specific types or
+/// script instances.
+///
+/// The new nodes are owned by the caller, and should be added to the scene tree or freed. On
+/// failure, the nodes are freed before the error is returned.
+pub trait PackedSceneExt {
+    /// Instances the scene, and casts the root node to `T`.
+    fn instance_as<T>(&self) -> Result<Ref<T, Unique>, InstanceError>
+    where
+        T: GodotObject<RefKind = ManuallyManaged> + SubClass<Node>;
+
+    /// Instances the scene, and casts the root node to an instance of the script class `C`.
+    fn instance_as_script<C>(&self) -> Result<Instance<C, Unique>, InstanceError>
+    where
+        C: NativeClass,
+        C::Base: GodotObject<RefKind = ManuallyManaged> + SubClass<Node>;
+}
+
+impl PackedSceneExt for PackedScene {
+    #[inline]
+    fn instance_as<T>(&self) -> Result<Ref<T, Unique>, InstanceError>
+    where
+        T: GodotObject<RefKind = ManuallyManaged> + SubClass<Node>,
+    {
+        let node = self
+            .instance(PackedScene::GEN_EDIT_STATE_DISABLED)
+            .ok_or_else(|| InstanceError::Failed {
+                path: self.path().to_string(),
+            })?;
+
+        // Nodes of a new instance aren't referenced anywhere else yet.
+        let node = unsafe { node.assume_unique() };
+
+        node.try_cast::<T>().map_err(|node| {
+            let error = InstanceError::WrongClass {
+                path: self.path().to_string(),
+                class: node.get_class().to_string(),
+                expected: T::class_name(),
+            };
+            node.free();
+            error
+        })
+    }
+
+    #[inline]
+    fn instance_as_script<C>(&self) -> Result<Instance<C, Unique>, InstanceError>
+    where
+        C: NativeClass,
+        C::Base: GodotObject<RefKind = ManuallyManaged> + SubClass<Node>,
+    {
+        let node = self.instance_as::<C::Base>()?;
+
+        Instance::<C, Unique>::try_from_base(node).map_err(|node| {
+            node.upcast::<Node>().free();
+            InstanceError::WrongScript {
+                path: self.path().to_string(),
+                expected: C::class_name(),
+            }
+        })
+    }
+}
+
+/// Implemented by the `NativeClass` derive macro for types with `#[node]` fields.
+#[doc(hidden)]
+pub trait ResolveNodes: NativeClass {
+    /// Resolves all `#[node]` fields, reporting the ones that can't be resolved to the engine.
+    ///
+    /// # Safety
+    ///
+    /// Must be called from the main thread. See `Ref::assume_safe_on_main_thread`.
+    unsafe fn resolve_nodes(&mut self, owner: &Node);
+}
+
+/// Implemented by `#[methods]` for types with an exported `_ready` method taking `&mut self`
+/// and a named owner argument, where `#[node]` fields are resolved. Required by the
+/// `NativeClass` derive macro for types with `#[node]` fields, which would never be resolved
+/// otherwise.
+#[doc(hidden)]
+pub trait ResolvesNodesInReady: NativeClass {}
+
+/// Probe type used by `#[methods]` to resolve `#[node]` fields at the start of `_ready`, if the
+/// type has any.
+#[doc(hidden)]
+pub struct ResolveNodesProbe<C>(PhantomData<fn() -> C>);
+
+impl<C> ResolveNodesProbe<C> {
+    #[inline]
+    pub fn new() -> Self {
+        ResolveNodesProbe(PhantomData)
+    }
+}
+
+impl<C> Default for ResolveNodesProbe<C> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[doc(hidden)]
+pub trait ResolveNodesIfAny<C: NativeClass> {
+    unsafe fn resolve_nodes(&self, this: &mut C, owner: &C::Base);
+}
+
+impl<C> ResolveNodesIfAny<C> for ResolveNodesProbe<C>
+where
+    C: ResolveNodes,
+    C::Base: SubClass<Node>,
+{
+    #[inline]
+    unsafe fn resolve_nodes(&self, this: &mut C, owner: &C::Base) {
+        this.resolve_nodes(owner.upcast())
+    }
+}
+
+#[doc(hidden)]
+pub trait SkipResolveNodes<C: NativeClass> {
+    #[inline]
+    unsafe fn resolve_nodes(&self, _this: &mut C, _owner: &C::Base) {}
+}
+
+impl<'a, C: NativeClass> SkipResolveNodes<C> for &'a ResolveNodesProbe<C> {}
+
+/// Resolves the `#[node]` fields of `$this`, if any. Inserted at the start of exported `_ready`
+/// methods by `#[methods]`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __gdnative_resolve_nodes {
+    ($this:ident, $owner:ident) => {{
+        #[allow(unused_imports)]
+        use $crate::utils::{ResolveNodesIfAny, ResolveNodesProbe, SkipResolveNodes};
+        // The owner of an exported method is assumed to be safe to use, and so are its children
+        // in `_ready`.
+        #[allow(unused_unsafe)]
+        unsafe {
+            (&ResolveNodesProbe::<Self>::new()).resolve_nodes($this, &*$owner);
+        }
+    }};
+}
+
+/// Marks `$ty` as resolving its `#[node]` fields in `_ready`. Emitted by `#[methods]`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __gdnative_resolves_nodes_in_ready {
+    ($ty:ty) => {
+        impl $crate::utils::ResolvesNodesInReady for $ty {}
+    };
+}
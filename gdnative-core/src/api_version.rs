@@ -0,0 +1,49 @@
+//! Queries for the optional GDNative APIs provided by the engine.
+//!
+//! Only version 1.0 of the core API, and NativeScript 1.0 with the `nativescript` feature, are
+//! required to load a library. Newer minor versions and extensions like NativeScript 1.1 might
+//! be missing on older 3.x engines. The functions of this crate that depend on them fall back
+//! as follows:
+//!
+//! - Without core 1.1, `Ref::is_instance_sane` always returns `false`, and is skipped by the
+//!   debug assertions of `assume_safe` and `assume_unique`. `VariantArray::duplicate` copies
+//!   the elements one by one, and `Variant::evaluate` only supports comparisons.
+//! - Without core 1.2, `try_from_instance_id`, `WeakObject::upgrade` and related functions
+//!   always return `None`, and classes registered with `add_reloadable_class` don't keep their
+//!   state when the library is reloaded.
+//! - Without NativeScript 1.1, type tags aren't registered, so `Instance::try_from_base` and
+//!   related functions always fail, and `profiling::add_data` does nothing.
+//!
+//! Calling any other function that isn't provided by the engine, like those of the generated
+//! bindings for other extensions, aborts the process with the name of the function. The
+//! queries here can be used to check for availability beforehand.
+//!
+//! All queries return `false` if the API isn't bound yet, i.e. before `gdnative_init`.
+
+use crate::private::try_get_api;
+
+macro_rules! api_version_queries {
+    ($($query:ident: $api:literal,)*) => {
+        $(
+            #[doc = "Returns `true` if the engine provides the "]
+            #[doc = $api]
+            #[doc = " API."]
+            #[inline]
+            pub fn $query() -> bool {
+                try_get_api().map_or(false, |api| api.$query())
+            }
+        )*
+    };
+}
+
+api_version_queries! {
+    has_core_1_1: "core 1.1",
+    has_core_1_2: "core 1.2",
+    has_nativescript_1_0: "NativeScript 1.0",
+    has_nativescript_1_1: "NativeScript 1.1",
+    has_pluginscript_1_0: "PluginScript 1.0",
+    has_arvr_1_1: "ARVR 1.1",
+    has_videodecoder_0_1: "VideoDecoder 0.1",
+    has_net_3_1: "Net 3.1",
+    has_net_3_2: "Net 3.2",
+}
@@ -11,6 +11,7 @@ pub mod error;
 pub mod float32_array;
 pub mod int32_array;
 pub mod node_path;
+pub mod node_path_buf;
 pub mod point2;
 pub mod rid;
 pub mod string;
@@ -34,6 +35,7 @@ pub use error::GodotError;
 pub use float32_array::*;
 pub use int32_array::*;
 pub use node_path::*;
+pub use node_path_buf::*;
 pub use point2::*;
 pub use rid::*;
 pub use string::*;
@@ -0,0 +1,321 @@
+use crate::core_types::NodePath;
+use std::fmt;
+use std::str::FromStr;
+
+/// A node path parsed on the Rust side, for manipulating paths without going through the engine.
+///
+/// Like `NodePath`, a `NodePathBuf` consists of node names, separated by slashes, followed by
+/// “sub-node” (resource or property) names, each prefixed by a colon, e.g.
+/// `"Path2D/PathFollow2D/Sprite:texture:size"`. Paths are parsed the same way as in the engine:
+/// empty names are skipped, and a single trailing colon is allowed but not kept.
+///
+/// `NodePathBuf` converts into `NodePath`, so it can be passed to methods like `Node::get_node`.
+/// Paths known at compile time can be created with the `node_path!` macro, which reports
+/// invalid paths as compile errors.
+///
+/// Paths are compared and manipulated name by name. `.` and `..` are kept as they are, and not
+/// resolved.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct NodePathBuf {
+    absolute: bool,
+    names: Vec<String>,
+    subnames: Vec<String>,
+}
+
+impl NodePathBuf {
+    /// Creates an empty relative path.
+    #[inline]
+    pub fn new() -> Self {
+        NodePathBuf::default()
+    }
+
+    /// Creates a path from its parts, which are assumed to be valid. Used by `node_path!`.
+    #[doc(hidden)]
+    #[inline]
+    pub fn from_parts_unchecked(absolute: bool, names: &[&str], subnames: &[&str]) -> Self {
+        NodePathBuf {
+            absolute,
+            names: names.iter().map(|&name| name.to_owned()).collect(),
+            subnames: subnames.iter().map(|&name| name.to_owned()).collect(),
+        }
+    }
+
+    /// Returns `true` if the path is empty, i.e. relative, with no names or subnames.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        !self.absolute && self.names.is_empty() && self.subnames.is_empty()
+    }
+
+    /// Returns `true` if the path is absolute, i.e. starts with a slash.
+    #[inline]
+    pub fn is_absolute(&self) -> bool {
+        self.absolute
+    }
+
+    /// Returns the node names of the path.
+    #[inline]
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Returns the resource or property names of the path, following the node names.
+    #[inline]
+    pub fn subnames(&self) -> &[String] {
+        &self.subnames
+    }
+
+    /// Returns an iterator over the node names of the path.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &str> + '_ {
+        self.names.iter().map(String::as_str)
+    }
+
+    /// Returns the path to the node `other` refers to, relative to the node `self` refers to.
+    /// The subnames of `self` are dropped. If `other` is absolute, it's returned as is.
+    #[inline]
+    pub fn join(&self, other: &NodePathBuf) -> NodePathBuf {
+        if other.absolute {
+            return other.clone();
+        }
+
+        NodePathBuf {
+            absolute: self.absolute,
+            names: self.names.iter().chain(&other.names).cloned().collect(),
+            subnames: other.subnames.clone(),
+        }
+    }
+
+    /// Returns the path to the parent of the node `self` refers to, without any subnames, or
+    /// `None` if the path has no node names.
+    #[inline]
+    pub fn parent(&self) -> Option<NodePathBuf> {
+        let (_, names) = self.names.split_last()?;
+        Some(NodePathBuf {
+            absolute: self.absolute,
+            names: names.to_vec(),
+            subnames: Vec::new(),
+        })
+    }
+
+    /// Returns a relative path equivalent to `self` from the node `base` refers to, using `..`
+    /// to go up the tree where needed. The subnames of `base` are ignored.
+    ///
+    /// Returns `None` if only one of the paths is absolute.
+    #[inline]
+    pub fn relative_to(&self, base: &NodePathBuf) -> Option<NodePathBuf> {
+        if self.absolute != base.absolute {
+            return None;
+        }
+
+        let common = self
+            .names
+            .iter()
+            .zip(&base.names)
+            .take_while(|(a, b)| a == b)
+            .count();
+
+        let mut names = vec!["..".to_owned(); base.names.len() - common];
+        names.extend_from_slice(&self.names[common..]);
+
+        Some(NodePathBuf {
+            absolute: false,
+            names,
+            subnames: self.subnames.clone(),
+        })
+    }
+
+    /// Converts the path into a `NodePath`.
+    #[inline]
+    pub fn to_node_path(&self) -> NodePath {
+        NodePath::from_str(&self.to_string())
+    }
+}
+
+impl fmt::Display for NodePathBuf {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.absolute {
+            f.write_str("/")?;
+        }
+
+        for (i, name) in self.names.iter().enumerate() {
+            if i > 0 {
+                f.write_str("/")?;
+            }
+            f.write_str(name)?;
+        }
+
+        for subname in &self.subnames {
+            write!(f, ":{}", subname)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Error returned when parsing a `NodePathBuf` fails.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseNodePathError {
+    path: String,
+}
+
+impl fmt::Display for ParseNodePathError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid node path {:?}: empty subname", self.path)
+    }
+}
+
+impl std::error::Error for ParseNodePathError {}
+
+impl FromStr for NodePathBuf {
+    type Err = ParseNodePathError;
+
+    // The `node_path!` macro in `gdnative-derive` validates paths the same way.
+    #[inline]
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        let (node_path, subpath) = match path.find(':') {
+            Some(index) => (&path[..index], Some(&path[index + 1..])),
+            None => (path, None),
+        };
+
+        let mut subnames = Vec::new();
+        if let Some(subpath) = subpath {
+            let mut parts = subpath.split(':').peekable();
+            while let Some(part) = parts.next() {
+                if !part.is_empty() {
+                    subnames.push(part.to_owned());
+                } else if parts.peek().is_some() {
+                    return Err(ParseNodePathError {
+                        path: path.to_owned(),
+                    });
+                }
+            }
+        }
+
+        Ok(NodePathBuf {
+            absolute: node_path.starts_with('/'),
+            names: node_path
+                .split('/')
+                .filter(|name| !name.is_empty())
+                .map(str::to_owned)
+                .collect(),
+            subnames,
+        })
+    }
+}
+
+impl From<NodePathBuf> for NodePath {
+    #[inline]
+    fn from(path: NodePathBuf) -> NodePath {
+        path.to_node_path()
+    }
+}
+
+impl<'a> From<&'a NodePathBuf> for NodePath {
+    #[inline]
+    fn from(path: &'a NodePathBuf) -> NodePath {
+        path.to_node_path()
+    }
+}
+
+impl NodePath {
+    /// Parses the path on the Rust side, for manipulation without going through the engine.
+    #[inline]
+    pub fn to_path_buf(&self) -> NodePathBuf {
+        // Paths are already validated by the engine.
+        self.to_string().parse().unwrap_or_default()
+    }
+}
+
+godot_test!(test_node_path_buf_conversion {
+    let path: NodePathBuf = "/root/Main/Player:position:x".parse().unwrap();
+    let node_path = path.to_node_path();
+    assert!(node_path.is_absolute());
+    assert_eq!("/root/Main/Player:position:x", node_path.to_string());
+    assert_eq!(path, node_path.to_path_buf());
+
+    assert!(NodePath::from(NodePathBuf::new()).is_empty());
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(s: &str) -> NodePathBuf {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_like_the_engine() {
+        let p = path("Player/Sprite:modulate:a");
+        assert!(!p.is_absolute());
+        assert_eq!(&["Player", "Sprite"], p.names());
+        assert_eq!(&["modulate", "a"], p.subnames());
+
+        let p = path("/root//Main/");
+        assert!(p.is_absolute());
+        assert_eq!(&["root", "Main"], p.names());
+        assert_eq!("/root/Main", p.to_string());
+
+        assert_eq!(path("Sprite:texture"), path("Sprite:texture:"));
+        assert_eq!(&["a"], path(":a").subnames());
+        assert!(path("").is_empty());
+        assert!(!path("/").is_empty());
+
+        assert!("Sprite::texture".parse::<NodePathBuf>().is_err());
+        assert!("Sprite:texture::".parse::<NodePathBuf>().is_err());
+    }
+
+    #[test]
+    fn display_round_trips() {
+        for s in &[
+            "",
+            "/",
+            ".",
+            "../Foo",
+            "/root/Main:position:x",
+            ":a:b",
+            "A/B/C",
+        ] {
+            assert_eq!(*s, path(s).to_string());
+        }
+    }
+
+    #[test]
+    fn join() {
+        assert_eq!(path("A/B/C:x"), path("A/B:y").join(&path("C:x")));
+        assert_eq!(path("/root/A"), path("/root").join(&path("A")));
+        assert_eq!(path("/root/A"), path("B").join(&path("/root/A")));
+    }
+
+    #[test]
+    fn parent() {
+        assert_eq!(Some(path("A/B")), path("A/B/C:x").parent());
+        assert_eq!(Some(path("/")), path("/root").parent());
+        assert_eq!(Some(path("")), path("A").parent());
+        assert_eq!(None, path(":x").parent());
+    }
+
+    #[test]
+    fn relative_to() {
+        let base = path("/root/Main/Player");
+        assert_eq!(
+            Some(path("Sprite:modulate")),
+            path("/root/Main/Player/Sprite:modulate").relative_to(&base)
+        );
+        assert_eq!(
+            Some(path("../HUD/Label")),
+            path("/root/Main/HUD/Label").relative_to(&base)
+        );
+        assert_eq!(Some(path("")), base.relative_to(&base));
+        assert_eq!(None, path("Main").relative_to(&base));
+    }
+
+    #[test]
+    fn iter() {
+        let p = path("/A/B/C:x");
+        let names: Vec<_> = p.iter().collect();
+        assert_eq!(vec!["A", "B", "C"], names);
+    }
+}
@@ -141,7 +141,7 @@ pub enum CallError {
 
 impl CallError {
     #[inline]
-    fn from_sys(v: sys::godot_variant_call_error_error) -> Result<(), CallError> {
+    pub(crate) fn from_sys(v: sys::godot_variant_call_error_error) -> Result<(), CallError> {
         if v == sys::godot_variant_call_error_error_GODOT_CALL_ERROR_CALL_OK {
             Ok(())
         } else {
@@ -692,25 +692,43 @@ impl Variant {
         method: impl Into<GodotString>,
         args: &[Variant],
     ) -> Result<Variant, CallError> {
-        let method = method.into();
+        self.call_raw(&method.into(), args)
+            .map_err(|err| CallError::from_sys(err.error).unwrap_err())
+    }
+
+    /// Calls `method` with `args`, returning the full error reported by the engine on failure,
+    /// including the argument and type it refers to.
+    #[inline]
+    pub(crate) fn call_raw(
+        &mut self,
+        method: &GodotString,
+        args: &[Variant],
+    ) -> Result<Variant, sys::godot_variant_call_error> {
         unsafe {
             let api = get_api();
             let mut err = sys::godot_variant_call_error::default();
             let mut arg_refs = args.iter().map(Variant::sys).collect::<Vec<_>>();
-            let variant = (api.godot_variant_call)(
+            let variant = Variant::from_sys((api.godot_variant_call)(
                 &mut self.0,
                 &method.0,
                 arg_refs.as_mut_ptr(),
                 args.len() as i32,
                 &mut err,
-            );
+            ));
 
-            CallError::from_sys(err.error).map(|_| Variant::from_sys(variant))
+            match CallError::from_sys(err.error) {
+                Ok(()) => Ok(variant),
+                Err(_) => Err(err),
+            }
         }
     }
 
     /// Evaluates a variant operator on `self` and `rhs` and returns the result on success.
     ///
+    /// If the engine doesn't provide the core 1.1 API, only comparisons are supported. They
+    /// are evaluated with the `==` and `<` operators of the core 1.0 API, which return `false`
+    /// instead of failing for values that can't be compared.
+    ///
     /// # Errors
     ///
     /// Returns `Err(InvalidOp)` if the result is not valid.
@@ -718,6 +736,10 @@ impl Variant {
     pub fn evaluate(&self, op: VariantOperator, rhs: &Self) -> Result<Variant, InvalidOp> {
         unsafe {
             let api = get_api();
+            if !api.has_core_1_1() {
+                return self.evaluate_comparison(op, rhs).map(Variant::from_bool);
+            }
+
             let mut ret = Variant::new();
             let mut valid = false;
 
@@ -737,6 +759,26 @@ impl Variant {
         }
     }
 
+    /// Evaluates comparison operators with core 1.0 functions, for engines without
+    /// `godot_variant_evaluate`.
+    fn evaluate_comparison(&self, op: VariantOperator, rhs: &Self) -> Result<bool, InvalidOp> {
+        let api = get_api();
+        let equal =
+            |a: &Self, b: &Self| unsafe { (api.godot_variant_operator_equal)(a.sys(), b.sys()) };
+        let less =
+            |a: &Self, b: &Self| unsafe { (api.godot_variant_operator_less)(a.sys(), b.sys()) };
+
+        match op {
+            VariantOperator::Equal => Ok(equal(self, rhs)),
+            VariantOperator::NotEqual => Ok(!equal(self, rhs)),
+            VariantOperator::Less => Ok(less(self, rhs)),
+            VariantOperator::LessEqual => Ok(less(self, rhs) || equal(self, rhs)),
+            VariantOperator::Greater => Ok(less(rhs, self)),
+            VariantOperator::GreaterEqual => Ok(less(rhs, self) || equal(self, rhs)),
+            _ => Err(InvalidOp),
+        }
+    }
+
     /// Get a reference to a `godot-rust` Variant from a raw sys::pointer.
     ///
     /// # Safety
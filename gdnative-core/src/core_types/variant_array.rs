@@ -148,9 +148,18 @@ impl<Access: ThreadAccess> VariantArray<Access> {
     /// Create a copy of the array.
     ///
     /// This creates a new array and is **not** a cheap reference count
-    /// increment.
+    /// increment. If the engine doesn't provide the core 1.1 API, the elements are copied
+    /// one by one.
     #[inline]
     pub fn duplicate(&self) -> VariantArray<Unique> {
+        if !get_api().has_core_1_1() {
+            let copy = VariantArray::new();
+            for value in self.iter() {
+                copy.push(value);
+            }
+            return copy;
+        }
+
         unsafe {
             let sys = (get_api().godot_array_duplicate)(self.sys(), false);
             VariantArray::<Unique>::from_sys(sys)
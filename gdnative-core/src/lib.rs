@@ -35,14 +35,22 @@ extern crate approx;
 // Macros have to be processed before they are used.
 mod macros;
 
+pub mod api_version;
 pub mod core_types;
 mod init;
 
+#[cfg(any(feature = "log", feature = "tracing"))]
+pub mod logging;
+
+#[cfg(feature = "mock_api")]
+pub mod mock_api;
+
 #[cfg(feature = "nativescript")]
 pub mod nativescript;
 
 mod new_ref;
 pub mod object;
+pub mod panic_hook;
 pub mod ref_kind;
 pub mod thread_access;
 
@@ -0,0 +1,260 @@
+//! Backends for the `log` and `tracing` crates that forward messages to the engine.
+//!
+//! Errors and warnings are printed with `godot_print_error` and `godot_print_warning`, so they
+//! show up in the editor's debugger with the module path, file and line of the call site. All
+//! other messages are printed with `godot_print`, prefixed by their level and module path.
+//!
+//! Messages logged while the API isn't bound, i.e. before `gdnative_init` or after
+//! `gdnative_terminate`, are discarded.
+//!
+//! These backends are only available with the `log` and `tracing` features, respectively.
+//! They are usually installed in the `gdnative_init` callback:
+//!
+//! ```ignore
+//! fn init(_options: &gdnative::InitializeInfo) {
+//!     gdnative::logging::init_log(log::LevelFilter::Info)
+//!         .expect("a logger should not be installed yet");
+//! }
+//!
+//! gdnative::godot_gdnative_init!(init);
+//! ```
+//!
+//! Global loggers can only be installed once per process. Reloadable libraries aren't always
+//! unloaded by the OS when the engine reloads them, in which case the backend installed
+//! before is still in place, and the error can be ignored.
+
+use std::fmt;
+
+use crate::core_types::GodotString;
+use crate::private::{to_c_string_escaped, try_get_api};
+
+#[cfg(feature = "log")]
+pub use self::log_backend::{init_log, GodotLogger};
+
+#[cfg(feature = "tracing")]
+pub use self::tracing_backend::{init_tracing, GodotLayer};
+
+/// How a message is printed by the engine.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Severity {
+    Print,
+    Warning,
+    Error,
+}
+
+/// Location of the code that logged a message.
+struct Location<'a> {
+    module_path: &'a str,
+    file: Option<&'a str>,
+    line: Option<u32>,
+}
+
+/// Prints a message using the engine's logging system, if the API is bound.
+fn print(severity: Severity, level: &dyn fmt::Display, location: Location<'_>, message: &str) {
+    let api = match try_get_api() {
+        Some(api) => api,
+        None => return,
+    };
+
+    let print_fn = match severity {
+        Severity::Print => {
+            let msg = format!("[{}] {}: {}", level, location.module_path, message);
+            let msg = GodotString::from_str(msg);
+            unsafe {
+                (api.godot_print)(&msg.to_sys() as *const _);
+            }
+            return;
+        }
+        Severity::Warning => api.godot_print_warning,
+        Severity::Error => api.godot_print_error,
+    };
+
+    let msg = to_c_string_escaped(message);
+    let func = to_c_string_escaped(location.module_path);
+    let file = to_c_string_escaped(location.file.unwrap_or("<unknown>"));
+    let line = location.line.unwrap_or(0);
+
+    unsafe {
+        print_fn(
+            msg.as_ptr() as *const _,
+            func.as_ptr() as *const _,
+            file.as_ptr() as *const _,
+            line as _,
+        );
+    }
+}
+
+#[cfg(feature = "log")]
+mod log_backend {
+    use super::*;
+
+    use ::log::{Level, LevelFilter, Log, Metadata, Record, SetLoggerError};
+
+    /// A `log::Log` implementation that prints records using the engine's logging system.
+    ///
+    /// Records are filtered by the global maximum level of the `log` crate, which is set by
+    /// `init_log`.
+    #[derive(Copy, Clone, Debug, Default)]
+    pub struct GodotLogger;
+
+    impl Log for GodotLogger {
+        #[inline]
+        fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+            metadata.level() <= ::log::max_level()
+        }
+
+        #[inline]
+        fn log(&self, record: &Record<'_>) {
+            if !self.enabled(record.metadata()) {
+                return;
+            }
+
+            let severity = match record.level() {
+                Level::Error => Severity::Error,
+                Level::Warn => Severity::Warning,
+                Level::Info | Level::Debug | Level::Trace => Severity::Print,
+            };
+
+            let location = Location {
+                module_path: record.module_path().unwrap_or_else(|| record.target()),
+                file: record.file(),
+                line: record.line(),
+            };
+
+            print(
+                severity,
+                &record.level(),
+                location,
+                &record.args().to_string(),
+            );
+        }
+
+        #[inline]
+        fn flush(&self) {}
+    }
+
+    /// Installs `GodotLogger` as the global logger, only letting through records up to
+    /// `level`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a global logger has already been installed.
+    #[inline]
+    pub fn init_log(level: LevelFilter) -> Result<(), SetLoggerError> {
+        static LOGGER: GodotLogger = GodotLogger;
+        ::log::set_logger(&LOGGER)?;
+        ::log::set_max_level(level);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "tracing")]
+mod tracing_backend {
+    use super::*;
+
+    use std::fmt::Write;
+
+    use tracing_core::dispatcher::{self, Dispatch, SetGlobalDefaultError};
+    use tracing_core::field::{Field, Visit};
+    use tracing_core::{Event, Level, LevelFilter, Metadata, Subscriber};
+    use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+
+    /// A `tracing_subscriber::Layer` that prints events using the engine's logging system.
+    ///
+    /// Events are formatted as their `message` field, followed by any other fields as
+    /// `name=value` pairs. Spans are ignored.
+    #[derive(Copy, Clone, Debug)]
+    pub struct GodotLayer {
+        level: LevelFilter,
+    }
+
+    impl GodotLayer {
+        /// Creates a `GodotLayer` that only lets through events up to `level`.
+        #[inline]
+        pub fn new(level: LevelFilter) -> Self {
+            GodotLayer { level }
+        }
+    }
+
+    impl Default for GodotLayer {
+        #[inline]
+        fn default() -> Self {
+            GodotLayer::new(LevelFilter::TRACE)
+        }
+    }
+
+    impl<S: Subscriber> Layer<S> for GodotLayer {
+        #[inline]
+        fn enabled(&self, metadata: &Metadata<'_>, _ctx: Context<'_, S>) -> bool {
+            self.level >= *metadata.level()
+        }
+
+        #[inline]
+        fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+            let metadata = event.metadata();
+
+            let mut visitor = MessageVisitor::default();
+            event.record(&mut visitor);
+
+            let severity = match *metadata.level() {
+                Level::ERROR => Severity::Error,
+                Level::WARN => Severity::Warning,
+                _ => Severity::Print,
+            };
+
+            let location = Location {
+                module_path: metadata.module_path().unwrap_or_else(|| metadata.target()),
+                file: metadata.file(),
+                line: metadata.line(),
+            };
+
+            print(severity, metadata.level(), location, &visitor.finish());
+        }
+    }
+
+    /// Collects the fields of an event into a single message.
+    #[derive(Default)]
+    struct MessageVisitor {
+        message: String,
+        fields: String,
+    }
+
+    impl MessageVisitor {
+        fn finish(mut self) -> String {
+            if !self.fields.is_empty() {
+                if !self.message.is_empty() {
+                    self.message.push(' ');
+                }
+                self.message.push_str(&self.fields);
+            }
+            self.message
+        }
+    }
+
+    impl Visit for MessageVisitor {
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            if field.name() == "message" {
+                write!(self.message, "{:?}", value).unwrap();
+            } else {
+                if !self.fields.is_empty() {
+                    self.fields.push(' ');
+                }
+                write!(self.fields, "{}={:?}", field.name(), value).unwrap();
+            }
+        }
+    }
+
+    /// Installs a subscriber consisting only of a `GodotLayer` as the global default,
+    /// letting through events up to `level`.
+    ///
+    /// To combine `GodotLayer` with other layers, construct the subscriber manually instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a global default subscriber has already been installed.
+    #[inline]
+    pub fn init_tracing(level: LevelFilter) -> Result<(), SetGlobalDefaultError> {
+        let subscriber = tracing_subscriber::registry().with(GodotLayer::new(level));
+        dispatcher::set_global_default(Dispatch::new(subscriber))
+    }
+}
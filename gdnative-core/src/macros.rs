@@ -37,6 +37,8 @@ macro_rules! godot_gdnative_init {
                 return;
             }
 
+            $crate::panic_hook::install();
+
             let __result = ::std::panic::catch_unwind(|| {
                 let callback_options = $crate::InitializeInfo::new(options);
                 $callback(&callback_options)
@@ -312,6 +314,16 @@ macro_rules! godot_test {
 
                 ok
             }
+
+            // Runs the test with `cargo test` as well, using the mock API.
+            #[cfg(all(test, feature = "gd_test", feature = "mock_api"))]
+            mod $test_name {
+                #[test]
+                fn mock_api() {
+                    $crate::mock_api::bind();
+                    assert!(super::$test_name());
+                }
+            }
         )*
     }
 }
@@ -0,0 +1,411 @@
+//! Pure-Rust implementation of a subset of the GDNative API, for testing without the engine.
+//!
+//! Most of the core types in this crate are thin wrappers around engine functions, which means
+//! that they can normally only be used inside a Godot process. This module provides Rust
+//! implementations of the functions used by `GodotString`, `Variant`, `VariantArray`,
+//! `Dictionary` and `TypedArray`, as well as basic object lifetime and reference counting for
+//! the `Object` and `Reference` classes, so that code using these types can be exercised with
+//! plain `cargo test`:
+//!
+//! ```ignore
+//! #[test]
+//! fn test_dictionary() {
+//!     gdnative::mock_api::bind();
+//!
+//!     let dict = gdnative::core_types::Dictionary::new();
+//!     dict.insert("foo", 42);
+//!     assert_eq!(Some(42), dict.get("foo").try_to_i64());
+//! }
+//! ```
+//!
+//! The implementations aim to be faithful where it's cheap to do so, but they are not a
+//! replacement for testing inside the engine. Notably:
+//!
+//! - Strings are stored as UTF-8, and operations that depend on Godot's string algorithms
+//!   (e.g. number parsing and formatting) are approximations.
+//! - Only the `Object`, `Reference` and `NativeScript` classes can be constructed, and the only
+//!   methods available on them are the ones used by this crate to manage object lifetimes,
+//!   metadata and scripts. `Engine` is the only singleton, and is a plain `Object`.
+//! - Script classes can be registered, and attached to objects with `attach_script`, but their
+//!   methods, properties and signals can't be used. Like in the engine, script instances stay
+//!   attached when the library is terminated, and classes registered again replace the old
+//!   ones.
+//! - Errors that the engine would report and recover from, like out-of-bounds reads, are
+//!   printed to stderr. Errors that would crash the engine abort the process.
+//!
+//! All other API functions abort the process with the name of the function when called.
+//!
+//! This module is only available with the `mock_api` feature.
+
+use std::cell::UnsafeCell;
+use std::ffi::CStr;
+use std::ptr;
+use std::sync::{Arc, Once};
+
+use crate::private;
+use crate::sys;
+
+mod array;
+mod dictionary;
+mod math;
+mod nativescript;
+mod object;
+mod pool_array;
+mod string;
+mod variant;
+
+pub use self::nativescript::{attach_script, detach_script};
+
+/// Creates an API struct with the mock implementations filled in. Functions without a mock
+/// implementation abort the process when called.
+///
+/// This can be used to replace individual functions before binding the API with `bind_api`.
+#[inline]
+pub fn api() -> sys::GodotApi {
+    let mut api = sys::GodotApi::unimplemented();
+
+    string::register(&mut api);
+    variant::register(&mut api);
+    array::register(&mut api);
+    dictionary::register(&mut api);
+    pool_array::register(&mut api);
+    math::register(&mut api);
+    object::register(&mut api);
+    nativescript::register(&mut api);
+
+    api.godot_print = print;
+    api.godot_print_warning = print_warning;
+    api.godot_print_error = print_error;
+
+    api
+}
+
+/// Binds the API returned by `api`, if no API is bound yet. This is usually called at the
+/// start of every test using the mock API.
+///
+/// Calling this inside the engine has no effect.
+#[inline]
+pub fn bind() {
+    static BIND: Once = Once::new();
+
+    BIND.call_once(|| {
+        if private::try_get_api().is_none() {
+            unsafe { bind_api(api()) }
+        }
+    });
+}
+
+/// Binds an API struct, usually one returned by `api` with some of the functions replaced.
+///
+/// # Safety
+///
+/// No API must be bound, and no other thread may be using the API while this function is
+/// called. An API can be bound again after the library is terminated, e.g. to simulate hot
+/// reloading.
+#[inline]
+pub unsafe fn bind_api(api: sys::GodotApi) {
+    private::bind_mock_api(api);
+}
+
+/// Opaque sys types that hold a pointer to a heap-allocated Rust value, which is null for
+/// zero-initialized values.
+///
+/// # Safety
+///
+/// The sys type must be at least as large as a pointer.
+unsafe trait Boxed: Sized {
+    type Target;
+}
+
+/// Initializes `dest` with a pointer to `value`, without destroying the old value.
+unsafe fn init<S: Boxed>(dest: *mut S, value: S::Target) {
+    ptr::write_unaligned(dest as *mut *mut S::Target, Box::into_raw(Box::new(value)));
+}
+
+/// Creates a sys value pointing to `value`.
+unsafe fn new<S: Boxed + Default>(value: S::Target) -> S {
+    let mut sys = S::default();
+    init(&mut sys, value);
+    sys
+}
+
+/// Returns the value pointed to by `sys`, or `None` if it's zero-initialized.
+unsafe fn get<'a, S: Boxed>(sys: *const S) -> Option<&'a S::Target> {
+    let ptr = ptr::read_unaligned(sys as *const *const S::Target);
+    ptr.as_ref()
+}
+
+/// Returns the value pointed to by `sys`, or `None` if it's zero-initialized.
+unsafe fn get_mut<'a, S: Boxed>(sys: *mut S) -> Option<&'a mut S::Target> {
+    let ptr = ptr::read_unaligned(sys as *const *mut S::Target);
+    ptr.as_mut()
+}
+
+/// Returns the value pointed to by `sys`, aborting if it's zero-initialized.
+unsafe fn expect<'a, S: Boxed>(sys: *const S, type_name: &str) -> &'a S::Target {
+    get(sys).unwrap_or_else(|| fatal(&format!("uninitialized {} used", type_name)))
+}
+
+/// Takes the value out of `sys`, leaving it zero-initialized.
+unsafe fn take<S: Boxed>(sys: *mut S) -> Option<S::Target> {
+    let ptr = ptr::read_unaligned(sys as *const *mut S::Target);
+    ptr::write_unaligned(sys as *mut *mut S::Target, ptr::null_mut());
+    if ptr.is_null() {
+        None
+    } else {
+        Some(*Box::from_raw(ptr))
+    }
+}
+
+/// Reference-counted value with interior mutability and no synchronization, used for the
+/// collection types that are shared by reference in the engine.
+///
+/// Like the engine, the mock API leaves synchronization to the callers, which is why the
+/// accessors are unsafe.
+struct Shared<T>(Arc<UnsafeCell<T>>);
+
+unsafe impl<T: Send> Send for Shared<T> {}
+unsafe impl<T: Send> Sync for Shared<T> {}
+
+impl<T> Shared<T> {
+    fn new(value: T) -> Self {
+        Shared(Arc::new(UnsafeCell::new(value)))
+    }
+
+    /// # Safety
+    ///
+    /// The value must not be mutated while the reference is alive.
+    unsafe fn get(&self) -> &T {
+        &*self.0.get()
+    }
+
+    /// # Safety
+    ///
+    /// No other references to the value may be alive while the reference is alive.
+    #[allow(clippy::mut_from_ref)]
+    unsafe fn get_mut(&self) -> &mut T {
+        &mut *self.0.get()
+    }
+
+    fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl<T> Clone for Shared<T> {
+    fn clone(&self) -> Self {
+        Shared(Arc::clone(&self.0))
+    }
+}
+
+impl<T: Default> Default for Shared<T> {
+    fn default() -> Self {
+        Shared::new(T::default())
+    }
+}
+
+/// Reports an error that the engine would recover from.
+fn error(message: &str) {
+    eprintln!("ERROR: {}\n   At: <mock API>", message);
+}
+
+/// Reports an error that would crash the engine, and aborts. Panicking isn't an option since
+/// the mock functions are called through FFI.
+fn fatal(message: &str) -> ! {
+    eprintln!("FATAL: {}\n   At: <mock API>", message);
+    std::process::abort()
+}
+
+/// Converts an index that may be negative, counting from the end, into a `usize`.
+fn normalize_index(idx: sys::godot_int, len: usize) -> Option<usize> {
+    let idx = if idx < 0 {
+        len as i64 + i64::from(idx)
+    } else {
+        i64::from(idx)
+    };
+
+    if idx >= 0 && (idx as usize) < len {
+        Some(idx as usize)
+    } else {
+        None
+    }
+}
+
+unsafe fn c_str<'a>(s: *const libc::c_char) -> std::borrow::Cow<'a, str> {
+    if s.is_null() {
+        "".into()
+    } else {
+        CStr::from_ptr(s).to_string_lossy()
+    }
+}
+
+unsafe extern "C" fn print(message: *const sys::godot_string) {
+    println!("{}", string::as_str(message));
+}
+
+unsafe extern "C" fn print_warning(
+    description: *const libc::c_char,
+    function: *const libc::c_char,
+    file: *const libc::c_char,
+    line: libc::c_int,
+) {
+    eprintln!(
+        "WARNING: {}: {}\n   At: {}:{}",
+        c_str(function),
+        c_str(description),
+        c_str(file),
+        line,
+    );
+}
+
+unsafe extern "C" fn print_error(
+    description: *const libc::c_char,
+    function: *const libc::c_char,
+    file: *const libc::c_char,
+    line: libc::c_int,
+) {
+    eprintln!(
+        "ERROR: {}: {}\n   At: {}:{}",
+        c_str(function),
+        c_str(description),
+        c_str(file),
+        line,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sys;
+
+    use crate::core_types::{
+        ByteArray, Dictionary, GodotString, StringArray, StringName, ToVariant, Variant,
+        VariantArray, VariantType,
+    };
+    use crate::NewRef;
+
+    #[test]
+    fn strings() {
+        super::bind();
+
+        let s = GodotString::from_str("Hello, wörld!");
+        assert_eq!(13, s.len());
+        assert_eq!("Hello, wörld!", s.to_string());
+        assert_eq!(GodotString::from("hello, wörld!"), s.to_lowercase());
+        assert!(s.begins_with(&GodotString::from("Hello")));
+        assert!(s.ends_with(&GodotString::from("!")));
+        assert_eq!(8, s.find(&GodotString::from("ö")));
+        assert_eq!(-1, s.find(&GodotString::from("nope")));
+        assert_eq!(GodotString::from("wörld"), s.sub_string(7..12));
+        assert_eq!("42", GodotString::from("42").to_utf8().as_str());
+        assert_eq!(42, GodotString::from("42").to_i32());
+        assert!(StringName::from_str("a") < StringName::from_str("b"));
+    }
+
+    #[test]
+    fn variants() {
+        super::bind();
+
+        assert_eq!(VariantType::Nil, Variant::new().get_type());
+        assert_eq!(Some(42), 42.to_variant().try_to_i64());
+        assert_eq!(Some(true), true.to_variant().try_to_bool());
+        assert_eq!(
+            Some(GodotString::from("foo")),
+            "foo".to_variant().try_to_godot_string()
+        );
+        assert_eq!(42.to_variant(), 42.0.to_variant());
+        assert_ne!(42.to_variant(), "42".to_variant());
+        assert_eq!("42", 42.to_variant().to_string());
+        assert_eq!("1.5", 1.5.to_variant().to_string());
+        assert_eq!("True", true.to_variant().to_string());
+        assert_eq!("Null", Variant::new().to_string());
+    }
+
+    #[test]
+    fn arrays_are_shared() {
+        super::bind();
+
+        let array = VariantArray::new();
+        array.push("foo");
+        let array = array.into_shared();
+        let other = array.new_ref();
+        assert_eq!(1, other.len());
+
+        let variant = array.to_variant();
+        let from_variant = unsafe { variant.try_to_array().unwrap().assume_unique() };
+        from_variant.push(42);
+        assert_eq!(2, array.len());
+        assert_eq!("[foo, 42]", variant.to_string());
+
+        let duplicate = array.duplicate();
+        duplicate.clear();
+        assert_eq!(2, array.len());
+    }
+
+    #[test]
+    fn dictionaries_keep_insertion_order() {
+        super::bind();
+
+        let dict = Dictionary::new();
+        dict.insert("b", 1);
+        dict.insert("a", 2);
+        dict.insert("b", 3);
+
+        let entries = dict
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.try_to_i64().unwrap()))
+            .collect::<Vec<_>>();
+        assert_eq!(vec![("b".to_string(), 3), ("a".to_string(), 2)], entries);
+        assert_eq!("{b:3, a:2}", dict.into_shared().to_variant().to_string());
+    }
+
+    #[test]
+    fn typed_arrays_are_copy_on_write() {
+        super::bind();
+
+        let bytes = (0..4).collect::<ByteArray>();
+        let mut copy = bytes.new_ref();
+        copy.set(0, 42);
+        assert_eq!(0, bytes.get(0));
+        assert_eq!(42, copy.get(0));
+
+        let strings = StringArray::from_vec(vec!["foo".into(), "bar".into()]);
+        let variant = strings.to_variant();
+        assert_eq!(VariantType::StringArray, variant.get_type());
+        assert_eq!(strings, variant.try_to_string_array().unwrap());
+    }
+
+    #[test]
+    fn variants_keep_references_alive() {
+        super::bind();
+
+        let api = crate::private::get_api();
+        let methods = crate::private::ReferenceMethodTable::get(api);
+
+        unsafe {
+            let constructor =
+                (api.godot_get_class_constructor)(b"Reference\0".as_ptr() as *const _);
+            let obj = constructor.unwrap()();
+
+            let mut ok = false;
+            let ret = &mut ok as *mut bool as *mut libc::c_void;
+            (api.godot_method_bind_ptrcall)(methods.init_ref, obj, std::ptr::null_mut(), ret);
+            assert!(ok);
+
+            let variant = Variant::from_sys({
+                let mut variant = sys::godot_variant::default();
+                (api.godot_variant_new_object)(&mut variant, obj);
+                variant
+            });
+            assert_eq!(VariantType::Object, variant.get_type());
+
+            let mut last = true;
+            let ret = &mut last as *mut bool as *mut libc::c_void;
+            (api.godot_method_bind_ptrcall)(methods.unreference, obj, std::ptr::null_mut(), ret);
+            assert!(!last);
+            assert!((api.godot_is_instance_valid)(obj));
+
+            drop(variant);
+            assert!(!(api.godot_is_instance_valid)(obj));
+        }
+    }
+}
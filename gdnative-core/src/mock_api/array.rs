@@ -0,0 +1,367 @@
+use std::cmp::Ordering;
+
+use crate::sys;
+
+use super::pool_array::Element;
+use super::variant::{self, OwnedVariant, Value};
+use super::{error, expect, fatal, init, new, normalize_index, take, Boxed, Shared};
+
+pub(super) type SharedArray = Shared<Vec<OwnedVariant>>;
+
+unsafe impl Boxed for sys::godot_array {
+    type Target = SharedArray;
+}
+
+pub(super) fn register(api: &mut sys::GodotApi) {
+    api.godot_array_new = array_new;
+    api.godot_array_new_copy = array_new_copy;
+    api.godot_array_destroy = array_destroy;
+    api.godot_array_size = array_size;
+    api.godot_array_empty = array_empty;
+    api.godot_array_get = array_get;
+    api.godot_array_set = array_set;
+    api.godot_array_operator_index = array_operator_index;
+    api.godot_array_operator_index_const = array_operator_index_const;
+    api.godot_array_front = array_front;
+    api.godot_array_back = array_back;
+    api.godot_array_append = array_push_back;
+    api.godot_array_push_back = array_push_back;
+    api.godot_array_push_front = array_push_front;
+    api.godot_array_pop_back = array_pop_back;
+    api.godot_array_pop_front = array_pop_front;
+    api.godot_array_insert = array_insert;
+    api.godot_array_remove = array_remove;
+    api.godot_array_erase = array_erase;
+    api.godot_array_clear = array_clear;
+    api.godot_array_count = array_count;
+    api.godot_array_has = array_has;
+    api.godot_array_find = array_find;
+    api.godot_array_find_last = array_find_last;
+    api.godot_array_rfind = array_rfind;
+    api.godot_array_invert = array_invert;
+    api.godot_array_resize = array_resize;
+    api.godot_array_duplicate = array_duplicate;
+    api.godot_array_hash = array_hash;
+    api.godot_array_sort = array_sort;
+    api.godot_array_bsearch = array_bsearch;
+}
+
+/// Returns the shared storage of an array, aborting if it's zero-initialized.
+pub(super) unsafe fn shared<'a>(arr: *const sys::godot_array) -> &'a SharedArray {
+    expect(arr, "Array")
+}
+
+unsafe fn elements<'a>(arr: *const sys::godot_array) -> &'a Vec<OwnedVariant> {
+    shared(arr).get()
+}
+
+unsafe fn elements_mut<'a>(arr: *mut sys::godot_array) -> &'a mut Vec<OwnedVariant> {
+    shared(arr).get_mut()
+}
+
+/// Creates a new array from the elements of a pool array.
+pub(super) fn from_elements<E: Element>(elements: &[E]) -> SharedArray {
+    Shared::new(
+        elements
+            .iter()
+            .map(|e| OwnedVariant::new(e.to_value()))
+            .collect(),
+    )
+}
+
+/// Returns a copy of the elements of an array, with nested collections copied as well if `deep`
+/// is `true`.
+pub(super) fn duplicate(elements: &[OwnedVariant], deep: bool) -> SharedArray {
+    Shared::new(
+        elements
+            .iter()
+            .map(|v| {
+                if deep {
+                    OwnedVariant::new(v.value().duplicate(true))
+                } else {
+                    v.clone()
+                }
+            })
+            .collect(),
+    )
+}
+
+pub(super) fn hash(elements: &[OwnedVariant]) -> u32 {
+    elements.iter().fold(5381u32, |hash, v| {
+        hash.wrapping_shl(5)
+            .wrapping_add(hash)
+            .wrapping_add(v.value().hash())
+    })
+}
+
+fn position(elements: &[OwnedVariant], what: &Value) -> Option<usize> {
+    elements.iter().position(|v| v.value().equals(what))
+}
+
+fn cmp(a: &Value, b: &Value) -> Ordering {
+    if a.less(b) {
+        Ordering::Less
+    } else if b.less(a) {
+        Ordering::Greater
+    } else {
+        Ordering::Equal
+    }
+}
+
+unsafe extern "C" fn array_new(dest: *mut sys::godot_array) {
+    init(dest, SharedArray::default());
+}
+
+unsafe extern "C" fn array_new_copy(dest: *mut sys::godot_array, src: *const sys::godot_array) {
+    init(dest, shared(src).clone());
+}
+
+unsafe extern "C" fn array_destroy(arr: *mut sys::godot_array) {
+    take(arr);
+}
+
+unsafe extern "C" fn array_size(arr: *const sys::godot_array) -> sys::godot_int {
+    elements(arr).len() as sys::godot_int
+}
+
+unsafe extern "C" fn array_empty(arr: *const sys::godot_array) -> sys::godot_bool {
+    elements(arr).is_empty()
+}
+
+unsafe extern "C" fn array_get(
+    arr: *const sys::godot_array,
+    idx: sys::godot_int,
+) -> sys::godot_variant {
+    let elements = elements(arr);
+    match normalize_index(idx, elements.len()) {
+        Some(idx) => elements[idx].clone().into_sys(),
+        None => {
+            error(&format!(
+                "index {} out of bounds (size {})",
+                idx,
+                elements.len()
+            ));
+            OwnedVariant::default().into_sys()
+        }
+    }
+}
+
+unsafe extern "C" fn array_set(
+    arr: *mut sys::godot_array,
+    idx: sys::godot_int,
+    value: *const sys::godot_variant,
+) {
+    let value = variant::value(value).clone();
+    let elements = elements_mut(arr);
+    match normalize_index(idx, elements.len()) {
+        Some(idx) => elements[idx] = OwnedVariant::new(value),
+        None => error(&format!(
+            "index {} out of bounds (size {})",
+            idx,
+            elements.len()
+        )),
+    }
+}
+
+unsafe extern "C" fn array_operator_index(
+    arr: *mut sys::godot_array,
+    idx: sys::godot_int,
+) -> *mut sys::godot_variant {
+    let elements = elements_mut(arr);
+    match normalize_index(idx, elements.len()) {
+        Some(idx) => elements[idx].sys_mut(),
+        None => fatal(&format!(
+            "index {} out of bounds (size {})",
+            idx,
+            elements.len()
+        )),
+    }
+}
+
+unsafe extern "C" fn array_operator_index_const(
+    arr: *const sys::godot_array,
+    idx: sys::godot_int,
+) -> *const sys::godot_variant {
+    array_operator_index(arr as *mut _, idx)
+}
+
+unsafe extern "C" fn array_front(arr: *const sys::godot_array) -> sys::godot_variant {
+    array_get(arr, 0)
+}
+
+unsafe extern "C" fn array_back(arr: *const sys::godot_array) -> sys::godot_variant {
+    array_get(arr, -1)
+}
+
+unsafe extern "C" fn array_push_back(arr: *mut sys::godot_array, value: *const sys::godot_variant) {
+    let value = OwnedVariant::new(variant::value(value).clone());
+    elements_mut(arr).push(value);
+}
+
+unsafe extern "C" fn array_push_front(
+    arr: *mut sys::godot_array,
+    value: *const sys::godot_variant,
+) {
+    let value = OwnedVariant::new(variant::value(value).clone());
+    elements_mut(arr).insert(0, value);
+}
+
+unsafe extern "C" fn array_pop_back(arr: *mut sys::godot_array) -> sys::godot_variant {
+    elements_mut(arr).pop().unwrap_or_default().into_sys()
+}
+
+unsafe extern "C" fn array_pop_front(arr: *mut sys::godot_array) -> sys::godot_variant {
+    let elements = elements_mut(arr);
+    if elements.is_empty() {
+        OwnedVariant::default().into_sys()
+    } else {
+        elements.remove(0).into_sys()
+    }
+}
+
+unsafe extern "C" fn array_insert(
+    arr: *mut sys::godot_array,
+    pos: sys::godot_int,
+    value: *const sys::godot_variant,
+) {
+    let value = OwnedVariant::new(variant::value(value).clone());
+    let elements = elements_mut(arr);
+    if pos >= 0 && pos as usize <= elements.len() {
+        elements.insert(pos as usize, value);
+    } else {
+        error(&format!(
+            "index {} out of bounds (size {})",
+            pos,
+            elements.len()
+        ));
+    }
+}
+
+unsafe extern "C" fn array_remove(arr: *mut sys::godot_array, idx: sys::godot_int) {
+    let elements = elements_mut(arr);
+    match normalize_index(idx, elements.len()) {
+        Some(idx) => {
+            elements.remove(idx);
+        }
+        None => error(&format!(
+            "index {} out of bounds (size {})",
+            idx,
+            elements.len()
+        )),
+    }
+}
+
+unsafe extern "C" fn array_erase(arr: *mut sys::godot_array, value: *const sys::godot_variant) {
+    let value = variant::value(value).clone();
+    let elements = elements_mut(arr);
+    if let Some(idx) = position(elements, &value) {
+        elements.remove(idx);
+    }
+}
+
+unsafe extern "C" fn array_clear(arr: *mut sys::godot_array) {
+    // Dropping the elements can run arbitrary code, so they are moved out first.
+    let old = std::mem::take(elements_mut(arr));
+    drop(old);
+}
+
+unsafe extern "C" fn array_count(
+    arr: *const sys::godot_array,
+    value: *const sys::godot_variant,
+) -> sys::godot_int {
+    let value = variant::value(value);
+    elements(arr)
+        .iter()
+        .filter(|v| v.value().equals(value))
+        .count() as sys::godot_int
+}
+
+unsafe extern "C" fn array_has(
+    arr: *const sys::godot_array,
+    value: *const sys::godot_variant,
+) -> sys::godot_bool {
+    position(elements(arr), variant::value(value)).is_some()
+}
+
+unsafe extern "C" fn array_find(
+    arr: *const sys::godot_array,
+    what: *const sys::godot_variant,
+    from: sys::godot_int,
+) -> sys::godot_int {
+    let elements = elements(arr);
+    let from = (from.max(0) as usize).min(elements.len());
+    position(&elements[from..], variant::value(what))
+        .map_or(-1, |idx| (from + idx) as sys::godot_int)
+}
+
+unsafe extern "C" fn array_find_last(
+    arr: *const sys::godot_array,
+    what: *const sys::godot_variant,
+) -> sys::godot_int {
+    array_rfind(arr, what, -1)
+}
+
+unsafe extern "C" fn array_rfind(
+    arr: *const sys::godot_array,
+    what: *const sys::godot_variant,
+    from: sys::godot_int,
+) -> sys::godot_int {
+    let elements = elements(arr);
+    let what = variant::value(what);
+    let end = match normalize_index(from, elements.len()) {
+        Some(from) => from + 1,
+        None => return -1,
+    };
+    elements[..end]
+        .iter()
+        .rposition(|v| v.value().equals(what))
+        .map_or(-1, |idx| idx as sys::godot_int)
+}
+
+unsafe extern "C" fn array_invert(arr: *mut sys::godot_array) {
+    elements_mut(arr).reverse();
+}
+
+unsafe extern "C" fn array_resize(arr: *mut sys::godot_array, size: sys::godot_int) {
+    let old = {
+        let elements = elements_mut(arr);
+        let size = size.max(0) as usize;
+        if size > elements.len() {
+            elements.resize_with(size, OwnedVariant::default);
+            Vec::new()
+        } else {
+            elements.split_off(size)
+        }
+    };
+    drop(old);
+}
+
+unsafe extern "C" fn array_duplicate(
+    arr: *const sys::godot_array,
+    deep: sys::godot_bool,
+) -> sys::godot_array {
+    new(duplicate(elements(arr), deep))
+}
+
+unsafe extern "C" fn array_hash(arr: *const sys::godot_array) -> sys::godot_int {
+    hash(elements(arr)) as sys::godot_int
+}
+
+unsafe extern "C" fn array_sort(arr: *mut sys::godot_array) {
+    elements_mut(arr).sort_by(|a, b| cmp(a.value(), b.value()));
+}
+
+unsafe extern "C" fn array_bsearch(
+    arr: *mut sys::godot_array,
+    value: *const sys::godot_variant,
+    before: sys::godot_bool,
+) -> sys::godot_int {
+    let value = variant::value(value);
+    let elements = elements(arr);
+    let idx = if before {
+        elements.partition_point(|v| v.value().less(value))
+    } else {
+        elements.partition_point(|v| !value.less(v.value()))
+    };
+    idx as sys::godot_int
+}
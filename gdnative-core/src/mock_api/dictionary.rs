@@ -0,0 +1,314 @@
+use crate::sys;
+
+use super::array::{self, SharedArray};
+use super::variant::{self, OwnedVariant, Value};
+use super::{expect, fatal, init, new, take, Boxed, Shared};
+
+/// Entries of a dictionary, in insertion order like in the engine. Lookups are linear, which is
+/// fine for the sizes used in tests.
+pub(super) type Entries = Vec<(OwnedVariant, OwnedVariant)>;
+
+pub(super) type SharedDictionary = Shared<Entries>;
+
+unsafe impl Boxed for sys::godot_dictionary {
+    type Target = SharedDictionary;
+}
+
+pub(super) fn register(api: &mut sys::GodotApi) {
+    api.godot_dictionary_new = dictionary_new;
+    api.godot_dictionary_new_copy = dictionary_new_copy;
+    api.godot_dictionary_destroy = dictionary_destroy;
+    api.godot_dictionary_size = dictionary_size;
+    api.godot_dictionary_empty = dictionary_empty;
+    api.godot_dictionary_clear = dictionary_clear;
+    api.godot_dictionary_has = dictionary_has;
+    api.godot_dictionary_has_all = dictionary_has_all;
+    api.godot_dictionary_erase = dictionary_erase;
+    api.godot_dictionary_erase_with_return = dictionary_erase_with_return;
+    api.godot_dictionary_get = dictionary_get;
+    api.godot_dictionary_get_with_default = dictionary_get_with_default;
+    api.godot_dictionary_set = dictionary_set;
+    api.godot_dictionary_operator_index = dictionary_operator_index;
+    api.godot_dictionary_operator_index_const = dictionary_operator_index_const;
+    api.godot_dictionary_next = dictionary_next;
+    api.godot_dictionary_keys = dictionary_keys;
+    api.godot_dictionary_values = dictionary_values;
+    api.godot_dictionary_hash = dictionary_hash;
+    api.godot_dictionary_to_json = dictionary_to_json;
+    api.godot_dictionary_operator_equal = dictionary_operator_equal;
+    api.godot_dictionary_duplicate = dictionary_duplicate;
+}
+
+/// Returns the shared storage of a dictionary, aborting if it's zero-initialized.
+pub(super) unsafe fn shared<'a>(dict: *const sys::godot_dictionary) -> &'a SharedDictionary {
+    expect(dict, "Dictionary")
+}
+
+unsafe fn entries<'a>(dict: *const sys::godot_dictionary) -> &'a Entries {
+    shared(dict).get()
+}
+
+unsafe fn entries_mut<'a>(dict: *mut sys::godot_dictionary) -> &'a mut Entries {
+    shared(dict).get_mut()
+}
+
+fn position(entries: &Entries, key: &Value) -> Option<usize> {
+    entries.iter().position(|(k, _)| k.value().hash_equals(key))
+}
+
+/// Returns a copy of the entries of a dictionary, with nested collections copied as well if
+/// `deep` is `true`.
+pub(super) fn duplicate(entries: &Entries, deep: bool) -> SharedDictionary {
+    Shared::new(
+        entries
+            .iter()
+            .map(|(k, v)| {
+                if deep {
+                    (
+                        OwnedVariant::new(k.value().duplicate(true)),
+                        OwnedVariant::new(v.value().duplicate(true)),
+                    )
+                } else {
+                    (k.clone(), v.clone())
+                }
+            })
+            .collect(),
+    )
+}
+
+pub(super) fn hash(entries: &Entries) -> u32 {
+    entries.iter().fold(5381u32, |hash, (k, v)| {
+        let hash = hash
+            .wrapping_shl(5)
+            .wrapping_add(hash)
+            .wrapping_add(k.value().hash());
+        hash.wrapping_shl(5)
+            .wrapping_add(hash)
+            .wrapping_add(v.value().hash())
+    })
+}
+
+/// Inserts `key` with a `Nil` value if it isn't present yet, and returns the index of the entry.
+fn entry(entries: &mut Entries, key: Value) -> usize {
+    position(entries, &key).unwrap_or_else(|| {
+        entries.push((OwnedVariant::new(key), OwnedVariant::default()));
+        entries.len() - 1
+    })
+}
+
+unsafe extern "C" fn dictionary_new(dest: *mut sys::godot_dictionary) {
+    init(dest, SharedDictionary::default());
+}
+
+unsafe extern "C" fn dictionary_new_copy(
+    dest: *mut sys::godot_dictionary,
+    src: *const sys::godot_dictionary,
+) {
+    init(dest, shared(src).clone());
+}
+
+unsafe extern "C" fn dictionary_destroy(dict: *mut sys::godot_dictionary) {
+    take(dict);
+}
+
+unsafe extern "C" fn dictionary_size(dict: *const sys::godot_dictionary) -> sys::godot_int {
+    entries(dict).len() as sys::godot_int
+}
+
+unsafe extern "C" fn dictionary_empty(dict: *const sys::godot_dictionary) -> sys::godot_bool {
+    entries(dict).is_empty()
+}
+
+unsafe extern "C" fn dictionary_clear(dict: *mut sys::godot_dictionary) {
+    // Dropping the entries can run arbitrary code, so they are moved out first.
+    let old = std::mem::take(entries_mut(dict));
+    drop(old);
+}
+
+unsafe extern "C" fn dictionary_has(
+    dict: *const sys::godot_dictionary,
+    key: *const sys::godot_variant,
+) -> sys::godot_bool {
+    position(entries(dict), variant::value(key)).is_some()
+}
+
+unsafe extern "C" fn dictionary_has_all(
+    dict: *const sys::godot_dictionary,
+    keys: *const sys::godot_array,
+) -> sys::godot_bool {
+    let entries = entries(dict);
+    array::shared(keys)
+        .get()
+        .iter()
+        .all(|key| position(entries, key.value()).is_some())
+}
+
+unsafe extern "C" fn dictionary_erase(
+    dict: *mut sys::godot_dictionary,
+    key: *const sys::godot_variant,
+) {
+    dictionary_erase_with_return(dict, key);
+}
+
+unsafe extern "C" fn dictionary_erase_with_return(
+    dict: *mut sys::godot_dictionary,
+    key: *const sys::godot_variant,
+) -> bool {
+    let key = variant::value(key).clone();
+    let entries = entries_mut(dict);
+    let old = position(entries, &key).map(|idx| entries.remove(idx));
+    old.is_some()
+}
+
+unsafe extern "C" fn dictionary_get(
+    dict: *const sys::godot_dictionary,
+    key: *const sys::godot_variant,
+) -> sys::godot_variant {
+    dictionary_get_with_default(dict, key, std::ptr::null())
+}
+
+unsafe extern "C" fn dictionary_get_with_default(
+    dict: *const sys::godot_dictionary,
+    key: *const sys::godot_variant,
+    default: *const sys::godot_variant,
+) -> sys::godot_variant {
+    let entries = entries(dict);
+    let value = match position(entries, variant::value(key)) {
+        Some(idx) => entries[idx].1.value(),
+        None => variant::value(default),
+    };
+    OwnedVariant::new(value.clone()).into_sys()
+}
+
+unsafe extern "C" fn dictionary_set(
+    dict: *mut sys::godot_dictionary,
+    key: *const sys::godot_variant,
+    value: *const sys::godot_variant,
+) {
+    let key = variant::value(key).clone();
+    let value = OwnedVariant::new(variant::value(value).clone());
+    let entries = entries_mut(dict);
+    let idx = entry(entries, key);
+    let old = std::mem::replace(&mut entries[idx].1, value);
+    drop(old);
+}
+
+unsafe extern "C" fn dictionary_operator_index(
+    dict: *mut sys::godot_dictionary,
+    key: *const sys::godot_variant,
+) -> *mut sys::godot_variant {
+    let key = variant::value(key).clone();
+    let entries = entries_mut(dict);
+    let idx = entry(entries, key);
+    entries[idx].1.sys_mut()
+}
+
+unsafe extern "C" fn dictionary_operator_index_const(
+    dict: *const sys::godot_dictionary,
+    key: *const sys::godot_variant,
+) -> *const sys::godot_variant {
+    let key = variant::value(key);
+    let entries = entries_mut(dict as *mut _);
+    match position(entries, key) {
+        Some(idx) => entries[idx].1.sys_mut(),
+        None => fatal(&format!("key {} not found in dictionary", key)),
+    }
+}
+
+unsafe extern "C" fn dictionary_next(
+    dict: *const sys::godot_dictionary,
+    key: *const sys::godot_variant,
+) -> *mut sys::godot_variant {
+    let entries = entries_mut(dict as *mut _);
+    let next = if key.is_null() {
+        0
+    } else {
+        match position(entries, variant::value(key)) {
+            Some(idx) => idx + 1,
+            None => return std::ptr::null_mut(),
+        }
+    };
+
+    entries
+        .get_mut(next)
+        .map_or(std::ptr::null_mut(), |(k, _)| k.sys_mut())
+}
+
+unsafe extern "C" fn dictionary_keys(dict: *const sys::godot_dictionary) -> sys::godot_array {
+    let keys = entries(dict).iter().map(|(k, _)| k.clone()).collect();
+    new::<sys::godot_array>(SharedArray::new(keys))
+}
+
+unsafe extern "C" fn dictionary_values(dict: *const sys::godot_dictionary) -> sys::godot_array {
+    let values = entries(dict).iter().map(|(_, v)| v.clone()).collect();
+    new::<sys::godot_array>(SharedArray::new(values))
+}
+
+unsafe extern "C" fn dictionary_hash(dict: *const sys::godot_dictionary) -> sys::godot_int {
+    hash(entries(dict)) as sys::godot_int
+}
+
+unsafe extern "C" fn dictionary_to_json(dict: *const sys::godot_dictionary) -> sys::godot_string {
+    fn write_json(out: &mut String, value: &Value) {
+        match value {
+            Value::Nil => out.push_str("null"),
+            Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+            Value::Int(_) | Value::Real(_) => out.push_str(&value.to_string()),
+            Value::Array(array) => {
+                out.push('[');
+                for (idx, v) in unsafe { array.get() }.iter().enumerate() {
+                    if idx > 0 {
+                        out.push(',');
+                    }
+                    write_json(out, v.value());
+                }
+                out.push(']');
+            }
+            Value::Dictionary(dict) => {
+                out.push('{');
+                for (idx, (k, v)) in unsafe { dict.get() }.iter().enumerate() {
+                    if idx > 0 {
+                        out.push(',');
+                    }
+                    write_json(out, &Value::String(k.value().to_string()));
+                    out.push(':');
+                    write_json(out, v.value());
+                }
+                out.push('}');
+            }
+            other => {
+                out.push('"');
+                for c in other.to_string().chars() {
+                    match c {
+                        '"' => out.push_str("\\\""),
+                        '\\' => out.push_str("\\\\"),
+                        '\n' => out.push_str("\\n"),
+                        '\r' => out.push_str("\\r"),
+                        '\t' => out.push_str("\\t"),
+                        c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+                        c => out.push(c),
+                    }
+                }
+                out.push('"');
+            }
+        }
+    }
+
+    let mut json = String::new();
+    write_json(&mut json, &Value::Dictionary(shared(dict).clone()));
+    super::string::new_string(json)
+}
+
+unsafe extern "C" fn dictionary_operator_equal(
+    dict: *const sys::godot_dictionary,
+    other: *const sys::godot_dictionary,
+) -> sys::godot_bool {
+    shared(dict).ptr_eq(shared(other))
+}
+
+unsafe extern "C" fn dictionary_duplicate(
+    dict: *const sys::godot_dictionary,
+    deep: sys::godot_bool,
+) -> sys::godot_dictionary {
+    new(duplicate(entries(dict), deep))
+}
@@ -0,0 +1,58 @@
+use crate::sys;
+
+use super::fatal;
+
+pub(super) fn register(api: &mut sys::GodotApi) {
+    api.godot_vector2_get_x = vector2_get_x;
+    api.godot_vector2_get_y = vector2_get_y;
+    api.godot_vector2_set_x = vector2_set_x;
+    api.godot_vector2_set_y = vector2_set_y;
+    api.godot_vector3_get_axis = vector3_get_axis;
+    api.godot_vector3_set_axis = vector3_set_axis;
+}
+
+/// Returns a pointer to a component of a plain math type, which are all stored as arrays of
+/// `godot_real`.
+unsafe fn component<T>(v: *const T, idx: usize) -> *mut sys::godot_real {
+    (v as *mut sys::godot_real).add(idx)
+}
+
+fn axis_index(axis: sys::godot_vector3_axis) -> usize {
+    match axis {
+        sys::godot_vector3_axis_GODOT_VECTOR3_AXIS_X => 0,
+        sys::godot_vector3_axis_GODOT_VECTOR3_AXIS_Y => 1,
+        sys::godot_vector3_axis_GODOT_VECTOR3_AXIS_Z => 2,
+        _ => fatal(&format!("invalid axis {}", axis)),
+    }
+}
+
+unsafe extern "C" fn vector2_get_x(v: *const sys::godot_vector2) -> sys::godot_real {
+    component(v, 0).read_unaligned()
+}
+
+unsafe extern "C" fn vector2_get_y(v: *const sys::godot_vector2) -> sys::godot_real {
+    component(v, 1).read_unaligned()
+}
+
+unsafe extern "C" fn vector2_set_x(v: *mut sys::godot_vector2, x: sys::godot_real) {
+    component(v, 0).write_unaligned(x);
+}
+
+unsafe extern "C" fn vector2_set_y(v: *mut sys::godot_vector2, y: sys::godot_real) {
+    component(v, 1).write_unaligned(y);
+}
+
+unsafe extern "C" fn vector3_get_axis(
+    v: *const sys::godot_vector3,
+    axis: sys::godot_vector3_axis,
+) -> sys::godot_real {
+    component(v, axis_index(axis)).read_unaligned()
+}
+
+unsafe extern "C" fn vector3_set_axis(
+    v: *mut sys::godot_vector3,
+    axis: sys::godot_vector3_axis,
+    val: sys::godot_real,
+) {
+    component(v, axis_index(axis)).write_unaligned(val);
+}
@@ -0,0 +1,246 @@
+use parking_lot::Mutex;
+
+use crate::sys;
+
+use super::object::{self, ObjectRef, ScriptInstance};
+use super::{c_str, error};
+
+/// A script class registered by the library.
+struct MockClass {
+    name: String,
+    create: sys::godot_instance_create_func,
+    destroy: sys::godot_instance_destroy_func,
+    type_tag: usize,
+}
+
+// Safety: The method data is only passed back to the callbacks it was registered with.
+unsafe impl Send for MockClass {}
+
+impl MockClass {
+    unsafe fn free(self) {
+        if let Some(free_func) = self.create.free_func {
+            free_func(self.create.method_data);
+        }
+        if let Some(free_func) = self.destroy.free_func {
+            free_func(self.destroy.method_data);
+        }
+    }
+}
+
+/// Script classes by name. Classes registered again, e.g. after the library is reloaded, replace
+/// the old ones.
+static CLASSES: Mutex<Vec<MockClass>> = parking_lot::const_mutex(Vec::new());
+
+pub(super) fn register(api: &mut sys::GodotApi) {
+    api.godot_nativescript_register_class = register_class;
+    api.godot_nativescript_register_tool_class = register_class;
+    api.godot_nativescript_register_method = register_method;
+    api.godot_nativescript_register_property = register_property;
+    api.godot_nativescript_register_signal = register_signal;
+    api.godot_nativescript_set_type_tag = set_type_tag;
+    api.godot_nativescript_get_type_tag = get_type_tag;
+    api.godot_nativescript_get_userdata = get_userdata;
+}
+
+/// Attaches a new `NativeScript` for the script class `class_name` to `owner`, like setting the
+/// script of an object in the engine. The previously attached instance, if any, is destroyed
+/// first.
+///
+/// Returns `false` if no class named `class_name` is registered, or its constructor failed.
+///
+/// # Safety
+///
+/// `owner` must point to a live object created by the mock API, of a class compatible with the
+/// base class of the script.
+#[inline]
+pub unsafe fn attach_script(owner: *mut sys::godot_object, class_name: &str) -> bool {
+    let script = object::new_native_script(class_name);
+    set_script(owner, script.as_ptr());
+    object::script_instance(owner).is_some()
+}
+
+/// Removes the script of `owner`, destroying its script instance, if any.
+///
+/// # Safety
+///
+/// `owner` must point to a live object created by the mock API.
+#[inline]
+pub unsafe fn detach_script(owner: *mut sys::godot_object) {
+    set_script(owner, std::ptr::null_mut());
+}
+
+/// Implementation of `Object::set_script`. Like in the engine, script instances are only
+/// created and destroyed if the script changes.
+pub(super) unsafe fn set_script(owner: *mut sys::godot_object, script: *mut sys::godot_object) {
+    if object::script(owner) == script {
+        return;
+    }
+
+    destroy_instance(owner);
+
+    let script = if script.is_null() {
+        None
+    } else {
+        Some(ObjectRef::new(script))
+    };
+    let class_name = script
+        .as_ref()
+        .and_then(|script| object::script_class_name(script.as_ptr()));
+    drop(object::replace_script(owner, script));
+
+    if let Some(class_name) = class_name {
+        create_instance(owner, &class_name);
+    }
+}
+
+unsafe fn create_instance(owner: *mut sys::godot_object, class_name: &str) {
+    let create = CLASSES
+        .lock()
+        .iter()
+        .find(|class| class.name == class_name)
+        .map(|class| class.create);
+
+    // The lock is released before calling into the library.
+    let create = match create {
+        Some(create) => create,
+        None => {
+            error(&format!("no script class named {:?}", class_name));
+            return;
+        }
+    };
+
+    let user_data = match create.create_func {
+        Some(create_func) => create_func(owner, create.method_data),
+        None => std::ptr::null_mut(),
+    };
+
+    if !user_data.is_null() {
+        object::replace_script_instance(
+            owner,
+            Some(ScriptInstance {
+                class_name: class_name.to_owned(),
+                user_data: user_data as usize,
+            }),
+        );
+    }
+}
+
+unsafe fn destroy_instance(owner: *mut sys::godot_object) {
+    let script = match object::replace_script_instance(owner, None) {
+        Some(script) => script,
+        None => return,
+    };
+
+    let destroy = CLASSES
+        .lock()
+        .iter()
+        .find(|class| class.name == script.class_name)
+        .map(|class| class.destroy);
+
+    if let Some(sys::godot_instance_destroy_func {
+        destroy_func: Some(destroy_func),
+        method_data,
+        ..
+    }) = destroy
+    {
+        destroy_func(owner, method_data, script.user_data as *mut _);
+    }
+}
+
+unsafe extern "C" fn register_class(
+    _gdnative_handle: *mut libc::c_void,
+    name: *const libc::c_char,
+    _base: *const libc::c_char,
+    create_func: sys::godot_instance_create_func,
+    destroy_func: sys::godot_instance_destroy_func,
+) {
+    let class = MockClass {
+        name: c_str(name).into_owned(),
+        create: create_func,
+        destroy: destroy_func,
+        type_tag: 0,
+    };
+
+    let old = {
+        let mut classes = CLASSES.lock();
+        match classes.iter().position(|old| old.name == class.name) {
+            Some(idx) => Some(std::mem::replace(&mut classes[idx], class)),
+            None => {
+                classes.push(class);
+                None
+            }
+        }
+    };
+
+    if let Some(old) = old {
+        old.free();
+    }
+}
+
+// Methods, properties and signals can't be used through the mock API, so their data is freed
+// right away.
+
+unsafe extern "C" fn register_method(
+    _gdnative_handle: *mut libc::c_void,
+    _name: *const libc::c_char,
+    _function_name: *const libc::c_char,
+    _attr: sys::godot_method_attributes,
+    method: sys::godot_instance_method,
+) {
+    if let Some(free_func) = method.free_func {
+        free_func(method.method_data);
+    }
+}
+
+unsafe extern "C" fn register_property(
+    _gdnative_handle: *mut libc::c_void,
+    _name: *const libc::c_char,
+    _path: *const libc::c_char,
+    _attr: *mut sys::godot_property_attributes,
+    set_func: sys::godot_property_set_func,
+    get_func: sys::godot_property_get_func,
+) {
+    if let Some(free_func) = set_func.free_func {
+        free_func(set_func.method_data);
+    }
+    if let Some(free_func) = get_func.free_func {
+        free_func(get_func.method_data);
+    }
+}
+
+unsafe extern "C" fn register_signal(
+    _gdnative_handle: *mut libc::c_void,
+    _name: *const libc::c_char,
+    _signal: *const sys::godot_signal,
+) {
+}
+
+unsafe extern "C" fn set_type_tag(
+    _gdnative_handle: *mut libc::c_void,
+    name: *const libc::c_char,
+    type_tag: *const libc::c_void,
+) {
+    let name = c_str(name);
+    match CLASSES.lock().iter_mut().find(|class| class.name == name) {
+        Some(class) => class.type_tag = type_tag as usize,
+        None => error(&format!("no script class named {:?}", name)),
+    }
+}
+
+unsafe extern "C" fn get_type_tag(obj: *const sys::godot_object) -> *const libc::c_void {
+    object::script_instance(obj)
+        .and_then(|script| {
+            CLASSES
+                .lock()
+                .iter()
+                .find(|class| class.name == script.class_name)
+                .map(|class| class.type_tag as *const libc::c_void)
+        })
+        .unwrap_or(std::ptr::null())
+}
+
+unsafe extern "C" fn get_userdata(obj: *mut sys::godot_object) -> *mut libc::c_void {
+    object::script_instance(obj).map_or(std::ptr::null_mut(), |script| {
+        script.user_data as *mut libc::c_void
+    })
+}
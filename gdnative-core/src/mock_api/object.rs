@@ -0,0 +1,530 @@
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicI32, AtomicU32, Ordering};
+
+use parking_lot::Mutex;
+
+use crate::sys;
+
+use super::variant::{self, OwnedVariant, Value};
+use super::{c_str, error, fatal, init, nativescript, string, take};
+
+/// Classes that can be constructed with the mock API.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Class {
+    Object,
+    Reference,
+    NativeScript,
+}
+
+impl Class {
+    fn name(self) -> &'static str {
+        match self {
+            Class::Object => "Object",
+            Class::Reference => "Reference",
+            Class::NativeScript => "NativeScript",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "Object" => Some(Class::Object),
+            "Reference" => Some(Class::Reference),
+            "NativeScript" => Some(Class::NativeScript),
+            _ => None,
+        }
+    }
+
+    fn inherits(self, base: Class) -> bool {
+        self == base
+            || base == Class::Object
+            || (base == Class::Reference && self == Class::NativeScript)
+    }
+}
+
+struct MockObject {
+    class: Class,
+    id: sys::godot_int,
+    refcount: AtomicU32,
+    refcount_init: AtomicBool,
+    meta: Mutex<Vec<(String, OwnedVariant)>>,
+    script: Mutex<Option<ObjectRef>>,
+    script_instance: Mutex<Option<ScriptInstance>>,
+    /// Class name of `NativeScript` objects.
+    script_class_name: Mutex<Option<String>>,
+}
+
+/// Script instance attached to an object with `set_script`.
+#[derive(Clone)]
+pub(super) struct ScriptInstance {
+    pub(super) class_name: String,
+    pub(super) user_data: usize,
+}
+
+impl MockObject {
+    /// Increments the reference count unless it's zero, like `Reference::reference`.
+    fn reference(&self) -> bool {
+        self.refcount
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |count| {
+                if count == 0 {
+                    None
+                } else {
+                    Some(count + 1)
+                }
+            })
+            .is_ok()
+    }
+
+    /// Decrements the reference count, returning `true` if it reached zero, like
+    /// `Reference::unreference`.
+    fn unreference(&self) -> bool {
+        let old = self
+            .refcount
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |count| {
+                count.checked_sub(1)
+            });
+        old == Ok(1)
+    }
+
+    /// Like `Reference::init_ref`.
+    fn init_ref(&self) -> bool {
+        if self.reference() {
+            if self.refcount_init.swap(false, Ordering::AcqRel) {
+                self.unreference();
+            }
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Objects that are currently alive, by instance ID.
+static LIVE_OBJECTS: Mutex<Vec<(sys::godot_int, usize)>> = parking_lot::const_mutex(Vec::new());
+
+static NEXT_ID: AtomicI32 = AtomicI32::new(1);
+
+/// Returns the object behind `obj` if it's alive. Objects are never accessed mutably, so
+/// shared references are fine.
+fn live_object<'a>(obj: *const sys::godot_object) -> Option<&'a MockObject> {
+    let live = LIVE_OBJECTS.lock();
+    if live.iter().any(|&(_, ptr)| ptr == obj as usize) {
+        unsafe { (obj as *const MockObject).as_ref() }
+    } else {
+        None
+    }
+}
+
+fn expect_live_object<'a>(obj: *const sys::godot_object) -> &'a MockObject {
+    live_object(obj).unwrap_or_else(|| fatal(&format!("object {:p} is not alive", obj)))
+}
+
+#[derive(Copy, Clone, Debug)]
+enum Method {
+    GetClass,
+    IsClass,
+    GetInstanceId,
+    HasMeta,
+    GetMeta,
+    SetMeta,
+    RemoveMeta,
+    GetScript,
+    SetScript,
+    Reference,
+    Unreference,
+    InitRef,
+    SetClassName,
+}
+
+struct MethodBind {
+    class: Class,
+    name: &'static str,
+    method: Method,
+}
+
+/// Methods available through `godot_method_bind_get_method`. Pointers into this table are
+/// used as method binds.
+static METHODS: [MethodBind; 13] = [
+    MethodBind {
+        class: Class::Object,
+        name: "get_class",
+        method: Method::GetClass,
+    },
+    MethodBind {
+        class: Class::Object,
+        name: "is_class",
+        method: Method::IsClass,
+    },
+    MethodBind {
+        class: Class::Object,
+        name: "get_instance_id",
+        method: Method::GetInstanceId,
+    },
+    MethodBind {
+        class: Class::Object,
+        name: "has_meta",
+        method: Method::HasMeta,
+    },
+    MethodBind {
+        class: Class::Object,
+        name: "get_meta",
+        method: Method::GetMeta,
+    },
+    MethodBind {
+        class: Class::Object,
+        name: "set_meta",
+        method: Method::SetMeta,
+    },
+    MethodBind {
+        class: Class::Object,
+        name: "remove_meta",
+        method: Method::RemoveMeta,
+    },
+    MethodBind {
+        class: Class::Object,
+        name: "get_script",
+        method: Method::GetScript,
+    },
+    MethodBind {
+        class: Class::Object,
+        name: "set_script",
+        method: Method::SetScript,
+    },
+    MethodBind {
+        class: Class::Reference,
+        name: "reference",
+        method: Method::Reference,
+    },
+    MethodBind {
+        class: Class::Reference,
+        name: "unreference",
+        method: Method::Unreference,
+    },
+    MethodBind {
+        class: Class::Reference,
+        name: "init_ref",
+        method: Method::InitRef,
+    },
+    MethodBind {
+        class: Class::NativeScript,
+        name: "set_class_name",
+        method: Method::SetClassName,
+    },
+];
+
+fn find_method(class: Class, name: &str) -> Option<&'static MethodBind> {
+    METHODS
+        .iter()
+        .find(|bind| bind.name == name && class.inherits(bind.class))
+}
+
+/// Returns whether the object behind `obj` has a method called `name`.
+pub(super) fn has_method(obj: *const sys::godot_object, name: &str) -> bool {
+    live_object(obj)
+        .and_then(|obj| find_method(obj.class, name))
+        .is_some()
+}
+
+/// Returns the script instance attached to the object behind `obj`, if any.
+pub(super) fn script_instance(obj: *const sys::godot_object) -> Option<ScriptInstance> {
+    live_object(obj).and_then(|obj| obj.script_instance.lock().clone())
+}
+
+/// Replaces the script instance attached to the object behind `obj`, returning the old one.
+/// The object must be alive.
+pub(super) fn replace_script_instance(
+    obj: *const sys::godot_object,
+    script: Option<ScriptInstance>,
+) -> Option<ScriptInstance> {
+    std::mem::replace(&mut *expect_live_object(obj).script_instance.lock(), script)
+}
+
+/// Returns the script of the object behind `obj`, or null. The object must be alive.
+pub(super) fn script(obj: *const sys::godot_object) -> *mut sys::godot_object {
+    expect_live_object(obj)
+        .script
+        .lock()
+        .as_ref()
+        .map_or(std::ptr::null_mut(), ObjectRef::as_ptr)
+}
+
+/// Replaces the script of the object behind `obj`, returning the old one. The object must be
+/// alive.
+pub(super) fn replace_script(
+    obj: *const sys::godot_object,
+    script: Option<ObjectRef>,
+) -> Option<ObjectRef> {
+    std::mem::replace(&mut *expect_live_object(obj).script.lock(), script)
+}
+
+/// Returns the class name of the object behind `obj`, if it's a `NativeScript`.
+pub(super) fn script_class_name(obj: *const sys::godot_object) -> Option<String> {
+    live_object(obj).and_then(|obj| obj.script_class_name.lock().clone())
+}
+
+/// Creates a `NativeScript` for the script class `class_name`.
+pub(super) fn new_native_script(class_name: &str) -> ObjectRef {
+    let script = construct(Class::NativeScript);
+    let obj = expect_live_object(script);
+    *obj.script_class_name.lock() = Some(class_name.to_owned());
+
+    // The reference held by the returned `ObjectRef`.
+    obj.init_ref();
+    ObjectRef(script)
+}
+
+/// Destroys an object. The object must be alive.
+unsafe fn destroy(obj: *mut sys::godot_object) {
+    {
+        let mut live = LIVE_OBJECTS.lock();
+        match live.iter().position(|&(_, ptr)| ptr == obj as usize) {
+            Some(idx) => {
+                live.swap_remove(idx);
+            }
+            None => fatal(&format!("object {:p} is freed twice", obj)),
+        }
+    }
+
+    drop(Box::from_raw(obj as *mut MockObject));
+}
+
+/// Object reference held by a variant. Like in the engine, variants holding `Reference`s keep
+/// them alive.
+pub(super) struct ObjectRef(*mut sys::godot_object);
+
+impl ObjectRef {
+    pub(super) fn new(obj: *mut sys::godot_object) -> Self {
+        if let Some(obj) = live_object(obj) {
+            if obj.class.inherits(Class::Reference) {
+                obj.reference();
+            }
+        }
+
+        ObjectRef(obj)
+    }
+
+    pub(super) fn as_ptr(&self) -> *mut sys::godot_object {
+        self.0
+    }
+}
+
+impl Clone for ObjectRef {
+    fn clone(&self) -> Self {
+        ObjectRef::new(self.0)
+    }
+}
+
+impl Drop for ObjectRef {
+    fn drop(&mut self) {
+        if let Some(obj) = live_object(self.0) {
+            if obj.class.inherits(Class::Reference) && obj.unreference() {
+                unsafe { destroy(self.0) }
+            }
+        }
+    }
+}
+
+/// Formats the object like `Object::to_string` in the engine.
+impl fmt::Display for ObjectRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match live_object(self.0) {
+            Some(obj) => write!(f, "[{}:{}]", obj.class.name(), obj.id),
+            None if self.0.is_null() => f.write_str("[Object:null]"),
+            None => f.write_str("[Deleted Object]"),
+        }
+    }
+}
+
+pub(super) fn register(api: &mut sys::GodotApi) {
+    api.godot_get_class_constructor = get_class_constructor;
+    api.godot_method_bind_get_method = method_bind_get_method;
+    api.godot_method_bind_ptrcall = method_bind_ptrcall;
+    api.godot_object_destroy = object_destroy;
+    api.godot_is_instance_valid = is_instance_valid;
+    api.godot_instance_from_id = instance_from_id;
+    api.godot_global_get_singleton = global_get_singleton;
+}
+
+fn construct(class: Class) -> *mut sys::godot_object {
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    let obj = Box::into_raw(Box::new(MockObject {
+        class,
+        id,
+        refcount: AtomicU32::new(1),
+        refcount_init: AtomicBool::new(true),
+        meta: Mutex::new(Vec::new()),
+        script: Mutex::new(None),
+        script_instance: Mutex::new(None),
+        script_class_name: Mutex::new(None),
+    }));
+
+    LIVE_OBJECTS.lock().push((id, obj as usize));
+    obj as *mut sys::godot_object
+}
+
+unsafe extern "C" fn construct_object() -> *mut sys::godot_object {
+    construct(Class::Object)
+}
+
+unsafe extern "C" fn construct_reference() -> *mut sys::godot_object {
+    construct(Class::Reference)
+}
+
+unsafe extern "C" fn construct_native_script() -> *mut sys::godot_object {
+    construct(Class::NativeScript)
+}
+
+unsafe extern "C" fn get_class_constructor(
+    classname: *const libc::c_char,
+) -> sys::godot_class_constructor {
+    match Class::from_name(&c_str(classname)) {
+        Some(Class::Object) => Some(construct_object),
+        Some(Class::Reference) => Some(construct_reference),
+        Some(Class::NativeScript) => Some(construct_native_script),
+        None => None,
+    }
+}
+
+unsafe extern "C" fn method_bind_get_method(
+    classname: *const libc::c_char,
+    methodname: *const libc::c_char,
+) -> *mut sys::godot_method_bind {
+    Class::from_name(&c_str(classname))
+        .and_then(|class| find_method(class, &c_str(methodname)))
+        .map_or(std::ptr::null_mut(), |bind| {
+            bind as *const MethodBind as *mut sys::godot_method_bind
+        })
+}
+
+unsafe extern "C" fn method_bind_ptrcall(
+    method_bind: *mut sys::godot_method_bind,
+    inst: *mut sys::godot_object,
+    args: *mut *const libc::c_void,
+    ret: *mut libc::c_void,
+) {
+    if method_bind.is_null() {
+        fatal("method bind is null");
+    }
+
+    let bind = &*(method_bind as *const MethodBind);
+    let obj = expect_live_object(inst);
+    if !obj.class.inherits(bind.class) {
+        fatal(&format!(
+            "method {} called on an object of class {}",
+            bind.name,
+            obj.class.name()
+        ));
+    }
+
+    match bind.method {
+        Method::GetClass => {
+            let ret = ret as *mut sys::godot_string;
+            take(ret);
+            init(ret, obj.class.name().to_owned());
+        }
+        Method::IsClass => {
+            let class = string::as_str(*args as *const sys::godot_string);
+            let is_class =
+                matches!(Class::from_name(class), Some(base) if obj.class.inherits(base));
+            *(ret as *mut bool) = is_class;
+        }
+        Method::GetInstanceId => *(ret as *mut i64) = i64::from(obj.id),
+        Method::HasMeta => {
+            let name = string::as_str(*args as *const sys::godot_string);
+            *(ret as *mut bool) = obj.meta.lock().iter().any(|(key, _)| key == name);
+        }
+        Method::GetMeta => {
+            let name = string::as_str(*args as *const sys::godot_string);
+            let value = obj
+                .meta
+                .lock()
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.value().clone());
+            let value = value.unwrap_or_else(|| {
+                error(&format!("object has no metadata named {:?}", name));
+                Value::Nil
+            });
+            let ret = ret as *mut sys::godot_variant;
+            take(ret);
+            init(ret, value);
+        }
+        Method::SetMeta => {
+            let name = string::as_str(*args as *const sys::godot_string).to_owned();
+            let value = OwnedVariant::new(variant::value(*args.add(1) as *const _).clone());
+            let old = {
+                let mut meta = obj.meta.lock();
+                match meta.iter_mut().find(|(key, _)| *key == name) {
+                    Some((_, old)) => Some(std::mem::replace(old, value)),
+                    None => {
+                        meta.push((name, value));
+                        None
+                    }
+                }
+            };
+            // Dropping the old value can run arbitrary code, so the lock is released first.
+            drop(old);
+        }
+        Method::RemoveMeta => {
+            let name = string::as_str(*args as *const sys::godot_string);
+            let old = {
+                let mut meta = obj.meta.lock();
+                meta.iter()
+                    .position(|(key, _)| key == name)
+                    .map(|idx| meta.remove(idx))
+            };
+            drop(old);
+        }
+        Method::GetScript => {
+            // Like `Ref` return values in the engine, the script is returned with a new
+            // reference.
+            let script = script(inst);
+            if let Some(script) = live_object(script) {
+                script.reference();
+            }
+            *(ret as *mut *mut sys::godot_object) = script;
+        }
+        Method::SetScript => nativescript::set_script(inst, *args as *mut sys::godot_object),
+        Method::Reference => *(ret as *mut bool) = obj.reference(),
+        Method::Unreference => *(ret as *mut bool) = obj.unreference(),
+        Method::InitRef => *(ret as *mut bool) = obj.init_ref(),
+        Method::SetClassName => {
+            let class_name = string::as_str(*args as *const sys::godot_string).to_owned();
+            *obj.script_class_name.lock() = Some(class_name);
+        }
+    }
+}
+
+unsafe extern "C" fn object_destroy(obj: *mut sys::godot_object) {
+    destroy(obj);
+}
+
+unsafe extern "C" fn is_instance_valid(obj: *const sys::godot_object) -> sys::godot_bool {
+    live_object(obj).is_some()
+}
+
+/// Object returned for the `Engine` singleton. It's never destroyed.
+static ENGINE: Mutex<usize> = parking_lot::const_mutex(0);
+
+unsafe extern "C" fn global_get_singleton(name: *mut libc::c_char) -> *mut sys::godot_object {
+    let name = c_str(name);
+    if name != "Engine" {
+        error(&format!("no singleton named {:?}", name));
+        return std::ptr::null_mut();
+    }
+
+    let mut engine = ENGINE.lock();
+    if *engine == 0 {
+        *engine = construct(Class::Object) as usize;
+    }
+    *engine as *mut sys::godot_object
+}
+
+unsafe extern "C" fn instance_from_id(id: sys::godot_int) -> *mut sys::godot_object {
+    LIVE_OBJECTS
+        .lock()
+        .iter()
+        .find(|&&(live_id, _)| live_id == id)
+        .map_or(std::ptr::null_mut(), |&(_, ptr)| {
+            ptr as *mut sys::godot_object
+        })
+}
@@ -0,0 +1,626 @@
+use std::sync::Arc;
+
+use crate::sys;
+
+use super::array;
+use super::string::{self, OwnedString};
+use super::variant::Value;
+use super::{error, expect, get_mut, init, normalize_index, take, Boxed};
+
+/// Element types of pool arrays. The elements are stored in the layout of their sys types, so
+/// that pointers to them can be handed out by the read and write accesses.
+pub(super) trait Element: Clone + Default {
+    /// Sys type of the elements.
+    type Sys;
+    /// Type of the argument used to pass an element to the API: the element itself for
+    /// primitive types, and a pointer to it otherwise.
+    type Arg: Copy;
+
+    unsafe fn from_arg(arg: Self::Arg) -> Self;
+    fn into_sys(self) -> Self::Sys;
+    fn to_value(&self) -> Value;
+    fn from_value(value: &Value) -> Self;
+    fn eq(&self, other: &Self) -> bool;
+
+    fn slice_eq(a: &[Self], b: &[Self]) -> bool {
+        a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.eq(b))
+    }
+}
+
+macro_rules! primitive_elements {
+    ($($ty:ty: $Variant:ident($to_value:expr, $from_value:ident);)*) => {
+        $(
+            impl Element for $ty {
+                type Sys = $ty;
+                type Arg = $ty;
+
+                unsafe fn from_arg(arg: $ty) -> Self {
+                    arg
+                }
+
+                fn into_sys(self) -> $ty {
+                    self
+                }
+
+                fn to_value(&self) -> Value {
+                    Value::$Variant($to_value(*self))
+                }
+
+                fn from_value(value: &Value) -> Self {
+                    value.$from_value() as $ty
+                }
+
+                fn eq(&self, other: &Self) -> bool {
+                    self == other
+                }
+            }
+        )*
+    };
+}
+
+primitive_elements! {
+    u8: Int(i64::from, to_i64);
+    sys::godot_int: Int(i64::from, to_i64);
+    sys::godot_real: Real(f64::from, to_f64);
+}
+
+macro_rules! plain_elements {
+    ($($sys:ident: $Variant:ident;)*) => {
+        $(
+            impl Element for sys::$sys {
+                type Sys = sys::$sys;
+                type Arg = *const sys::$sys;
+
+                unsafe fn from_arg(arg: *const sys::$sys) -> Self {
+                    *arg
+                }
+
+                fn into_sys(self) -> sys::$sys {
+                    self
+                }
+
+                fn to_value(&self) -> Value {
+                    Value::$Variant(*self)
+                }
+
+                fn from_value(value: &Value) -> Self {
+                    match value {
+                        Value::$Variant(v) => *v,
+                        _ => Self::default(),
+                    }
+                }
+
+                fn eq(&self, other: &Self) -> bool {
+                    self._dont_touch_that == other._dont_touch_that
+                }
+            }
+        )*
+    };
+}
+
+plain_elements! {
+    godot_vector2: Vector2;
+    godot_vector3: Vector3;
+    godot_color: Color;
+}
+
+impl Element for OwnedString {
+    type Sys = sys::godot_string;
+    type Arg = *const sys::godot_string;
+
+    unsafe fn from_arg(arg: *const sys::godot_string) -> Self {
+        OwnedString::new(string::as_str(arg).to_owned())
+    }
+
+    fn into_sys(self) -> sys::godot_string {
+        let sys = self.0;
+        std::mem::forget(self);
+        sys
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.as_str().to_owned())
+    }
+
+    fn from_value(value: &Value) -> Self {
+        OwnedString::new(value.to_string())
+    }
+
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+/// Sys types of pool arrays, which are copy-on-write like in the engine.
+pub(super) trait PoolArray:
+    Boxed<Target = Arc<Vec<<Self as PoolArray>::Element>>> + Default
+{
+    type Element: Element;
+
+    fn into_value(data: Arc<Vec<Self::Element>>) -> Value;
+
+    /// Converts a variant to a pool array, like the `as_pool_*_array` functions in the engine.
+    fn from_value(value: &Value) -> Arc<Vec<Self::Element>>;
+
+    /// Returns the contents of a pool array, aborting if it's zero-initialized.
+    unsafe fn data<'a>(arr: *const Self) -> &'a Arc<Vec<Self::Element>> {
+        expect(arr, "PoolArray")
+    }
+}
+
+macro_rules! pool_arrays {
+    ($($sys:ident: $elem:ty, $Variant:ident;)*) => {
+        $(
+            unsafe impl Boxed for sys::$sys {
+                type Target = Arc<Vec<$elem>>;
+            }
+
+            impl PoolArray for sys::$sys {
+                type Element = $elem;
+
+                fn into_value(data: Arc<Vec<$elem>>) -> Value {
+                    Value::$Variant(data)
+                }
+
+                fn from_value(value: &Value) -> Arc<Vec<$elem>> {
+                    match value {
+                        Value::$Variant(data) => Arc::clone(data),
+                        Value::Array(array) => Arc::new(
+                            unsafe { array.get() }
+                                .iter()
+                                .map(|v| <$elem>::from_value(v.value()))
+                                .collect(),
+                        ),
+                        _ => Arc::default(),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+pool_arrays! {
+    godot_pool_byte_array: u8, PoolByteArray;
+    godot_pool_int_array: sys::godot_int, PoolIntArray;
+    godot_pool_real_array: sys::godot_real, PoolRealArray;
+    godot_pool_string_array: OwnedString, PoolStringArray;
+    godot_pool_vector2_array: sys::godot_vector2, PoolVector2Array;
+    godot_pool_vector3_array: sys::godot_vector3, PoolVector3Array;
+    godot_pool_color_array: sys::godot_color, PoolColorArray;
+}
+
+macro_rules! register {
+    ($api:ident: $($sys:ident => {
+        $new:ident, $new_copy:ident, $new_with_array:ident, $append:ident, $append_array:ident,
+        $insert:ident, $invert:ident, $push_back:ident, $remove:ident, $resize:ident,
+        $read:ident, $write:ident, $set:ident, $get:ident, $size:ident, $empty:ident,
+        $destroy:ident, $array_new:ident, $read_copy:ident, $read_ptr:ident,
+        $read_assign:ident, $read_destroy:ident, $write_copy:ident, $write_ptr:ident,
+        $write_assign:ident, $write_destroy:ident,
+    })*) => {
+        $(
+            $api.$new = new::<sys::$sys>;
+            $api.$new_copy = new_copy::<sys::$sys>;
+            $api.$new_with_array = new_with_array::<sys::$sys>;
+            $api.$append = push_back::<sys::$sys>;
+            $api.$append_array = append_array::<sys::$sys>;
+            $api.$insert = insert::<sys::$sys>;
+            $api.$invert = invert::<sys::$sys>;
+            $api.$push_back = push_back::<sys::$sys>;
+            $api.$remove = remove::<sys::$sys>;
+            $api.$resize = resize::<sys::$sys>;
+            $api.$read = read::<sys::$sys>;
+            $api.$write = write::<sys::$sys>;
+            $api.$set = set::<sys::$sys>;
+            $api.$get = get::<sys::$sys>;
+            $api.$size = size::<sys::$sys>;
+            $api.$empty = empty::<sys::$sys>;
+            $api.$destroy = destroy::<sys::$sys>;
+            $api.$array_new = array_new::<sys::$sys>;
+            $api.$read_copy = read_access_copy::<<sys::$sys as PoolArray>::Element>;
+            $api.$read_ptr = read_access_ptr::<<sys::$sys as PoolArray>::Element>;
+            $api.$read_assign = read_access_operator_assign::<<sys::$sys as PoolArray>::Element>;
+            $api.$read_destroy = read_access_destroy::<<sys::$sys as PoolArray>::Element>;
+            $api.$write_copy = write_access_copy::<<sys::$sys as PoolArray>::Element>;
+            $api.$write_ptr = write_access_ptr::<<sys::$sys as PoolArray>::Element>;
+            $api.$write_assign = write_access_operator_assign::<<sys::$sys as PoolArray>::Element>;
+            $api.$write_destroy = write_access_destroy::<<sys::$sys as PoolArray>::Element>;
+        )*
+    };
+}
+
+pub(super) fn register(api: &mut sys::GodotApi) {
+    register! {
+        api:
+        godot_pool_byte_array => {
+            godot_pool_byte_array_new,
+            godot_pool_byte_array_new_copy,
+            godot_pool_byte_array_new_with_array,
+            godot_pool_byte_array_append,
+            godot_pool_byte_array_append_array,
+            godot_pool_byte_array_insert,
+            godot_pool_byte_array_invert,
+            godot_pool_byte_array_push_back,
+            godot_pool_byte_array_remove,
+            godot_pool_byte_array_resize,
+            godot_pool_byte_array_read,
+            godot_pool_byte_array_write,
+            godot_pool_byte_array_set,
+            godot_pool_byte_array_get,
+            godot_pool_byte_array_size,
+            godot_pool_byte_array_empty,
+            godot_pool_byte_array_destroy,
+            godot_array_new_pool_byte_array,
+            godot_pool_byte_array_read_access_copy,
+            godot_pool_byte_array_read_access_ptr,
+            godot_pool_byte_array_read_access_operator_assign,
+            godot_pool_byte_array_read_access_destroy,
+            godot_pool_byte_array_write_access_copy,
+            godot_pool_byte_array_write_access_ptr,
+            godot_pool_byte_array_write_access_operator_assign,
+            godot_pool_byte_array_write_access_destroy,
+        }
+        godot_pool_int_array => {
+            godot_pool_int_array_new,
+            godot_pool_int_array_new_copy,
+            godot_pool_int_array_new_with_array,
+            godot_pool_int_array_append,
+            godot_pool_int_array_append_array,
+            godot_pool_int_array_insert,
+            godot_pool_int_array_invert,
+            godot_pool_int_array_push_back,
+            godot_pool_int_array_remove,
+            godot_pool_int_array_resize,
+            godot_pool_int_array_read,
+            godot_pool_int_array_write,
+            godot_pool_int_array_set,
+            godot_pool_int_array_get,
+            godot_pool_int_array_size,
+            godot_pool_int_array_empty,
+            godot_pool_int_array_destroy,
+            godot_array_new_pool_int_array,
+            godot_pool_int_array_read_access_copy,
+            godot_pool_int_array_read_access_ptr,
+            godot_pool_int_array_read_access_operator_assign,
+            godot_pool_int_array_read_access_destroy,
+            godot_pool_int_array_write_access_copy,
+            godot_pool_int_array_write_access_ptr,
+            godot_pool_int_array_write_access_operator_assign,
+            godot_pool_int_array_write_access_destroy,
+        }
+        godot_pool_real_array => {
+            godot_pool_real_array_new,
+            godot_pool_real_array_new_copy,
+            godot_pool_real_array_new_with_array,
+            godot_pool_real_array_append,
+            godot_pool_real_array_append_array,
+            godot_pool_real_array_insert,
+            godot_pool_real_array_invert,
+            godot_pool_real_array_push_back,
+            godot_pool_real_array_remove,
+            godot_pool_real_array_resize,
+            godot_pool_real_array_read,
+            godot_pool_real_array_write,
+            godot_pool_real_array_set,
+            godot_pool_real_array_get,
+            godot_pool_real_array_size,
+            godot_pool_real_array_empty,
+            godot_pool_real_array_destroy,
+            godot_array_new_pool_real_array,
+            godot_pool_real_array_read_access_copy,
+            godot_pool_real_array_read_access_ptr,
+            godot_pool_real_array_read_access_operator_assign,
+            godot_pool_real_array_read_access_destroy,
+            godot_pool_real_array_write_access_copy,
+            godot_pool_real_array_write_access_ptr,
+            godot_pool_real_array_write_access_operator_assign,
+            godot_pool_real_array_write_access_destroy,
+        }
+        godot_pool_string_array => {
+            godot_pool_string_array_new,
+            godot_pool_string_array_new_copy,
+            godot_pool_string_array_new_with_array,
+            godot_pool_string_array_append,
+            godot_pool_string_array_append_array,
+            godot_pool_string_array_insert,
+            godot_pool_string_array_invert,
+            godot_pool_string_array_push_back,
+            godot_pool_string_array_remove,
+            godot_pool_string_array_resize,
+            godot_pool_string_array_read,
+            godot_pool_string_array_write,
+            godot_pool_string_array_set,
+            godot_pool_string_array_get,
+            godot_pool_string_array_size,
+            godot_pool_string_array_empty,
+            godot_pool_string_array_destroy,
+            godot_array_new_pool_string_array,
+            godot_pool_string_array_read_access_copy,
+            godot_pool_string_array_read_access_ptr,
+            godot_pool_string_array_read_access_operator_assign,
+            godot_pool_string_array_read_access_destroy,
+            godot_pool_string_array_write_access_copy,
+            godot_pool_string_array_write_access_ptr,
+            godot_pool_string_array_write_access_operator_assign,
+            godot_pool_string_array_write_access_destroy,
+        }
+        godot_pool_vector2_array => {
+            godot_pool_vector2_array_new,
+            godot_pool_vector2_array_new_copy,
+            godot_pool_vector2_array_new_with_array,
+            godot_pool_vector2_array_append,
+            godot_pool_vector2_array_append_array,
+            godot_pool_vector2_array_insert,
+            godot_pool_vector2_array_invert,
+            godot_pool_vector2_array_push_back,
+            godot_pool_vector2_array_remove,
+            godot_pool_vector2_array_resize,
+            godot_pool_vector2_array_read,
+            godot_pool_vector2_array_write,
+            godot_pool_vector2_array_set,
+            godot_pool_vector2_array_get,
+            godot_pool_vector2_array_size,
+            godot_pool_vector2_array_empty,
+            godot_pool_vector2_array_destroy,
+            godot_array_new_pool_vector2_array,
+            godot_pool_vector2_array_read_access_copy,
+            godot_pool_vector2_array_read_access_ptr,
+            godot_pool_vector2_array_read_access_operator_assign,
+            godot_pool_vector2_array_read_access_destroy,
+            godot_pool_vector2_array_write_access_copy,
+            godot_pool_vector2_array_write_access_ptr,
+            godot_pool_vector2_array_write_access_operator_assign,
+            godot_pool_vector2_array_write_access_destroy,
+        }
+        godot_pool_vector3_array => {
+            godot_pool_vector3_array_new,
+            godot_pool_vector3_array_new_copy,
+            godot_pool_vector3_array_new_with_array,
+            godot_pool_vector3_array_append,
+            godot_pool_vector3_array_append_array,
+            godot_pool_vector3_array_insert,
+            godot_pool_vector3_array_invert,
+            godot_pool_vector3_array_push_back,
+            godot_pool_vector3_array_remove,
+            godot_pool_vector3_array_resize,
+            godot_pool_vector3_array_read,
+            godot_pool_vector3_array_write,
+            godot_pool_vector3_array_set,
+            godot_pool_vector3_array_get,
+            godot_pool_vector3_array_size,
+            godot_pool_vector3_array_empty,
+            godot_pool_vector3_array_destroy,
+            godot_array_new_pool_vector3_array,
+            godot_pool_vector3_array_read_access_copy,
+            godot_pool_vector3_array_read_access_ptr,
+            godot_pool_vector3_array_read_access_operator_assign,
+            godot_pool_vector3_array_read_access_destroy,
+            godot_pool_vector3_array_write_access_copy,
+            godot_pool_vector3_array_write_access_ptr,
+            godot_pool_vector3_array_write_access_operator_assign,
+            godot_pool_vector3_array_write_access_destroy,
+        }
+        godot_pool_color_array => {
+            godot_pool_color_array_new,
+            godot_pool_color_array_new_copy,
+            godot_pool_color_array_new_with_array,
+            godot_pool_color_array_append,
+            godot_pool_color_array_append_array,
+            godot_pool_color_array_insert,
+            godot_pool_color_array_invert,
+            godot_pool_color_array_push_back,
+            godot_pool_color_array_remove,
+            godot_pool_color_array_resize,
+            godot_pool_color_array_read,
+            godot_pool_color_array_write,
+            godot_pool_color_array_set,
+            godot_pool_color_array_get,
+            godot_pool_color_array_size,
+            godot_pool_color_array_empty,
+            godot_pool_color_array_destroy,
+            godot_array_new_pool_color_array,
+            godot_pool_color_array_read_access_copy,
+            godot_pool_color_array_read_access_ptr,
+            godot_pool_color_array_read_access_operator_assign,
+            godot_pool_color_array_read_access_destroy,
+            godot_pool_color_array_write_access_copy,
+            godot_pool_color_array_write_access_ptr,
+            godot_pool_color_array_write_access_operator_assign,
+            godot_pool_color_array_write_access_destroy,
+        }
+    }
+}
+
+/// Returns the contents of a pool array for writing, copying them first if they are shared.
+unsafe fn data_mut<'a, A: PoolArray>(arr: *mut A) -> &'a mut Vec<A::Element> {
+    match get_mut(arr) {
+        Some(data) => Arc::make_mut(data),
+        None => super::fatal("uninitialized PoolArray used"),
+    }
+}
+
+/// Read access to a pool array, which keeps the contents alive while it exists.
+struct ReadAccess<E>(Arc<Vec<E>>);
+
+/// Write access to a pool array, which points into contents made unique when the access was
+/// created.
+struct WriteAccess<E>(*mut E);
+
+unsafe extern "C" fn new<A: PoolArray>(dest: *mut A) {
+    init(dest, Arc::default());
+}
+
+unsafe extern "C" fn new_copy<A: PoolArray>(dest: *mut A, src: *const A) {
+    init(dest, Arc::clone(A::data(src)));
+}
+
+unsafe extern "C" fn new_with_array<A: PoolArray>(dest: *mut A, arr: *const sys::godot_array) {
+    let value = Value::Array(array::shared(arr).clone());
+    init(dest, A::from_value(&value));
+}
+
+unsafe extern "C" fn append_array<A: PoolArray>(arr: *mut A, other: *const A) {
+    let other = Arc::clone(A::data(other));
+    data_mut(arr).extend(other.iter().cloned());
+}
+
+unsafe extern "C" fn insert<A: PoolArray>(
+    arr: *mut A,
+    idx: sys::godot_int,
+    data: <A::Element as Element>::Arg,
+) -> sys::godot_error {
+    let data = A::Element::from_arg(data);
+    let elements = data_mut(arr);
+    if idx >= 0 && idx as usize <= elements.len() {
+        elements.insert(idx as usize, data);
+        sys::godot_error_GODOT_OK
+    } else {
+        error(&format!(
+            "index {} out of bounds (size {})",
+            idx,
+            elements.len()
+        ));
+        sys::godot_error_GODOT_ERR_INVALID_PARAMETER
+    }
+}
+
+unsafe extern "C" fn invert<A: PoolArray>(arr: *mut A) {
+    data_mut(arr).reverse();
+}
+
+unsafe extern "C" fn push_back<A: PoolArray>(arr: *mut A, data: <A::Element as Element>::Arg) {
+    let data = A::Element::from_arg(data);
+    data_mut(arr).push(data);
+}
+
+unsafe extern "C" fn remove<A: PoolArray>(arr: *mut A, idx: sys::godot_int) {
+    let elements = data_mut(arr);
+    match normalize_index(idx, elements.len()) {
+        Some(idx) => {
+            elements.remove(idx);
+        }
+        None => error(&format!(
+            "index {} out of bounds (size {})",
+            idx,
+            elements.len()
+        )),
+    }
+}
+
+unsafe extern "C" fn resize<A: PoolArray>(arr: *mut A, size: sys::godot_int) {
+    data_mut(arr).resize(size.max(0) as usize, A::Element::default());
+}
+
+unsafe extern "C" fn read<A: PoolArray>(arr: *const A) -> *mut sys::godot_pool_array_read_access {
+    let access = ReadAccess(Arc::clone(A::data(arr)));
+    Box::into_raw(Box::new(access)) as *mut _
+}
+
+unsafe extern "C" fn write<A: PoolArray>(arr: *mut A) -> *mut sys::godot_pool_array_write_access {
+    let access = WriteAccess(data_mut(arr).as_mut_ptr());
+    Box::into_raw(Box::new(access)) as *mut _
+}
+
+unsafe extern "C" fn set<A: PoolArray>(
+    arr: *mut A,
+    idx: sys::godot_int,
+    data: <A::Element as Element>::Arg,
+) {
+    let data = A::Element::from_arg(data);
+    let elements = data_mut(arr);
+    match normalize_index(idx, elements.len()) {
+        Some(idx) => elements[idx] = data,
+        None => error(&format!(
+            "index {} out of bounds (size {})",
+            idx,
+            elements.len()
+        )),
+    }
+}
+
+unsafe extern "C" fn get<A: PoolArray>(
+    arr: *const A,
+    idx: sys::godot_int,
+) -> <A::Element as Element>::Sys {
+    let elements = A::data(arr);
+    match normalize_index(idx, elements.len()) {
+        Some(idx) => elements[idx].clone().into_sys(),
+        None => {
+            error(&format!(
+                "index {} out of bounds (size {})",
+                idx,
+                elements.len()
+            ));
+            A::Element::default().into_sys()
+        }
+    }
+}
+
+unsafe extern "C" fn size<A: PoolArray>(arr: *const A) -> sys::godot_int {
+    A::data(arr).len() as sys::godot_int
+}
+
+unsafe extern "C" fn empty<A: PoolArray>(arr: *const A) -> sys::godot_bool {
+    A::data(arr).is_empty()
+}
+
+unsafe extern "C" fn destroy<A: PoolArray>(arr: *mut A) {
+    take(arr);
+}
+
+unsafe extern "C" fn array_new<A: PoolArray>(dest: *mut sys::godot_array, arr: *const A) {
+    init(dest, array::from_elements(A::data(arr)));
+}
+
+unsafe extern "C" fn read_access_copy<E: Element>(
+    read: *const sys::godot_pool_array_read_access,
+) -> *mut sys::godot_pool_array_read_access {
+    let read = &*(read as *const ReadAccess<E>);
+    Box::into_raw(Box::new(ReadAccess(Arc::clone(&read.0)))) as *mut _
+}
+
+unsafe extern "C" fn read_access_ptr<E: Element>(
+    read: *const sys::godot_pool_array_read_access,
+) -> *const E::Sys {
+    let read = &*(read as *const ReadAccess<E>);
+    read.0.as_ptr() as *const E::Sys
+}
+
+unsafe extern "C" fn read_access_operator_assign<E: Element>(
+    read: *mut sys::godot_pool_array_read_access,
+    other: *mut sys::godot_pool_array_read_access,
+) {
+    let other = Arc::clone(&(*(other as *const ReadAccess<E>)).0);
+    (*(read as *mut ReadAccess<E>)).0 = other;
+}
+
+unsafe extern "C" fn read_access_destroy<E: Element>(read: *mut sys::godot_pool_array_read_access) {
+    drop(Box::from_raw(read as *mut ReadAccess<E>));
+}
+
+unsafe extern "C" fn write_access_copy<E: Element>(
+    write: *const sys::godot_pool_array_write_access,
+) -> *mut sys::godot_pool_array_write_access {
+    let write = &*(write as *const WriteAccess<E>);
+    Box::into_raw(Box::new(WriteAccess(write.0))) as *mut _
+}
+
+unsafe extern "C" fn write_access_ptr<E: Element>(
+    write: *const sys::godot_pool_array_write_access,
+) -> *mut E::Sys {
+    let write = &*(write as *const WriteAccess<E>);
+    write.0 as *mut E::Sys
+}
+
+unsafe extern "C" fn write_access_operator_assign<E: Element>(
+    write: *mut sys::godot_pool_array_write_access,
+    other: *mut sys::godot_pool_array_write_access,
+) {
+    (*(write as *mut WriteAccess<E>)).0 = (*(other as *const WriteAccess<E>)).0;
+}
+
+unsafe extern "C" fn write_access_destroy<E: Element>(
+    write: *mut sys::godot_pool_array_write_access,
+) {
+    drop(Box::from_raw(write as *mut WriteAccess<E>));
+}
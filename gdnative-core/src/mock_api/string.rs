@@ -0,0 +1,413 @@
+use std::cmp::Ordering;
+use std::ffi::CString;
+
+use crate::sys;
+
+use super::{c_str, get, init, new, take, Boxed};
+
+unsafe impl Boxed for sys::godot_string {
+    type Target = String;
+}
+
+unsafe impl Boxed for sys::godot_string_name {
+    type Target = String;
+}
+
+unsafe impl Boxed for sys::godot_char_string {
+    type Target = CString;
+}
+
+unsafe impl Boxed for sys::godot_node_path {
+    type Target = String;
+}
+
+pub(super) fn register(api: &mut sys::GodotApi) {
+    api.godot_string_new = string_new;
+    api.godot_string_new_copy = string_new_copy;
+    api.godot_string_destroy = string_destroy;
+    api.godot_string_chars_to_utf8 = string_chars_to_utf8;
+    api.godot_string_chars_to_utf8_with_len = string_chars_to_utf8_with_len;
+    api.godot_string_utf8 = string_utf8;
+    api.godot_string_length = string_length;
+    api.godot_string_empty = string_empty;
+    api.godot_string_operator_equal = string_operator_equal;
+    api.godot_string_operator_less = string_operator_less;
+    api.godot_string_operator_plus = string_operator_plus;
+    api.godot_string_hash = string_hash;
+    api.godot_string_hash64 = string_hash64;
+    api.godot_string_begins_with = string_begins_with;
+    api.godot_string_begins_with_char_array = string_begins_with_char_array;
+    api.godot_string_ends_with = string_ends_with;
+    api.godot_string_find = string_find;
+    api.godot_string_find_from = string_find_from;
+    api.godot_string_find_last = string_find_last;
+    api.godot_string_substr = string_substr;
+    api.godot_string_to_lower = string_to_lower;
+    api.godot_string_to_upper = string_to_upper;
+    api.godot_string_to_int = string_to_int;
+    api.godot_string_to_float = string_to_float;
+    api.godot_string_to_double = string_to_double;
+    api.godot_string_is_numeric = string_is_numeric;
+
+    api.godot_char_string_length = char_string_length;
+    api.godot_char_string_get_data = char_string_get_data;
+    api.godot_char_string_destroy = char_string_destroy;
+
+    api.godot_string_name_new = string_name_new;
+    api.godot_string_name_new_data = string_name_new_data;
+    api.godot_string_name_get_name = string_name_get_name;
+    api.godot_string_name_get_hash = string_name_get_hash;
+    api.godot_string_name_operator_equal = string_name_operator_equal;
+    api.godot_string_name_operator_less = string_name_operator_less;
+    api.godot_string_name_destroy = string_name_destroy;
+
+    api.godot_node_path_new = node_path_new;
+    api.godot_node_path_new_copy = node_path_new_copy;
+    api.godot_node_path_destroy = node_path_destroy;
+    api.godot_node_path_as_string = node_path_as_string;
+    api.godot_node_path_is_absolute = node_path_is_absolute;
+    api.godot_node_path_is_empty = node_path_is_empty;
+    api.godot_node_path_operator_equal = node_path_operator_equal;
+}
+
+/// Returns the contents of a string. Zero-initialized strings are empty, like in the engine.
+pub(super) unsafe fn as_str<'a>(s: *const sys::godot_string) -> &'a str {
+    get(s).map_or("", String::as_str)
+}
+
+/// Creates a new string with the given contents.
+pub(super) fn new_string(s: String) -> sys::godot_string {
+    unsafe { new(s) }
+}
+
+/// Hashes a string with the DJB2 algorithm, as the engine does.
+pub(super) fn hash(s: &str) -> u32 {
+    s.chars().fold(5381u32, |hash, c| {
+        hash.wrapping_shl(5)
+            .wrapping_add(hash)
+            .wrapping_add(c as u32)
+    })
+}
+
+/// A string owned by Rust code, e.g. as an element of a `PoolStringArray`. Has the same layout
+/// as `sys::godot_string`, so references to it can be handed out to callers.
+#[repr(transparent)]
+pub(super) struct OwnedString(pub(super) sys::godot_string);
+
+impl OwnedString {
+    pub(super) fn new(s: String) -> Self {
+        OwnedString(new_string(s))
+    }
+
+    pub(super) fn as_str(&self) -> &str {
+        unsafe { as_str(&self.0) }
+    }
+}
+
+impl Clone for OwnedString {
+    fn clone(&self) -> Self {
+        OwnedString::new(self.as_str().to_owned())
+    }
+}
+
+impl Default for OwnedString {
+    fn default() -> Self {
+        OwnedString::new(String::new())
+    }
+}
+
+impl Drop for OwnedString {
+    fn drop(&mut self) {
+        unsafe {
+            take(&mut self.0);
+        }
+    }
+}
+
+/// Converts a byte index into `s` into a character index.
+fn char_index(s: &str, byte_idx: usize) -> sys::godot_int {
+    s[..byte_idx].chars().count() as sys::godot_int
+}
+
+/// Converts a character index into `s` into a byte index, clamping to the end of the string.
+fn byte_index(s: &str, char_idx: usize) -> usize {
+    s.char_indices()
+        .nth(char_idx)
+        .map_or(s.len(), |(idx, _)| idx)
+}
+
+/// Parses the longest prefix of `s` that looks like a number, ignoring whitespace.
+fn parse_prefix<T: std::str::FromStr + Default>(s: &str, allow_float: bool) -> T {
+    let s = s.trim();
+    let end = s
+        .char_indices()
+        .take_while(|&(idx, c)| {
+            c.is_ascii_digit() || (idx == 0 && (c == '-' || c == '+')) || (allow_float && c == '.')
+        })
+        .last()
+        .map_or(0, |(idx, c)| idx + c.len_utf8());
+
+    s[..end].parse().unwrap_or_default()
+}
+
+unsafe extern "C" fn string_new(dest: *mut sys::godot_string) {
+    init(dest, String::new());
+}
+
+unsafe extern "C" fn string_new_copy(dest: *mut sys::godot_string, src: *const sys::godot_string) {
+    init(dest, as_str(src).to_owned());
+}
+
+unsafe extern "C" fn string_destroy(s: *mut sys::godot_string) {
+    take(s);
+}
+
+unsafe extern "C" fn string_chars_to_utf8(utf8: *const libc::c_char) -> sys::godot_string {
+    new_string(c_str(utf8).into_owned())
+}
+
+unsafe extern "C" fn string_chars_to_utf8_with_len(
+    utf8: *const libc::c_char,
+    len: sys::godot_int,
+) -> sys::godot_string {
+    let bytes = std::slice::from_raw_parts(utf8 as *const u8, len as usize);
+    new_string(String::from_utf8_lossy(bytes).into_owned())
+}
+
+unsafe extern "C" fn string_utf8(s: *const sys::godot_string) -> sys::godot_char_string {
+    let bytes = as_str(s)
+        .bytes()
+        .take_while(|&b| b != 0)
+        .collect::<Vec<_>>();
+    new(CString::new(bytes).unwrap())
+}
+
+unsafe extern "C" fn string_length(s: *const sys::godot_string) -> sys::godot_int {
+    as_str(s).chars().count() as sys::godot_int
+}
+
+unsafe extern "C" fn string_empty(s: *const sys::godot_string) -> sys::godot_bool {
+    as_str(s).is_empty()
+}
+
+unsafe extern "C" fn string_operator_equal(
+    s: *const sys::godot_string,
+    b: *const sys::godot_string,
+) -> sys::godot_bool {
+    as_str(s) == as_str(b)
+}
+
+unsafe extern "C" fn string_operator_less(
+    s: *const sys::godot_string,
+    b: *const sys::godot_string,
+) -> sys::godot_bool {
+    as_str(s).cmp(as_str(b)) == Ordering::Less
+}
+
+unsafe extern "C" fn string_operator_plus(
+    s: *const sys::godot_string,
+    b: *const sys::godot_string,
+) -> sys::godot_string {
+    new_string(format!("{}{}", as_str(s), as_str(b)))
+}
+
+unsafe extern "C" fn string_hash(s: *const sys::godot_string) -> u32 {
+    hash(as_str(s))
+}
+
+unsafe extern "C" fn string_hash64(s: *const sys::godot_string) -> u64 {
+    as_str(s).chars().fold(5381u64, |hash, c| {
+        hash.wrapping_shl(5)
+            .wrapping_add(hash)
+            .wrapping_add(c as u64)
+    })
+}
+
+unsafe extern "C" fn string_begins_with(
+    s: *const sys::godot_string,
+    string: *const sys::godot_string,
+) -> sys::godot_bool {
+    as_str(s).starts_with(as_str(string))
+}
+
+unsafe extern "C" fn string_begins_with_char_array(
+    s: *const sys::godot_string,
+    char_array: *const libc::c_char,
+) -> sys::godot_bool {
+    as_str(s).starts_with(&*c_str(char_array))
+}
+
+unsafe extern "C" fn string_ends_with(
+    s: *const sys::godot_string,
+    string: *const sys::godot_string,
+) -> sys::godot_bool {
+    as_str(s).ends_with(as_str(string))
+}
+
+unsafe extern "C" fn string_find(
+    s: *const sys::godot_string,
+    what: sys::godot_string,
+) -> sys::godot_int {
+    string_find_from(s, what, 0)
+}
+
+unsafe extern "C" fn string_find_from(
+    s: *const sys::godot_string,
+    what: sys::godot_string,
+    from: sys::godot_int,
+) -> sys::godot_int {
+    let s = as_str(s);
+    if from < 0 {
+        return -1;
+    }
+
+    let start = byte_index(s, from as usize);
+    s[start..]
+        .find(as_str(&what))
+        .map_or(-1, |idx| char_index(s, start + idx))
+}
+
+unsafe extern "C" fn string_find_last(
+    s: *const sys::godot_string,
+    what: sys::godot_string,
+) -> sys::godot_int {
+    let s = as_str(s);
+    s.rfind(as_str(&what)).map_or(-1, |idx| char_index(s, idx))
+}
+
+unsafe extern "C" fn string_substr(
+    s: *const sys::godot_string,
+    from: sys::godot_int,
+    chars: sys::godot_int,
+) -> sys::godot_string {
+    let substr = as_str(s)
+        .chars()
+        .skip(from.max(0) as usize)
+        .take(chars.max(0) as usize)
+        .collect();
+    new_string(substr)
+}
+
+unsafe extern "C" fn string_to_lower(s: *const sys::godot_string) -> sys::godot_string {
+    new_string(as_str(s).to_lowercase())
+}
+
+unsafe extern "C" fn string_to_upper(s: *const sys::godot_string) -> sys::godot_string {
+    new_string(as_str(s).to_uppercase())
+}
+
+unsafe extern "C" fn string_to_int(s: *const sys::godot_string) -> sys::godot_int {
+    parse_prefix(as_str(s), false)
+}
+
+unsafe extern "C" fn string_to_float(s: *const sys::godot_string) -> sys::godot_real {
+    parse_prefix(as_str(s), true)
+}
+
+unsafe extern "C" fn string_to_double(s: *const sys::godot_string) -> f64 {
+    parse_prefix(as_str(s), true)
+}
+
+unsafe extern "C" fn string_is_numeric(s: *const sys::godot_string) -> sys::godot_bool {
+    as_str(s).parse::<f64>().is_ok()
+}
+
+unsafe extern "C" fn char_string_length(cs: *const sys::godot_char_string) -> sys::godot_int {
+    get(cs).map_or(0, |cs| cs.as_bytes().len() as sys::godot_int)
+}
+
+unsafe extern "C" fn char_string_get_data(
+    cs: *const sys::godot_char_string,
+) -> *const libc::c_char {
+    get(cs).map_or(b"\0".as_ptr() as *const _, |cs| cs.as_ptr())
+}
+
+unsafe extern "C" fn char_string_destroy(cs: *mut sys::godot_char_string) {
+    take(cs);
+}
+
+unsafe extern "C" fn string_name_new(
+    dest: *mut sys::godot_string_name,
+    name: *const sys::godot_string,
+) {
+    init(dest, as_str(name).to_owned());
+}
+
+unsafe extern "C" fn string_name_new_data(
+    dest: *mut sys::godot_string_name,
+    name: *const libc::c_char,
+) {
+    init(dest, c_str(name).into_owned());
+}
+
+unsafe fn string_name_as_str<'a>(s: *const sys::godot_string_name) -> &'a str {
+    get(s).map_or("", String::as_str)
+}
+
+unsafe extern "C" fn string_name_get_name(s: *const sys::godot_string_name) -> sys::godot_string {
+    new_string(string_name_as_str(s).to_owned())
+}
+
+unsafe extern "C" fn string_name_get_hash(s: *const sys::godot_string_name) -> u32 {
+    hash(string_name_as_str(s))
+}
+
+unsafe extern "C" fn string_name_operator_equal(
+    s: *const sys::godot_string_name,
+    other: *const sys::godot_string_name,
+) -> sys::godot_bool {
+    string_name_as_str(s) == string_name_as_str(other)
+}
+
+unsafe extern "C" fn string_name_operator_less(
+    s: *const sys::godot_string_name,
+    other: *const sys::godot_string_name,
+) -> sys::godot_bool {
+    string_name_as_str(s) < string_name_as_str(other)
+}
+
+unsafe extern "C" fn string_name_destroy(s: *mut sys::godot_string_name) {
+    take(s);
+}
+
+/// Returns the contents of a node path. Node paths are stored unparsed.
+pub(super) unsafe fn node_path_as_str<'a>(np: *const sys::godot_node_path) -> &'a str {
+    get(np).map_or("", String::as_str)
+}
+
+unsafe extern "C" fn node_path_new(
+    dest: *mut sys::godot_node_path,
+    from: *const sys::godot_string,
+) {
+    init(dest, as_str(from).to_owned());
+}
+
+unsafe extern "C" fn node_path_new_copy(
+    dest: *mut sys::godot_node_path,
+    src: *const sys::godot_node_path,
+) {
+    init(dest, node_path_as_str(src).to_owned());
+}
+
+unsafe extern "C" fn node_path_destroy(np: *mut sys::godot_node_path) {
+    take(np);
+}
+
+unsafe extern "C" fn node_path_as_string(np: *const sys::godot_node_path) -> sys::godot_string {
+    new_string(node_path_as_str(np).to_owned())
+}
+
+unsafe extern "C" fn node_path_is_absolute(np: *const sys::godot_node_path) -> sys::godot_bool {
+    node_path_as_str(np).starts_with('/')
+}
+
+unsafe extern "C" fn node_path_is_empty(np: *const sys::godot_node_path) -> sys::godot_bool {
+    node_path_as_str(np).is_empty()
+}
+
+unsafe extern "C" fn node_path_operator_equal(
+    np: *const sys::godot_node_path,
+    b: *const sys::godot_node_path,
+) -> sys::godot_bool {
+    node_path_as_str(np) == node_path_as_str(b)
+}
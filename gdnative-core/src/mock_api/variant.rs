@@ -0,0 +1,648 @@
+use std::fmt::{self, Write as _};
+use std::sync::Arc;
+
+use crate::sys;
+
+use super::array::{self, SharedArray};
+use super::dictionary::{self, SharedDictionary};
+use super::object::{self, ObjectRef};
+use super::pool_array::{Element, PoolArray};
+use super::string::{self, OwnedString};
+use super::{get, init, new, take, Boxed};
+
+unsafe impl Boxed for sys::godot_variant {
+    type Target = Value;
+}
+
+/// Value of a variant. Collections are stored as Rust values with the same sharing semantics
+/// as in the engine: arrays and dictionaries are shared, and pool arrays are copy-on-write.
+#[derive(Clone)]
+pub(super) enum Value {
+    Nil,
+    Bool(bool),
+    Int(i64),
+    Real(f64),
+    String(String),
+    Vector2(sys::godot_vector2),
+    Rect2(sys::godot_rect2),
+    Vector3(sys::godot_vector3),
+    Transform2D(sys::godot_transform2d),
+    Plane(sys::godot_plane),
+    Quat(sys::godot_quat),
+    Aabb(sys::godot_aabb),
+    Basis(sys::godot_basis),
+    Transform(sys::godot_transform),
+    Color(sys::godot_color),
+    NodePath(String),
+    Rid(sys::godot_rid),
+    Object(ObjectRef),
+    Dictionary(SharedDictionary),
+    Array(SharedArray),
+    PoolByteArray(Arc<Vec<u8>>),
+    PoolIntArray(Arc<Vec<sys::godot_int>>),
+    PoolRealArray(Arc<Vec<sys::godot_real>>),
+    PoolStringArray(Arc<Vec<OwnedString>>),
+    PoolVector2Array(Arc<Vec<sys::godot_vector2>>),
+    PoolVector3Array(Arc<Vec<sys::godot_vector3>>),
+    PoolColorArray(Arc<Vec<sys::godot_color>>),
+}
+
+const NIL: Value = Value::Nil;
+
+/// A variant owned by Rust code, e.g. as an element of a collection. Has the same layout as
+/// `sys::godot_variant`, so references to it can be handed out to callers.
+#[repr(transparent)]
+pub(super) struct OwnedVariant(sys::godot_variant);
+
+impl OwnedVariant {
+    pub(super) fn new(value: Value) -> Self {
+        unsafe { OwnedVariant(new(value)) }
+    }
+
+    pub(super) fn value(&self) -> &Value {
+        unsafe { value(&self.0) }
+    }
+
+    pub(super) fn sys_mut(&mut self) -> *mut sys::godot_variant {
+        &mut self.0
+    }
+
+    pub(super) fn into_sys(self) -> sys::godot_variant {
+        let sys = self.0;
+        std::mem::forget(self);
+        sys
+    }
+}
+
+impl Clone for OwnedVariant {
+    fn clone(&self) -> Self {
+        OwnedVariant::new(self.value().clone())
+    }
+}
+
+impl Drop for OwnedVariant {
+    fn drop(&mut self) {
+        unsafe {
+            take(&mut self.0);
+        }
+    }
+}
+
+impl Default for OwnedVariant {
+    fn default() -> Self {
+        OwnedVariant::new(Value::Nil)
+    }
+}
+
+/// Returns the value of a variant. Zero-initialized variants are `Nil`, like in the engine.
+pub(super) unsafe fn value<'a>(v: *const sys::godot_variant) -> &'a Value {
+    get(v).unwrap_or(&NIL)
+}
+
+/// Reads the floating point components of a plain math type.
+fn reals(bytes: &[u8]) -> impl Iterator<Item = f32> + '_ {
+    bytes
+        .chunks_exact(4)
+        .map(|chunk| f32::from_ne_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+}
+
+/// Writes a list of values separated by `", "`.
+fn write_list<T>(
+    f: &mut fmt::Formatter<'_>,
+    items: impl IntoIterator<Item = T>,
+    mut write_item: impl FnMut(&mut fmt::Formatter<'_>, T) -> fmt::Result,
+) -> fmt::Result {
+    for (idx, item) in items.into_iter().enumerate() {
+        if idx > 0 {
+            f.write_str(", ")?;
+        }
+        write_item(f, item)?;
+    }
+    Ok(())
+}
+
+fn write_reals(f: &mut fmt::Formatter<'_>, bytes: &[u8]) -> fmt::Result {
+    f.write_char('(')?;
+    write_list(f, reals(bytes), |f, r| write!(f, "{}", r))?;
+    f.write_char(')')
+}
+
+impl Value {
+    pub(super) fn variant_type(&self) -> sys::godot_variant_type {
+        match self {
+            Value::Nil => sys::godot_variant_type_GODOT_VARIANT_TYPE_NIL,
+            Value::Bool(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_BOOL,
+            Value::Int(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_INT,
+            Value::Real(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_REAL,
+            Value::String(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_STRING,
+            Value::Vector2(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_VECTOR2,
+            Value::Rect2(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_RECT2,
+            Value::Vector3(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_VECTOR3,
+            Value::Transform2D(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_TRANSFORM2D,
+            Value::Plane(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_PLANE,
+            Value::Quat(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_QUAT,
+            Value::Aabb(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_AABB,
+            Value::Basis(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_BASIS,
+            Value::Transform(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_TRANSFORM,
+            Value::Color(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_COLOR,
+            Value::NodePath(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_NODE_PATH,
+            Value::Rid(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_RID,
+            Value::Object(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_OBJECT,
+            Value::Dictionary(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_DICTIONARY,
+            Value::Array(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_ARRAY,
+            Value::PoolByteArray(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_POOL_BYTE_ARRAY,
+            Value::PoolIntArray(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_POOL_INT_ARRAY,
+            Value::PoolRealArray(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_POOL_REAL_ARRAY,
+            Value::PoolStringArray(_) => {
+                sys::godot_variant_type_GODOT_VARIANT_TYPE_POOL_STRING_ARRAY
+            }
+            Value::PoolVector2Array(_) => {
+                sys::godot_variant_type_GODOT_VARIANT_TYPE_POOL_VECTOR2_ARRAY
+            }
+            Value::PoolVector3Array(_) => {
+                sys::godot_variant_type_GODOT_VARIANT_TYPE_POOL_VECTOR3_ARRAY
+            }
+            Value::PoolColorArray(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_POOL_COLOR_ARRAY,
+        }
+    }
+
+    /// Converts the value to `bool`, like `Variant::booleanize` in the engine.
+    pub(super) fn booleanize(&self) -> bool {
+        match self {
+            Value::Nil => false,
+            Value::Bool(b) => *b,
+            Value::Int(i) => *i != 0,
+            Value::Real(r) => *r != 0.0,
+            Value::String(s) | Value::NodePath(s) => !s.is_empty(),
+            Value::Object(obj) => !obj.as_ptr().is_null(),
+            Value::Dictionary(dict) => !unsafe { dict.get() }.is_empty(),
+            Value::Array(array) => !unsafe { array.get() }.is_empty(),
+            Value::PoolByteArray(a) => !a.is_empty(),
+            Value::PoolIntArray(a) => !a.is_empty(),
+            Value::PoolRealArray(a) => !a.is_empty(),
+            Value::PoolStringArray(a) => !a.is_empty(),
+            Value::PoolVector2Array(a) => !a.is_empty(),
+            Value::PoolVector3Array(a) => !a.is_empty(),
+            Value::PoolColorArray(a) => !a.is_empty(),
+            other => matches!(other.plain_bytes(), Some(b) if b.iter().any(|&b| b != 0)),
+        }
+    }
+
+    /// Returns the bytes of plain math types and RIDs.
+    fn plain_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Value::Vector2(v) => Some(&v._dont_touch_that),
+            Value::Rect2(v) => Some(&v._dont_touch_that),
+            Value::Vector3(v) => Some(&v._dont_touch_that),
+            Value::Transform2D(v) => Some(&v._dont_touch_that),
+            Value::Plane(v) => Some(&v._dont_touch_that),
+            Value::Quat(v) => Some(&v._dont_touch_that),
+            Value::Aabb(v) => Some(&v._dont_touch_that),
+            Value::Basis(v) => Some(&v._dont_touch_that),
+            Value::Transform(v) => Some(&v._dont_touch_that),
+            Value::Color(v) => Some(&v._dont_touch_that),
+            Value::Rid(v) => Some(&v._dont_touch_that),
+            _ => None,
+        }
+    }
+
+    pub(super) fn to_i64(&self) -> i64 {
+        match self {
+            Value::Bool(b) => *b as i64,
+            Value::Int(i) => *i,
+            Value::Real(r) => *r as i64,
+            Value::String(s) => s.trim().parse().unwrap_or(0),
+            _ => 0,
+        }
+    }
+
+    pub(super) fn to_f64(&self) -> f64 {
+        match self {
+            Value::Bool(b) => *b as i64 as f64,
+            Value::Int(i) => *i as f64,
+            Value::Real(r) => *r,
+            Value::String(s) => s.trim().parse().unwrap_or(0.0),
+            _ => 0.0,
+        }
+    }
+
+    /// Compares two values for equality, like the `==` operator in the engine. Numbers are
+    /// compared by value, arrays and dictionaries by reference, and pool arrays by contents.
+    pub(super) fn equals(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::Nil, Value::Nil) => true,
+            (Value::Bool(a), Value::Bool(b)) => a == b,
+            (Value::Int(a), Value::Int(b)) => a == b,
+            (Value::Int(_), Value::Real(_)) | (Value::Real(_), Value::Int(_)) => {
+                self.to_f64() == other.to_f64()
+            }
+            (Value::Real(a), Value::Real(b)) => a == b,
+            (Value::String(a), Value::String(b)) => a == b,
+            (Value::NodePath(a), Value::NodePath(b)) => a == b,
+            (Value::Object(a), Value::Object(b)) => a.as_ptr() == b.as_ptr(),
+            (Value::Dictionary(a), Value::Dictionary(b)) => a.ptr_eq(b),
+            (Value::Array(a), Value::Array(b)) => a.ptr_eq(b),
+            (Value::PoolByteArray(a), Value::PoolByteArray(b)) => a == b,
+            (Value::PoolIntArray(a), Value::PoolIntArray(b)) => a == b,
+            (Value::PoolRealArray(a), Value::PoolRealArray(b)) => a == b,
+            (Value::PoolStringArray(a), Value::PoolStringArray(b)) => {
+                Element::slice_eq(a.as_slice(), b.as_slice())
+            }
+            (Value::PoolVector2Array(a), Value::PoolVector2Array(b)) => {
+                Element::slice_eq(a.as_slice(), b.as_slice())
+            }
+            (Value::PoolVector3Array(a), Value::PoolVector3Array(b)) => {
+                Element::slice_eq(a.as_slice(), b.as_slice())
+            }
+            (Value::PoolColorArray(a), Value::PoolColorArray(b)) => {
+                Element::slice_eq(a.as_slice(), b.as_slice())
+            }
+            (a, b) => {
+                a.variant_type() == b.variant_type()
+                    && a.plain_bytes().is_some()
+                    && a.plain_bytes() == b.plain_bytes()
+            }
+        }
+    }
+
+    /// Compares two values for use as dictionary keys. Unlike `equals`, values of different
+    /// types are never equal.
+    pub(super) fn hash_equals(&self, other: &Value) -> bool {
+        self.variant_type() == other.variant_type() && self.equals(other)
+    }
+
+    /// Returns a hash of the value that is consistent with `hash_equals`.
+    pub(super) fn hash(&self) -> u32 {
+        let hash = match self {
+            Value::Nil => 0,
+            Value::Bool(b) => *b as u32,
+            Value::Int(i) => *i as u32 ^ (*i >> 32) as u32,
+            Value::Real(r) => r.to_bits() as u32 ^ (r.to_bits() >> 32) as u32,
+            Value::String(s) | Value::NodePath(s) => string::hash(s),
+            Value::Object(obj) => obj.as_ptr() as usize as u32,
+            Value::Dictionary(dict) => dictionary::hash(unsafe { dict.get() }),
+            Value::Array(array) => array::hash(unsafe { array.get() }),
+            other => {
+                // Pool arrays are hashed by their string representation, which is slow but
+                // consistent with `equals`.
+                let bytes = other
+                    .plain_bytes()
+                    .map(<[u8]>::to_vec)
+                    .unwrap_or_else(|| other.to_string().into_bytes());
+                bytes.iter().fold(5381u32, |hash, &b| {
+                    hash.wrapping_shl(5)
+                        .wrapping_add(hash)
+                        .wrapping_add(b as u32)
+                })
+            }
+        };
+
+        hash ^ self.variant_type()
+    }
+
+    /// Returns a copy of the value, where arrays and dictionaries are copied as well instead of
+    /// being shared.
+    pub(super) fn duplicate(&self, deep: bool) -> Value {
+        match self {
+            Value::Array(array) => Value::Array(array::duplicate(unsafe { array.get() }, deep)),
+            Value::Dictionary(dict) => {
+                Value::Dictionary(dictionary::duplicate(unsafe { dict.get() }, deep))
+            }
+            other => other.clone(),
+        }
+    }
+
+    /// Orders two values, for sorting arrays. Numbers and strings are ordered by value, and
+    /// other values by their types.
+    pub(super) fn less(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::Bool(a), Value::Bool(b)) => a < b,
+            (Value::Int(a), Value::Int(b)) => a < b,
+            (Value::Int(_), Value::Real(_))
+            | (Value::Real(_), Value::Int(_))
+            | (Value::Real(_), Value::Real(_)) => self.to_f64() < other.to_f64(),
+            (Value::String(a), Value::String(b)) => a < b,
+            (a, b) => a.variant_type() < b.variant_type(),
+        }
+    }
+}
+
+/// Formats the value like `Variant::operator String` in the engine. Math types are formatted
+/// as lists of their components.
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Nil => f.write_str("Null"),
+            Value::Bool(true) => f.write_str("True"),
+            Value::Bool(false) => f.write_str("False"),
+            Value::Int(i) => write!(f, "{}", i),
+            Value::Real(r) => write!(f, "{}", r),
+            Value::String(s) | Value::NodePath(s) => f.write_str(s),
+            Value::Color(c) => {
+                for (idx, r) in reals(&c._dont_touch_that).enumerate() {
+                    if idx > 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{}", r)?;
+                }
+                Ok(())
+            }
+            Value::Rid(_) => f.write_str("[RID]"),
+            Value::Object(obj) => write!(f, "{}", obj),
+            Value::Dictionary(dict) => {
+                f.write_char('{')?;
+                write_list(f, unsafe { dict.get() }.iter(), |f, (k, v)| {
+                    write!(f, "{}:{}", k.value(), v.value())
+                })?;
+                f.write_char('}')
+            }
+            Value::Array(array) => {
+                f.write_char('[')?;
+                write_list(f, unsafe { array.get() }.iter(), |f, v| {
+                    write!(f, "{}", v.value())
+                })?;
+                f.write_char(']')
+            }
+            Value::PoolByteArray(a) => write_pool(f, a),
+            Value::PoolIntArray(a) => write_pool(f, a),
+            Value::PoolRealArray(a) => write_pool(f, a),
+            Value::PoolStringArray(a) => write_pool(f, a),
+            Value::PoolVector2Array(a) => write_pool(f, a),
+            Value::PoolVector3Array(a) => write_pool(f, a),
+            Value::PoolColorArray(a) => write_pool(f, a),
+            other => write_reals(f, other.plain_bytes().unwrap_or(&[])),
+        }
+    }
+}
+
+fn write_pool<E: Element>(f: &mut fmt::Formatter<'_>, elements: &[E]) -> fmt::Result {
+    f.write_char('[')?;
+    write_list(f, elements, |f, e| write!(f, "{}", e.to_value()))?;
+    f.write_char(']')
+}
+
+macro_rules! plain_types {
+    ($($Variant:ident($sys:ident): $new_fn:ident, $as_fn:ident;)*) => {
+        $(
+            unsafe extern "C" fn $new_fn(dest: *mut sys::godot_variant, v: *const sys::$sys) {
+                init(dest, Value::$Variant(*v));
+            }
+
+            unsafe extern "C" fn $as_fn(v: *const sys::godot_variant) -> sys::$sys {
+                match value(v) {
+                    Value::$Variant(v) => *v,
+                    _ => sys::$sys::default(),
+                }
+            }
+        )*
+
+        fn register_plain_types(api: &mut sys::GodotApi) {
+            $(
+                api.$new_fn = $new_fn;
+                api.$as_fn = $as_fn;
+            )*
+        }
+    };
+}
+
+plain_types! {
+    Vector2(godot_vector2): godot_variant_new_vector2, godot_variant_as_vector2;
+    Rect2(godot_rect2): godot_variant_new_rect2, godot_variant_as_rect2;
+    Vector3(godot_vector3): godot_variant_new_vector3, godot_variant_as_vector3;
+    Transform2D(godot_transform2d): godot_variant_new_transform2d, godot_variant_as_transform2d;
+    Plane(godot_plane): godot_variant_new_plane, godot_variant_as_plane;
+    Quat(godot_quat): godot_variant_new_quat, godot_variant_as_quat;
+    Aabb(godot_aabb): godot_variant_new_aabb, godot_variant_as_aabb;
+    Basis(godot_basis): godot_variant_new_basis, godot_variant_as_basis;
+    Transform(godot_transform): godot_variant_new_transform, godot_variant_as_transform;
+    Color(godot_color): godot_variant_new_color, godot_variant_as_color;
+    Rid(godot_rid): godot_variant_new_rid, godot_variant_as_rid;
+}
+
+macro_rules! pool_array_types {
+    ($($sys:ident: $new_fn:ident, $as_fn:ident;)*) => {
+        $(
+            unsafe extern "C" fn $new_fn(dest: *mut sys::godot_variant, a: *const sys::$sys) {
+                init(dest, <sys::$sys as PoolArray>::into_value(PoolArray::data(a).clone()));
+            }
+
+            unsafe extern "C" fn $as_fn(v: *const sys::godot_variant) -> sys::$sys {
+                new(<sys::$sys as PoolArray>::from_value(value(v)))
+            }
+        )*
+
+        fn register_pool_array_types(api: &mut sys::GodotApi) {
+            $(
+                api.$new_fn = $new_fn;
+                api.$as_fn = $as_fn;
+            )*
+        }
+    };
+}
+
+pool_array_types! {
+    godot_pool_byte_array: godot_variant_new_pool_byte_array, godot_variant_as_pool_byte_array;
+    godot_pool_int_array: godot_variant_new_pool_int_array, godot_variant_as_pool_int_array;
+    godot_pool_real_array: godot_variant_new_pool_real_array, godot_variant_as_pool_real_array;
+    godot_pool_string_array: godot_variant_new_pool_string_array, godot_variant_as_pool_string_array;
+    godot_pool_vector2_array: godot_variant_new_pool_vector2_array, godot_variant_as_pool_vector2_array;
+    godot_pool_vector3_array: godot_variant_new_pool_vector3_array, godot_variant_as_pool_vector3_array;
+    godot_pool_color_array: godot_variant_new_pool_color_array, godot_variant_as_pool_color_array;
+}
+
+pub(super) fn register(api: &mut sys::GodotApi) {
+    api.godot_variant_new_nil = variant_new_nil;
+    api.godot_variant_new_copy = variant_new_copy;
+    api.godot_variant_new_bool = variant_new_bool;
+    api.godot_variant_new_uint = variant_new_uint;
+    api.godot_variant_new_int = variant_new_int;
+    api.godot_variant_new_real = variant_new_real;
+    api.godot_variant_new_string = variant_new_string;
+    api.godot_variant_new_node_path = variant_new_node_path;
+    api.godot_variant_new_object = variant_new_object;
+    api.godot_variant_new_dictionary = variant_new_dictionary;
+    api.godot_variant_new_array = variant_new_array;
+    api.godot_variant_as_bool = variant_as_bool;
+    api.godot_variant_as_uint = variant_as_uint;
+    api.godot_variant_as_int = variant_as_int;
+    api.godot_variant_as_real = variant_as_real;
+    api.godot_variant_as_string = variant_as_string;
+    api.godot_variant_as_node_path = variant_as_node_path;
+    api.godot_variant_as_object = variant_as_object;
+    api.godot_variant_as_dictionary = variant_as_dictionary;
+    api.godot_variant_as_array = variant_as_array;
+    api.godot_variant_get_type = variant_get_type;
+    api.godot_variant_has_method = variant_has_method;
+    api.godot_variant_operator_equal = variant_operator_equal;
+    api.godot_variant_operator_less = variant_operator_less;
+    api.godot_variant_hash_compare = variant_hash_compare;
+    api.godot_variant_booleanize = variant_booleanize;
+    api.godot_variant_destroy = variant_destroy;
+
+    register_plain_types(api);
+    register_pool_array_types(api);
+}
+
+unsafe extern "C" fn variant_new_nil(dest: *mut sys::godot_variant) {
+    init(dest, Value::Nil);
+}
+
+unsafe extern "C" fn variant_new_copy(
+    dest: *mut sys::godot_variant,
+    src: *const sys::godot_variant,
+) {
+    init(dest, value(src).clone());
+}
+
+unsafe extern "C" fn variant_new_bool(dest: *mut sys::godot_variant, b: sys::godot_bool) {
+    init(dest, Value::Bool(b));
+}
+
+unsafe extern "C" fn variant_new_uint(dest: *mut sys::godot_variant, i: u64) {
+    init(dest, Value::Int(i as i64));
+}
+
+unsafe extern "C" fn variant_new_int(dest: *mut sys::godot_variant, i: i64) {
+    init(dest, Value::Int(i));
+}
+
+unsafe extern "C" fn variant_new_real(dest: *mut sys::godot_variant, r: f64) {
+    init(dest, Value::Real(r));
+}
+
+unsafe extern "C" fn variant_new_string(
+    dest: *mut sys::godot_variant,
+    s: *const sys::godot_string,
+) {
+    init(dest, Value::String(string::as_str(s).to_owned()));
+}
+
+unsafe extern "C" fn variant_new_node_path(
+    dest: *mut sys::godot_variant,
+    np: *const sys::godot_node_path,
+) {
+    init(
+        dest,
+        Value::NodePath(string::node_path_as_str(np).to_owned()),
+    );
+}
+
+unsafe extern "C" fn variant_new_object(
+    dest: *mut sys::godot_variant,
+    obj: *const sys::godot_object,
+) {
+    init(dest, Value::Object(ObjectRef::new(obj as *mut _)));
+}
+
+unsafe extern "C" fn variant_new_dictionary(
+    dest: *mut sys::godot_variant,
+    dict: *const sys::godot_dictionary,
+) {
+    init(dest, Value::Dictionary(dictionary::shared(dict).clone()));
+}
+
+unsafe extern "C" fn variant_new_array(
+    dest: *mut sys::godot_variant,
+    arr: *const sys::godot_array,
+) {
+    init(dest, Value::Array(array::shared(arr).clone()));
+}
+
+unsafe extern "C" fn variant_as_bool(v: *const sys::godot_variant) -> sys::godot_bool {
+    value(v).booleanize()
+}
+
+unsafe extern "C" fn variant_as_uint(v: *const sys::godot_variant) -> u64 {
+    value(v).to_i64() as u64
+}
+
+unsafe extern "C" fn variant_as_int(v: *const sys::godot_variant) -> i64 {
+    value(v).to_i64()
+}
+
+unsafe extern "C" fn variant_as_real(v: *const sys::godot_variant) -> f64 {
+    value(v).to_f64()
+}
+
+unsafe extern "C" fn variant_as_string(v: *const sys::godot_variant) -> sys::godot_string {
+    string::new_string(value(v).to_string())
+}
+
+unsafe extern "C" fn variant_as_node_path(v: *const sys::godot_variant) -> sys::godot_node_path {
+    match value(v) {
+        Value::NodePath(s) | Value::String(s) => new(s.clone()),
+        _ => new(String::new()),
+    }
+}
+
+unsafe extern "C" fn variant_as_object(v: *const sys::godot_variant) -> *mut sys::godot_object {
+    match value(v) {
+        Value::Object(obj) => obj.as_ptr(),
+        _ => std::ptr::null_mut(),
+    }
+}
+
+unsafe extern "C" fn variant_as_dictionary(v: *const sys::godot_variant) -> sys::godot_dictionary {
+    match value(v) {
+        Value::Dictionary(dict) => new(dict.clone()),
+        _ => new(SharedDictionary::default()),
+    }
+}
+
+unsafe extern "C" fn variant_as_array(v: *const sys::godot_variant) -> sys::godot_array {
+    let array = match value(v) {
+        Value::Array(array) => return new(array.clone()),
+        Value::PoolByteArray(a) => array::from_elements(a),
+        Value::PoolIntArray(a) => array::from_elements(a),
+        Value::PoolRealArray(a) => array::from_elements(a),
+        Value::PoolStringArray(a) => array::from_elements(a),
+        Value::PoolVector2Array(a) => array::from_elements(a),
+        Value::PoolVector3Array(a) => array::from_elements(a),
+        Value::PoolColorArray(a) => array::from_elements(a),
+        _ => SharedArray::default(),
+    };
+    new(array)
+}
+
+unsafe extern "C" fn variant_get_type(v: *const sys::godot_variant) -> sys::godot_variant_type {
+    value(v).variant_type()
+}
+
+unsafe extern "C" fn variant_has_method(
+    v: *const sys::godot_variant,
+    method: *const sys::godot_string,
+) -> sys::godot_bool {
+    match value(v) {
+        Value::Object(obj) => object::has_method(obj.as_ptr(), string::as_str(method)),
+        _ => false,
+    }
+}
+
+unsafe extern "C" fn variant_operator_equal(
+    v: *const sys::godot_variant,
+    other: *const sys::godot_variant,
+) -> sys::godot_bool {
+    value(v).equals(value(other))
+}
+
+unsafe extern "C" fn variant_operator_less(
+    v: *const sys::godot_variant,
+    other: *const sys::godot_variant,
+) -> sys::godot_bool {
+    value(v).less(value(other))
+}
+
+unsafe extern "C" fn variant_hash_compare(
+    v: *const sys::godot_variant,
+    other: *const sys::godot_variant,
+) -> sys::godot_bool {
+    value(v).hash_equals(value(other))
+}
+
+unsafe extern "C" fn variant_booleanize(v: *const sys::godot_variant) -> sys::godot_bool {
+    value(v).booleanize()
+}
+
+unsafe extern "C" fn variant_destroy(v: *mut sys::godot_variant) {
+    take(v);
+}
@@ -9,12 +9,14 @@ use crate::nativescript::MapMut;
 use crate::nativescript::UserData;
 use crate::object::{
     AssumeSafeLifetime, LifetimeConstraint, QueueFree, RawObject, Ref, RefImplBound, SafeAsRaw,
-    SafeDeref, TRef,
+    SafeDeref, TRef, WeakObject,
 };
 use crate::object::{GodotObject, Instanciable};
 use crate::private::{get_api, ReferenceCountedClassPlaceholder};
 use crate::ref_kind::{ManuallyManaged, RefCounted};
-use crate::thread_access::{NonUniqueThreadAccess, Shared, ThreadAccess, ThreadLocal, Unique};
+use crate::thread_access::{
+    AnyThread, MainThreadToken, NonUniqueThreadAccess, Shared, ThreadAccess, ThreadLocal, Unique,
+};
 
 use super::emplace;
 
@@ -191,6 +193,18 @@ pub struct RefInstance<'a, T: NativeClass, Access: ThreadAccess> {
     script: T::UserData,
 }
 
+/// A weak reference to a GodotObject with a rust NativeClass attached, identified by the
+/// instance ID of the object.
+///
+/// Upgrading a `WeakInstance` fails if the object has been freed, or if it no longer has a
+/// script of the class `T` attached. If the script was replaced by another instance of `T`,
+/// the new instance is returned.
+///
+/// See the type-level documentation on `WeakObject` for more information.
+pub struct WeakInstance<T: NativeClass> {
+    owner: WeakObject<T::Base>,
+}
+
 impl<T: NativeClass> Instance<T, Unique> {
     /// Creates a `T::Base` with the script `T` attached. Both `T::Base` and `T` must have zero
     /// argument constructors.
@@ -456,6 +470,7 @@ impl<T: NativeClass> Instance<T, Shared> {
     #[inline]
     pub unsafe fn assume_safe<'a, 'r>(&'r self) -> RefInstance<'a, T, Shared>
     where
+        T::Base: AnyThread,
         AssumeSafeLifetime<'a, 'r>: LifetimeConstraint<<T::Base as GodotObject>::RefKind>,
     {
         RefInstance {
@@ -463,6 +478,27 @@ impl<T: NativeClass> Instance<T, Shared> {
             script: self.script.clone(),
         }
     }
+
+    /// Assume that `self` is safe to use on the main thread. Unlike `assume_safe`, this is
+    /// available for all base classes, including `MainThreadOnly` ones like nodes.
+    ///
+    /// # Safety
+    ///
+    /// It's safe to call `assume_safe_on_main_thread` only if the constraints of
+    /// `Ref::assume_safe_on_main_thread` are satisfied for the base object.
+    #[inline]
+    pub unsafe fn assume_safe_on_main_thread<'a, 'r>(
+        &'r self,
+        token: MainThreadToken,
+    ) -> RefInstance<'a, T, Shared>
+    where
+        AssumeSafeLifetime<'a, 'r>: LifetimeConstraint<<T::Base as GodotObject>::RefKind>,
+    {
+        RefInstance {
+            owner: self.owner.assume_safe_on_main_thread(token),
+            script: self.script.clone(),
+        }
+    }
 }
 
 impl<T: NativeClass> Instance<T, Shared>
@@ -608,6 +644,16 @@ impl<'a, T: NativeClass, Access: ThreadAccess> RefInstance<'a, T, Access> {
     }
 }
 
+impl<'a, T: NativeClass, Access: ThreadAccess> RefInstance<'a, T, Access> {
+    /// Creates a `WeakInstance` referring to this instance.
+    #[inline]
+    pub fn downgrade(&self) -> WeakInstance<T> {
+        WeakInstance {
+            owner: self.owner.downgrade(),
+        }
+    }
+}
+
 impl<'a, T: NativeClass, Access: NonUniqueThreadAccess> RefInstance<'a, T, Access> {
     /// Persists this into a persistent `Instance` with the same thread access, without cloning
     /// the userdata wrapper.
@@ -650,6 +696,84 @@ where
     }
 }
 
+impl<T: NativeClass> WeakInstance<T> {
+    /// Returns a weak reference to the base object.
+    #[inline]
+    pub fn base(&self) -> WeakObject<T::Base> {
+        self.owner
+    }
+
+    /// Returns the instance ID of the base object.
+    #[inline]
+    pub fn instance_id(&self) -> i64 {
+        self.owner.instance_id()
+    }
+
+    /// Returns a reference to the instance if the base object is still alive and still has a
+    /// script of the class `T` attached, or `None` otherwise.
+    ///
+    /// # Safety
+    ///
+    /// See `WeakObject::upgrade`.
+    #[inline]
+    pub unsafe fn upgrade<'a>(&self) -> Option<RefInstance<'a, T, Shared>>
+    where
+        T::Base: AnyThread,
+    {
+        RefInstance::try_from_base(self.owner.upgrade()?)
+    }
+
+    /// Returns a reference to the instance if the base object is still alive and still has a
+    /// script of the class `T` attached, or `None` otherwise. Unlike `upgrade`, this is
+    /// available for all base classes, including `MainThreadOnly` ones like nodes.
+    ///
+    /// # Safety
+    ///
+    /// See `WeakObject::upgrade_on_main_thread`.
+    #[inline]
+    pub unsafe fn upgrade_on_main_thread<'a>(
+        &self,
+        token: MainThreadToken,
+    ) -> Option<RefInstance<'a, T, Shared>> {
+        RefInstance::try_from_base(self.owner.upgrade_on_main_thread(token)?)
+    }
+}
+
+impl<T: NativeClass> Copy for WeakInstance<T> {}
+impl<T: NativeClass> Clone for WeakInstance<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: NativeClass> Eq for WeakInstance<T> {}
+impl<T: NativeClass> PartialEq for WeakInstance<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.owner == other.owner
+    }
+}
+
+impl<T: NativeClass> std::hash::Hash for WeakInstance<T> {
+    #[inline]
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.owner.hash(state)
+    }
+}
+
+impl<T: NativeClass> std::fmt::Debug for WeakInstance<T> {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "WeakInstance<{}>({})",
+            T::class_name(),
+            self.instance_id()
+        )
+    }
+}
+
 impl<T, Access: ThreadAccess> Clone for Instance<T, Access>
 where
     T: NativeClass,
@@ -716,6 +840,11 @@ fn try_get_user_data_ptr<T: NativeClass>(owner: &RawObject<T::Base>) -> Option<*
     unsafe {
         let api = get_api();
 
+        // Without type tags, there is no way to check the type of the script instance.
+        if !api.has_nativescript_1_1() {
+            return None;
+        }
+
         let owner_ptr = owner.sys().as_ptr();
 
         let type_tag = (api.godot_nativescript_get_type_tag)(owner_ptr);
@@ -9,7 +9,8 @@
 //! - [`godot_gdnative_terminate`](macro.godot_gdnative_terminate.html),
 //!
 //! All three must be present. To quickly define all three endpoints using the default names,
-//! use [`godot_init`](macro.godot_init.html).
+//! use [`godot_init`](macro.godot_init.html), which also declares the endpoint to call static
+//! methods, [`godot_nativescript_call_static`](macro.godot_nativescript_call_static.html).
 //!
 //! ## Registering script classes
 //!
@@ -33,21 +34,28 @@
 
 use crate::*;
 
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
 use std::ffi::CString;
 use std::marker::PhantomData;
 use std::ptr;
 
 use crate::core_types::{GodotString, Variant};
+use crate::nativescript::reload::{HotReload, ReloadHooks};
 use crate::nativescript::NativeClass;
 use crate::nativescript::NativeClassMethods;
-use crate::nativescript::UserData;
+use crate::nativescript::{Map, UserData};
 use crate::private::get_api;
+use crate::thread_access::Shared;
 
 use super::emplace;
 
 pub mod property;
+pub(crate) mod static_methods;
 
-pub use self::property::{Export, ExportInfo, PropertyBuilder, Usage as PropertyUsage};
+pub use self::property::{
+    DynamicProperties, Export, ExportInfo, PropertyBuilder, PropertyInfo, Usage as PropertyUsage,
+};
 
 /// A handle that can register new classes to the engine during initialization.
 ///
@@ -72,7 +80,7 @@ impl InitHandle {
     where
         C: NativeClassMethods,
     {
-        self.add_maybe_tool_class::<C>(false)
+        self.add_maybe_tool_class::<C, NoHooks>(false)
     }
 
     /// Registers a new tool class to the engine.
@@ -81,20 +89,43 @@ impl InitHandle {
     where
         C: NativeClassMethods,
     {
-        self.add_maybe_tool_class::<C>(true)
+        self.add_maybe_tool_class::<C, NoHooks>(true)
     }
 
+    /// Registers a new class to the engine, whose instances keep their state when the library
+    /// is reloaded. See the [`reload`](../reload/index.html) module for details.
     #[inline]
-    fn add_maybe_tool_class<C>(self, is_tool: bool)
+    pub fn add_reloadable_class<C>(self)
+    where
+        C: NativeClassMethods + HotReload,
+        C::UserData: Map,
+    {
+        self.add_maybe_tool_class::<C, ReloadHooks>(false)
+    }
+
+    /// Registers a new tool class to the engine, whose instances keep their state when the
+    /// library is reloaded. See the [`reload`](../reload/index.html) module for details.
+    #[inline]
+    pub fn add_reloadable_tool_class<C>(self)
+    where
+        C: NativeClassMethods + HotReload,
+        C::UserData: Map,
+    {
+        self.add_maybe_tool_class::<C, ReloadHooks>(true)
+    }
+
+    #[inline]
+    fn add_maybe_tool_class<C, H>(self, is_tool: bool)
     where
         C: NativeClassMethods,
+        H: InstanceHooks<C>,
     {
         unsafe {
             let class_name = CString::new(C::class_name()).unwrap();
             let base_name = CString::new(C::Base::class_name()).unwrap();
 
             let create = {
-                unsafe extern "C" fn constructor<C: NativeClass>(
+                unsafe extern "C" fn constructor<C: NativeClass, H: InstanceHooks<C>>(
                     this: *mut sys::godot_object,
                     _method_data: *mut libc::c_void,
                 ) -> *mut libc::c_void {
@@ -125,8 +156,10 @@ impl InitHandle {
                     };
 
                     let val = match panic::catch_unwind(AssertUnwindSafe(|| {
+                        let owner = TRef::new(C::Base::cast_ref(owner));
                         emplace::take()
-                            .unwrap_or_else(|| C::init(TRef::new(C::Base::cast_ref(owner))))
+                            .or_else(|| H::construct(owner))
+                            .unwrap_or_else(|| C::init(owner))
                     })) {
                         Ok(val) => val,
                         Err(_) => {
@@ -139,19 +172,21 @@ impl InitHandle {
                     };
 
                     let wrapper = C::UserData::new(val);
-                    C::UserData::into_user_data(wrapper) as *mut _
+                    let user_data = C::UserData::into_user_data(wrapper);
+                    H::created(this.as_ptr(), user_data);
+                    user_data as *mut _
                 }
 
                 sys::godot_instance_create_func {
-                    create_func: Some(constructor::<C>),
+                    create_func: Some(constructor::<C, H>),
                     method_data: ptr::null_mut(),
                     free_func: None,
                 }
             };
 
             let destroy = {
-                unsafe extern "C" fn destructor<C: NativeClass>(
-                    _this: *mut sys::godot_object,
+                unsafe extern "C" fn destructor<C: NativeClass, H: InstanceHooks<C>>(
+                    this: *mut sys::godot_object,
                     _method_data: *mut libc::c_void,
                     user_data: *mut libc::c_void,
                 ) {
@@ -163,12 +198,14 @@ impl InitHandle {
                         return;
                     }
 
-                    let wrapper = C::UserData::consume_user_data_unchecked(user_data);
-                    drop(wrapper)
+                    if H::destroyed(this, user_data) {
+                        let wrapper = C::UserData::consume_user_data_unchecked(user_data);
+                        drop(wrapper)
+                    }
                 }
 
                 sys::godot_instance_destroy_func {
-                    destroy_func: Some(destructor::<C>),
+                    destroy_func: Some(destructor::<C, H>),
                     method_data: ptr::null_mut(),
                     free_func: None,
                 }
@@ -192,15 +229,24 @@ impl InitHandle {
                 );
             }
 
-            (get_api().godot_nativescript_set_type_tag)(
-                self.handle as *mut _,
-                class_name.as_ptr() as *const _,
-                crate::nativescript::type_tag::create::<C>(),
-            );
+            H::registered();
+
+            // Type tags are only available since NativeScript 1.1. Without them, instances
+            // can't be recovered from their base objects.
+            if get_api().has_nativescript_1_1() {
+                (get_api().godot_nativescript_set_type_tag)(
+                    self.handle as *mut _,
+                    class_name.as_ptr() as *const _,
+                    crate::nativescript::type_tag::create::<C>(),
+                );
+            }
 
             let builder = ClassBuilder {
                 init_handle: self.handle,
                 class_name,
+                methods: RefCell::new(HashSet::new()),
+                group_open: Cell::new(false),
+                group_ends: Cell::new(0),
                 _marker: PhantomData,
             };
 
@@ -212,6 +258,53 @@ impl InitHandle {
     }
 }
 
+/// Hooks into the creation and destruction of script instances, used to implement
+/// class-specific behavior in the shared constructor and destructor.
+pub(crate) trait InstanceHooks<C: NativeClass> {
+    /// Constructs the instance for `owner`, or returns `None` to use `NativeClass::init`.
+    /// Instances emplaced from Rust are never constructed by this.
+    fn construct(owner: TRef<'_, C::Base, Shared>) -> Option<C>;
+
+    /// Called after an instance is created, with its user data.
+    ///
+    /// # Safety
+    ///
+    /// `owner` and `user_data` must belong to a live instance of `C`.
+    unsafe fn created(owner: *mut sys::godot_object, user_data: *const libc::c_void);
+
+    /// Called after the class is registered.
+    fn registered();
+
+    /// Called before an instance is destroyed, with its user data. Returns `false` if the
+    /// user data was already dropped, in which case it's left alone.
+    ///
+    /// # Safety
+    ///
+    /// `owner` and `user_data` must belong to an instance of `C`.
+    unsafe fn destroyed(owner: *mut sys::godot_object, user_data: *const libc::c_void) -> bool;
+}
+
+/// Hooks for classes without any special behavior.
+pub(crate) struct NoHooks;
+
+impl<C: NativeClass> InstanceHooks<C> for NoHooks {
+    #[inline]
+    fn construct(_owner: TRef<'_, C::Base, Shared>) -> Option<C> {
+        None
+    }
+
+    #[inline]
+    unsafe fn created(_owner: *mut sys::godot_object, _user_data: *const libc::c_void) {}
+
+    #[inline]
+    fn registered() {}
+
+    #[inline]
+    unsafe fn destroyed(_owner: *mut sys::godot_object, _user_data: *const libc::c_void) -> bool {
+        true
+    }
+}
+
 pub type ScriptMethodFn = unsafe extern "C" fn(
     *mut sys::godot_object,
     *mut libc::c_void,
@@ -247,12 +340,28 @@ pub struct ScriptMethod<'l> {
 pub struct ClassBuilder<C> {
     init_handle: *mut libc::c_void,
     class_name: CString,
+    methods: RefCell<HashSet<String>>,
+    /// Whether a property group was added and not ended yet.
+    group_open: Cell<bool>,
+    /// Number of groups ended with `end_property_group`, used to name the end markers.
+    group_ends: Cell<u32>,
     _marker: PhantomData<C>,
 }
 
 impl<C: NativeClass> ClassBuilder<C> {
     #[inline]
     pub fn add_method_advanced(&self, method: ScriptMethod) {
+        // The engine silently replaces methods that are registered again, e.g. `_get` exported
+        // by a class that also implements `DynamicProperties`.
+        if !self.methods.borrow_mut().insert(method.name.to_string()) {
+            godot_error!(
+                "gdnative-core: method {}::{} is registered more than once, ignoring the later registration",
+                C::class_name(),
+                method.name,
+            );
+            return;
+        }
+
         let method_name = CString::new(method.name).unwrap();
 
         let rpc = match method.attributes.rpc_mode {
@@ -300,6 +409,26 @@ impl<C: NativeClass> ClassBuilder<C> {
         self.add_method_with_rpc_mode(name, method, RpcMode::Disabled);
     }
 
+    /// Registers a static method, wrapped with `godot_wrap_static_method`. Static methods can
+    /// be called on instances of the class like other methods, and without an instance through
+    /// the function declared by [`godot_nativescript_call_static`](../macro.godot_nativescript_call_static.html),
+    /// which takes the names of the class and method followed by the arguments:
+    ///
+    /// ```ignore
+    /// var gdn = GDNative.new()
+    /// gdn.library = load("res://my_library.gdnlib")
+    /// gdn.initialize()
+    /// var value = gdn.call_native("standard_varcall", "godot_nativescript_call_static", ["MyType", "lerp", 0.0, 10.0, 0.5])
+    /// ```
+    ///
+    /// `method` must not access the base object or the user data, since they're null when
+    /// the method is called without an instance.
+    #[inline]
+    pub fn add_static_method(&self, name: &str, method: ScriptMethodFn) {
+        self.add_method(name, method);
+        static_methods::register(C::class_name(), name, method);
+    }
+
     /// Returns a `PropertyBuilder` which can be used to add a property to the class being
     /// registered.
     ///
@@ -324,6 +453,57 @@ impl<C: NativeClass> ClassBuilder<C> {
         PropertyBuilder::new(self, name)
     }
 
+    /// Adds a property group to the inspector. Properties registered after the group are
+    /// shown under it, until the next group or category, or `end_property_group`. If `prefix`
+    /// isn't empty, the group also ends at the first property whose name doesn't start with
+    /// `prefix`, and the prefix is hidden from the names of the properties in the group.
+    ///
+    /// Groups are registered like properties, so their names must be unique within the class.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// builder.add_property_group("Movement", "movement_");
+    ///
+    /// builder
+    ///     .add_property("movement_speed")
+    ///     .with_getter(MyType::get_speed)
+    ///     .with_setter(MyType::set_speed)
+    ///     .done();
+    /// ```
+    #[inline]
+    pub fn add_property_group(&self, name: &str, prefix: &str) {
+        property::register_separator(self, name, prefix, PropertyUsage::GROUP);
+        self.group_open.set(true);
+    }
+
+    /// Ends the current property group, so that properties registered afterwards are shown
+    /// without a group again. Does nothing if no group is open.
+    ///
+    /// The inspector ends a group at the first property that doesn't start with the group's
+    /// prefix. This registers an empty group with a prefix that can't start a property name,
+    /// which ends the current group without showing up in the inspector itself.
+    #[inline]
+    pub fn end_property_group(&self) {
+        if !self.group_open.replace(false) {
+            return;
+        }
+
+        let index = self.group_ends.get();
+        self.group_ends.set(index + 1);
+
+        let marker = format!("/end_group_{}", index);
+        property::register_separator(self, &marker, &marker, PropertyUsage::GROUP);
+    }
+
+    /// Adds a property category to the inspector. Properties registered after the category
+    /// are shown under it, until the next category. This also ends the current group.
+    #[inline]
+    pub fn add_property_category(&self, name: &str) {
+        property::register_separator(self, name, "", PropertyUsage::CATEGORY);
+        self.group_open.set(false);
+    }
+
     #[inline]
     pub fn add_signal(&self, signal: Signal) {
         unsafe {
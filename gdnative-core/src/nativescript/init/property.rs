@@ -10,8 +10,10 @@ use crate::thread_access::Shared;
 use super::ClassBuilder;
 
 mod accessor;
+pub mod dynamic;
 pub mod hint;
 
+pub use dynamic::{DynamicProperties, PropertyInfo, PropertyInfoBuilder};
 pub use hint::*;
 
 use accessor::{Getter, InvalidGetter, InvalidSetter, RawGetter, RawSetter, Setter};
@@ -58,6 +60,73 @@ impl ExportInfo {
     }
 }
 
+/// Registers an entry that is only used to separate other properties in the inspector.
+/// `usage` should be either `Usage::GROUP` or `Usage::CATEGORY`.
+///
+/// The engine has no notion of usage-only entries in scripts, so the entry is still added as
+/// a property, but its accessors never touch the instance: writes are ignored and reads
+/// always return nil. It's also never stored or shown as an editable property, since `usage`
+/// doesn't include `Usage::STORAGE` or `Usage::EDITOR`.
+pub(super) fn register_separator<C: NativeClass>(
+    class_builder: &ClassBuilder<C>,
+    name: &str,
+    hint_string: &str,
+    usage: Usage,
+) {
+    debug_assert!(!usage.intersects(Usage::STORAGE | Usage::EDITOR));
+
+    let hint_string = GodotString::from_str(hint_string);
+    let default = Variant::new();
+
+    let mut attr = sys::godot_property_attributes {
+        rset_type: sys::godot_method_rpc_mode_GODOT_METHOD_RPC_MODE_DISABLED,
+        type_: VariantType::Nil as sys::godot_int,
+        hint: sys::godot_property_hint_GODOT_PROPERTY_HINT_NONE,
+        hint_string: hint_string.to_sys(),
+        usage: usage.to_sys(),
+        default_value: default.to_sys(),
+    };
+
+    let path = ::std::ffi::CString::new(name).unwrap();
+
+    extern "C" fn separator_setter(
+        _this: *mut sys::godot_object,
+        _method_data: *mut libc::c_void,
+        _user_data: *mut libc::c_void,
+        _val: *mut sys::godot_variant,
+    ) {
+    }
+
+    extern "C" fn separator_getter(
+        _this: *mut sys::godot_object,
+        _method_data: *mut libc::c_void,
+        _user_data: *mut libc::c_void,
+    ) -> sys::godot_variant {
+        Variant::new().forget()
+    }
+
+    let set = sys::godot_property_set_func {
+        set_func: Some(separator_setter),
+        ..Default::default()
+    };
+
+    let get = sys::godot_property_get_func {
+        get_func: Some(separator_getter),
+        ..Default::default()
+    };
+
+    unsafe {
+        (get_api().godot_nativescript_register_property)(
+            class_builder.init_handle,
+            class_builder.class_name.as_ptr(),
+            path.as_ptr() as *const _,
+            &mut attr,
+            set,
+            get,
+        );
+    }
+}
+
 /// Builder type used to register a property on a `NativeClass`.
 #[derive(Debug)]
 #[must_use]
@@ -404,17 +473,66 @@ mod impl_export {
     impl_export_for_core_type_without_hint!(Aabb);
     impl_export_for_core_type_without_hint!(Basis);
     impl_export_for_core_type_without_hint!(Transform);
-    impl_export_for_core_type_without_hint!(NodePath);
     impl_export_for_core_type_without_hint!(Rid);
-    impl_export_for_core_type_without_hint!(Dictionary);
-    impl_export_for_core_type_without_hint!(VariantArray);
-    impl_export_for_core_type_without_hint!(ByteArray);
-    impl_export_for_core_type_without_hint!(Int32Array);
-    impl_export_for_core_type_without_hint!(Float32Array);
-    impl_export_for_core_type_without_hint!(StringArray);
-    impl_export_for_core_type_without_hint!(Vector2Array);
-    impl_export_for_core_type_without_hint!(Vector3Array);
-    impl_export_for_core_type_without_hint!(ColorArray);
+
+    impl Export for Dictionary {
+        type Hint = hint::DictionaryHint;
+        #[inline]
+        fn export_info(hint: Option<Self::Hint>) -> ExportInfo {
+            hint.unwrap_or_default().export_info()
+        }
+    }
+
+    impl Export for NodePath {
+        type Hint = hint::NodePathHint;
+        #[inline]
+        fn export_info(hint: Option<Self::Hint>) -> ExportInfo {
+            hint.map_or_else(
+                || ExportInfo::new(VariantType::NodePath),
+                Self::Hint::export_info,
+            )
+        }
+    }
+
+    impl Export for VariantArray<Shared> {
+        type Hint = hint::ArrayHint;
+        #[inline]
+        fn export_info(hint: Option<Self::Hint>) -> ExportInfo {
+            hint.unwrap_or_default()
+                .export_info(VariantType::VariantArray)
+        }
+    }
+
+    impl<T> Export for Vec<T>
+    where
+        T: Export,
+    {
+        type Hint = T::Hint;
+        #[inline]
+        fn export_info(hint: Option<Self::Hint>) -> ExportInfo {
+            hint::ArrayHint::with_element::<T>(hint).export_info(VariantType::VariantArray)
+        }
+    }
+
+    macro_rules! impl_export_for_typed_array {
+        ($ty:ident: $element:ty) => {
+            impl Export for $ty {
+                type Hint = <$element as Export>::Hint;
+                #[inline]
+                fn export_info(hint: Option<Self::Hint>) -> ExportInfo {
+                    hint::ArrayHint::with_element::<$element>(hint).export_info(VariantType::$ty)
+                }
+            }
+        };
+    }
+
+    impl_export_for_typed_array!(ByteArray: u8);
+    impl_export_for_typed_array!(Int32Array: i32);
+    impl_export_for_typed_array!(Float32Array: f32);
+    impl_export_for_typed_array!(StringArray: GodotString);
+    impl_export_for_typed_array!(Vector2Array: Vector2);
+    impl_export_for_typed_array!(Vector3Array: Vector3);
+    impl_export_for_typed_array!(ColorArray: Color);
 
     impl Export for Color {
         type Hint = hint::ColorHint;
@@ -431,10 +549,10 @@ mod impl_export {
     where
         T: GodotObject,
     {
-        type Hint = ();
+        type Hint = hint::ObjectHint;
         #[inline]
-        fn export_info(_hint: Option<Self::Hint>) -> ExportInfo {
-            ExportInfo::resource_type::<T>()
+        fn export_info(hint: Option<Self::Hint>) -> ExportInfo {
+            hint.map_or_else(ExportInfo::resource_type::<T>, Self::Hint::export_info)
         }
     }
 
@@ -258,7 +258,10 @@ where
             });
 
             result.unwrap_or_else(|_| {
-                godot_error!("gdnative-core: property setter panicked (check stderr for output)");
+                godot_error!(
+                    "gdnative-core: property setter of {} panicked",
+                    C::class_name()
+                );
             })
         }
         set.set_func = Some(invoke::<SelfArg, C, F, T>);
@@ -330,7 +333,10 @@ where
             });
 
             result.unwrap_or_else(|_| {
-                godot_error!("gdnative-core: property getter panicked (check stderr for output)");
+                godot_error!(
+                    "gdnative-core: property getter of {} panicked",
+                    C::class_name()
+                );
                 Variant::new().forget()
             })
         }
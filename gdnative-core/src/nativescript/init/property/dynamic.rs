@@ -0,0 +1,400 @@
+//! Properties whose set is only known at runtime.
+
+use std::ptr::NonNull;
+
+use crate::core_types::*;
+use crate::nativescript::init::ClassBuilder;
+use crate::nativescript::user_data::UserData;
+use crate::nativescript::{Map, MapMut, NativeClass};
+use crate::object::{GodotObject, RawObject};
+use crate::*;
+
+use super::{Export, ExportInfo, Usage};
+
+/// Trait for `NativeClass`es that expose a dynamic set of properties, in addition to the
+/// ones registered with `ClassBuilder::add_property`.
+///
+/// The methods of this trait are called by the engine through the virtual methods `_get`,
+/// `_set` and `_get_property_list`. They're only consulted for properties that aren't
+/// registered statically.
+///
+/// When using the `NativeClass` derive macro, implementations of this trait are registered
+/// automatically. Otherwise, call `ClassBuilder::add_dynamic_properties` in
+/// `NativeClass::register_properties`. The trait can only be implemented for types whose
+/// user data wrapper implements both `Map` and `MapMut`, like `MutexData`, `RwLockData` and
+/// `LocalCellData`.
+///
+/// The class can't export its own `_get`, `_set` or `_get_property_list` methods, since these
+/// are registered for it. Registering them twice is reported as an error, and the second
+/// registration is ignored.
+///
+/// # Examples
+///
+/// ```ignore
+/// impl DynamicProperties for Entity {
+///     fn get_property(&self, _owner: TRef<Node>, name: &str) -> Option<Variant> {
+///         self.values.get(name).cloned()
+///     }
+///
+///     fn set_property(&mut self, _owner: TRef<Node>, name: &str, value: &Variant) -> bool {
+///         match self.values.get_mut(name) {
+///             Some(slot) => {
+///                 *slot = value.clone();
+///                 true
+///             }
+///             None => false,
+///         }
+///     }
+///
+///     fn property_list(&self, _owner: TRef<Node>) -> Vec<PropertyInfo> {
+///         self.values
+///             .keys()
+///             .map(|name| PropertyInfo::builder::<f64>(name).done())
+///             .collect()
+///     }
+/// }
+/// ```
+pub trait DynamicProperties: NativeClass
+where
+    Self::UserData: Map + MapMut,
+{
+    /// Returns the value of the property `name`, or `None` if the property isn't handled
+    /// by this type.
+    fn get_property(&self, owner: TRef<'_, Self::Base>, name: &str) -> Option<Variant>;
+
+    /// Sets the value of the property `name`. Returns `true` if the property is handled
+    /// by this type.
+    fn set_property(&mut self, owner: TRef<'_, Self::Base>, name: &str, value: &Variant) -> bool;
+
+    /// Returns descriptions of the properties currently exposed by this type, for use in
+    /// the editor and for serialization.
+    fn property_list(&self, owner: TRef<'_, Self::Base>) -> Vec<PropertyInfo>;
+}
+
+/// Description of a dynamic property, as returned by `DynamicProperties::property_list`.
+#[derive(Debug)]
+pub struct PropertyInfo {
+    name: GodotString,
+    export_info: ExportInfo,
+    usage: Usage,
+}
+
+impl PropertyInfo {
+    /// Creates a `PropertyInfo` from an untyped `ExportInfo`.
+    #[inline]
+    pub fn new(name: impl Into<GodotString>, export_info: ExportInfo, usage: Usage) -> Self {
+        PropertyInfo {
+            name: name.into(),
+            export_info,
+            usage,
+        }
+    }
+
+    /// Returns a builder for a property of the exported type `T`.
+    #[inline]
+    pub fn builder<T: Export>(name: impl Into<GodotString>) -> PropertyInfoBuilder<T> {
+        PropertyInfoBuilder {
+            name: name.into(),
+            hint: None,
+            usage: Usage::DEFAULT,
+        }
+    }
+
+    /// Returns the name of the property.
+    #[inline]
+    pub fn name(&self) -> &GodotString {
+        &self.name
+    }
+
+    /// Returns the usage flags of the property.
+    #[inline]
+    pub fn usage(&self) -> Usage {
+        self.usage
+    }
+
+    /// Converts the description into the dictionary format expected by the engine.
+    #[inline]
+    pub fn to_dictionary(&self) -> Dictionary {
+        let dict = Dictionary::new();
+        dict.insert("name", self.name.new_ref());
+        dict.insert("type", self.export_info.variant_type as i64);
+        dict.insert("hint", self.export_info.hint_kind as i64);
+        dict.insert("hint_string", self.export_info.hint_string.new_ref());
+        dict.insert("usage", self.usage.bits() as i64);
+        dict.into_shared()
+    }
+}
+
+/// Builder for `PropertyInfo`s of a specific exported type.
+#[derive(Debug)]
+#[must_use]
+pub struct PropertyInfoBuilder<T: Export> {
+    name: GodotString,
+    hint: Option<T::Hint>,
+    usage: Usage,
+}
+
+impl<T: Export> PropertyInfoBuilder<T> {
+    /// Sets an editor hint.
+    #[inline]
+    pub fn with_hint(mut self, hint: T::Hint) -> Self {
+        self.hint = Some(hint);
+        self
+    }
+
+    /// Sets a property usage.
+    #[inline]
+    pub fn with_usage(mut self, usage: Usage) -> Self {
+        self.usage = usage;
+        self
+    }
+
+    /// Creates the `PropertyInfo`.
+    #[inline]
+    pub fn done(self) -> PropertyInfo {
+        PropertyInfo {
+            name: self.name,
+            export_info: T::export_info(self.hint),
+            usage: self.usage,
+        }
+    }
+}
+
+impl<C> ClassBuilder<C>
+where
+    C: DynamicProperties,
+    C::UserData: Map + MapMut,
+{
+    /// Registers the `_get`, `_set` and `_get_property_list` methods of the class to forward
+    /// to its `DynamicProperties` implementation.
+    #[inline]
+    pub fn add_dynamic_properties(&self) {
+        self.add_method("_get", get_property::<C>);
+        self.add_method("_set", set_property::<C>);
+        self.add_method("_get_property_list", get_property_list::<C>);
+    }
+}
+
+/// Calls `op` with the user data and owner of the instance, and the arguments of the method.
+/// Any errors are reported as they happen, in which case `None` is returned.
+unsafe fn with_instance<C, F, R>(
+    method_name: &str,
+    this: *mut sys::godot_object,
+    user_data: *mut libc::c_void,
+    num_args: libc::c_int,
+    args: *mut *mut sys::godot_variant,
+    expected_args: usize,
+    op: F,
+) -> Option<R>
+where
+    C: NativeClass,
+    F: FnOnce(&C::UserData, TRef<'_, C::Base>, &[&Variant]) -> R,
+{
+    if user_data.is_null() {
+        godot_error!(
+            "gdnative-core: user data pointer for {} is null (did the constructor fail?)",
+            C::class_name(),
+        );
+        return None;
+    }
+
+    let this = match NonNull::new(this) {
+        Some(this) => this,
+        None => {
+            godot_error!(
+                "gdnative-core: owner pointer for {} is null",
+                C::class_name(),
+            );
+            return None;
+        }
+    };
+
+    if num_args as usize != expected_args {
+        godot_error!(
+            "Incorrect number of parameters for {}: expected {} but got {}",
+            method_name,
+            expected_args,
+            num_args,
+        );
+        return None;
+    }
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let user_data = C::UserData::clone_from_user_data_unchecked(user_data as *const _);
+        let owner = TRef::new(C::Base::cast_ref(RawObject::from_sys_ref_unchecked(this)));
+        let args = (0..expected_args)
+            .map(|n| Variant::cast_ref(*args.add(n)))
+            .collect::<Vec<_>>();
+
+        op(&user_data, owner, &args)
+    }));
+
+    match result {
+        Ok(ret) => Some(ret),
+        Err(_) => {
+            godot_error!(
+                "gdnative-core: {} of {} panicked",
+                method_name,
+                C::class_name(),
+            );
+            None
+        }
+    }
+}
+
+fn property_name(variant: &Variant) -> Option<String> {
+    let name = String::from_variant(variant);
+    if let Err(err) = &name {
+        godot_error!("Cannot convert property name to String: {}", err);
+    }
+    name.ok()
+}
+
+unsafe extern "C" fn get_property<C>(
+    this: *mut sys::godot_object,
+    _method_data: *mut libc::c_void,
+    user_data: *mut libc::c_void,
+    num_args: libc::c_int,
+    args: *mut *mut sys::godot_variant,
+) -> sys::godot_variant
+where
+    C: DynamicProperties,
+    C::UserData: Map + MapMut,
+{
+    with_instance::<C, _, _>(
+        "_get",
+        this,
+        user_data,
+        num_args,
+        args,
+        1,
+        |ud, owner, args| {
+            let name = property_name(args[0])?;
+            ud.map(|rust_ty| rust_ty.get_property(owner, &name))
+                .unwrap_or_else(|err| {
+                    godot_error!("gdnative-core: cannot call _get: {:?}", err);
+                    None
+                })
+        },
+    )
+    .flatten()
+    .unwrap_or_else(Variant::new)
+    .forget()
+}
+
+unsafe extern "C" fn set_property<C>(
+    this: *mut sys::godot_object,
+    _method_data: *mut libc::c_void,
+    user_data: *mut libc::c_void,
+    num_args: libc::c_int,
+    args: *mut *mut sys::godot_variant,
+) -> sys::godot_variant
+where
+    C: DynamicProperties,
+    C::UserData: Map + MapMut,
+{
+    with_instance::<C, _, _>(
+        "_set",
+        this,
+        user_data,
+        num_args,
+        args,
+        2,
+        |ud, owner, args| {
+            let name = match property_name(args[0]) {
+                Some(name) => name,
+                None => return false,
+            };
+            ud.map_mut(|rust_ty| rust_ty.set_property(owner, &name, args[1]))
+                .unwrap_or_else(|err| {
+                    godot_error!("gdnative-core: cannot call _set: {:?}", err);
+                    false
+                })
+        },
+    )
+    .unwrap_or(false)
+    .to_variant()
+    .forget()
+}
+
+unsafe extern "C" fn get_property_list<C>(
+    this: *mut sys::godot_object,
+    _method_data: *mut libc::c_void,
+    user_data: *mut libc::c_void,
+    num_args: libc::c_int,
+    args: *mut *mut sys::godot_variant,
+) -> sys::godot_variant
+where
+    C: DynamicProperties,
+    C::UserData: Map + MapMut,
+{
+    let list = with_instance::<C, _, _>(
+        "_get_property_list",
+        this,
+        user_data,
+        num_args,
+        args,
+        0,
+        |ud, owner, _args| {
+            ud.map(|rust_ty| rust_ty.property_list(owner))
+                .unwrap_or_else(|err| {
+                    godot_error!("gdnative-core: cannot call _get_property_list: {:?}", err);
+                    Vec::new()
+                })
+        },
+    )
+    .unwrap_or_default();
+
+    let array = VariantArray::new();
+    for info in &list {
+        array.push(info.to_dictionary());
+    }
+
+    array.into_shared().to_variant().forget()
+}
+
+/// Probe type used by the `NativeClass` derive macro to register `DynamicProperties`
+/// implementations, if any. Since `DynamicProperties` requires `Map` and `MapMut`, the
+/// implementation is always registered if it exists.
+#[doc(hidden)]
+pub struct DynamicPropertiesProbe<C>(std::marker::PhantomData<fn() -> C>);
+
+impl<C> DynamicPropertiesProbe<C> {
+    #[inline]
+    pub fn new() -> Self {
+        DynamicPropertiesProbe(std::marker::PhantomData)
+    }
+}
+
+impl<C> Default for DynamicPropertiesProbe<C> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Registers dynamic properties for types implementing `DynamicProperties`.
+#[doc(hidden)]
+pub trait RegisterDynamicProperties<C: NativeClass> {
+    fn register_dynamic_properties(&self, builder: &ClassBuilder<C>);
+}
+
+impl<C> RegisterDynamicProperties<C> for DynamicPropertiesProbe<C>
+where
+    C: DynamicProperties,
+    C::UserData: Map + MapMut,
+{
+    #[inline]
+    fn register_dynamic_properties(&self, builder: &ClassBuilder<C>) {
+        builder.add_dynamic_properties();
+    }
+}
+
+/// Does nothing for types that don't implement `DynamicProperties`.
+#[doc(hidden)]
+pub trait SkipDynamicProperties<C: NativeClass> {
+    #[inline]
+    fn register_dynamic_properties(&self, _builder: &ClassBuilder<C>) {}
+}
+
+impl<C: NativeClass> SkipDynamicProperties<C> for &DynamicPropertiesProbe<C> {}
@@ -5,9 +5,17 @@ use std::ops::RangeInclusive;
 
 use crate::core_types::GodotString;
 use crate::core_types::VariantType;
+use crate::object::GodotObject;
 use crate::sys;
 
-use super::ExportInfo;
+use super::{Export, ExportInfo};
+
+// Hints added to the engine after the GDNative headers were last updated. The values follow
+// the engine's `PropertyHint` enum, where they come right after `PROPERTY_OF_SCRIPT`.
+const PROPERTY_HINT_OBJECT_TOO_BIG: sys::godot_property_hint =
+    sys::godot_property_hint_GODOT_PROPERTY_HINT_MAX;
+const PROPERTY_HINT_NODE_PATH_VALID_TYPES: sys::godot_property_hint =
+    sys::godot_property_hint_GODOT_PROPERTY_HINT_MAX + 1;
 
 /// Hints that an integer or float property should be within an inclusive range.
 ///
@@ -162,6 +170,11 @@ pub enum IntHint<T> {
     Layers3DRender,
     /// Hints that an integer property is a bitmask using the optionally named 3D physics layers.
     Layers3DPhysics,
+    /// Hints that an integer property is the instance ID of an object.
+    ObjectId,
+    /// Hints that an integer property is a key accelerator, i.e. a scancode combined with
+    /// modifier masks. Not used by the 3.x inspector.
+    KeyAccel,
 }
 
 impl<T> IntHint<T>
@@ -181,6 +194,8 @@ where
             IH::Layers2DPhysics => sys::godot_property_hint_GODOT_PROPERTY_HINT_LAYERS_2D_PHYSICS,
             IH::Layers3DRender => sys::godot_property_hint_GODOT_PROPERTY_HINT_LAYERS_3D_RENDER,
             IH::Layers3DPhysics => sys::godot_property_hint_GODOT_PROPERTY_HINT_LAYERS_3D_PHYSICS,
+            IH::ObjectId => sys::godot_property_hint_GODOT_PROPERTY_HINT_OBJECT_ID,
+            IH::KeyAccel => sys::godot_property_hint_GODOT_PROPERTY_HINT_KEY_ACCEL,
         };
 
         let hint_string = match self {
@@ -349,8 +364,28 @@ pub enum StringHint {
     GlobalDir,
     /// Hints that a string property is text with line breaks.
     Multiline,
+    /// Hints that a string property has a maximum length. Not used by the 3.x inspector.
+    Length(u32),
     /// Hints that a string property should have a placeholder text visible on its input field, whenever the property is empty.
     Placeholder { placeholder: String },
+    /// Hints that a string property is the name of a class inheriting the given base class.
+    TypeString { base_type: String },
+    /// Hints that a string property is the name of a method of the given built-in type.
+    MethodOfVariantType(VariantType),
+    /// Hints that a string property is the name of a method of the given class.
+    MethodOfBaseType { base_type: String },
+    /// Hints that a string property is the name of a method of the object with the given instance ID.
+    MethodOfInstance(i64),
+    /// Hints that a string property is the name of a method of the script with the given instance ID.
+    MethodOfScript(i64),
+    /// Hints that a string property is the name of a property of the given built-in type.
+    PropertyOfVariantType(VariantType),
+    /// Hints that a string property is the name of a property of the given class.
+    PropertyOfBaseType { base_type: String },
+    /// Hints that a string property is the name of a property of the object with the given instance ID.
+    PropertyOfInstance(i64),
+    /// Hints that a string property is the name of a property of the script with the given instance ID.
+    PropertyOfScript(i64),
 }
 
 impl StringHint {
@@ -365,12 +400,47 @@ impl StringHint {
             SH::Dir => sys::godot_property_hint_GODOT_PROPERTY_HINT_DIR,
             SH::GlobalDir => sys::godot_property_hint_GODOT_PROPERTY_HINT_GLOBAL_DIR,
             SH::Multiline => sys::godot_property_hint_GODOT_PROPERTY_HINT_MULTILINE_TEXT,
+            SH::Length(_) => sys::godot_property_hint_GODOT_PROPERTY_HINT_LENGTH,
             SH::Placeholder { .. } => sys::godot_property_hint_GODOT_PROPERTY_HINT_PLACEHOLDER_TEXT,
+            SH::TypeString { .. } => sys::godot_property_hint_GODOT_PROPERTY_HINT_TYPE_STRING,
+            SH::MethodOfVariantType(_) => {
+                sys::godot_property_hint_GODOT_PROPERTY_HINT_METHOD_OF_VARIANT_TYPE
+            }
+            SH::MethodOfBaseType { .. } => {
+                sys::godot_property_hint_GODOT_PROPERTY_HINT_METHOD_OF_BASE_TYPE
+            }
+            SH::MethodOfInstance(_) => {
+                sys::godot_property_hint_GODOT_PROPERTY_HINT_METHOD_OF_INSTANCE
+            }
+            SH::MethodOfScript(_) => sys::godot_property_hint_GODOT_PROPERTY_HINT_METHOD_OF_SCRIPT,
+            SH::PropertyOfVariantType(_) => {
+                sys::godot_property_hint_GODOT_PROPERTY_HINT_PROPERTY_OF_VARIANT_TYPE
+            }
+            SH::PropertyOfBaseType { .. } => {
+                sys::godot_property_hint_GODOT_PROPERTY_HINT_PROPERTY_OF_BASE_TYPE
+            }
+            SH::PropertyOfInstance(_) => {
+                sys::godot_property_hint_GODOT_PROPERTY_HINT_PROPERTY_OF_INSTANCE
+            }
+            SH::PropertyOfScript(_) => {
+                sys::godot_property_hint_GODOT_PROPERTY_HINT_PROPERTY_OF_SCRIPT
+            }
         };
 
         let hint_string = match self {
             SH::Enum(e) | SH::File(e) | SH::GlobalFile(e) => e.to_godot_hint_string(),
             SH::Placeholder { placeholder } => placeholder.into(),
+            SH::TypeString { base_type }
+            | SH::MethodOfBaseType { base_type }
+            | SH::PropertyOfBaseType { base_type } => base_type.into(),
+            SH::MethodOfVariantType(ty) | SH::PropertyOfVariantType(ty) => {
+                (ty as i64).to_string().into()
+            }
+            SH::MethodOfInstance(id)
+            | SH::MethodOfScript(id)
+            | SH::PropertyOfInstance(id)
+            | SH::PropertyOfScript(id) => id.to_string().into(),
+            SH::Length(len) => len.to_string().into(),
             _ => GodotString::new(),
         };
 
@@ -401,3 +471,203 @@ impl ColorHint {
         }
     }
 }
+
+/// Possible hints for `NodePath`.
+#[derive(Clone, Debug)]
+pub enum NodePathHint {
+    /// Hints that a node path property should point to a node of one of the given classes.
+    ValidTypes(Vec<String>),
+    /// Hints that a node path property is relative to the node being edited.
+    ToEditedNode,
+}
+
+impl NodePathHint {
+    /// Hints that a node path property should point to a node of the class `T`, or one of its
+    /// subclasses.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let hint = NodePathHint::valid_type::<Spatial>();
+    /// ```
+    #[inline]
+    pub fn valid_type<T: GodotObject>() -> Self {
+        NodePathHint::ValidTypes(vec![T::class_name().to_string()])
+    }
+
+    #[inline]
+    pub fn export_info(self) -> ExportInfo {
+        let (hint_kind, hint_string) = match self {
+            NodePathHint::ValidTypes(types) => {
+                (PROPERTY_HINT_NODE_PATH_VALID_TYPES, types.join(",").into())
+            }
+            NodePathHint::ToEditedNode => (
+                sys::godot_property_hint_GODOT_PROPERTY_HINT_NODE_PATH_TO_EDITED_NODE,
+                GodotString::new(),
+            ),
+        };
+
+        ExportInfo {
+            variant_type: VariantType::NodePath,
+            hint_kind,
+            hint_string,
+        }
+    }
+}
+
+/// Possible hints for object references. Properties without a hint are hinted with their
+/// resource type instead.
+#[derive(Clone, Debug)]
+pub enum ObjectHint {
+    /// Hints that an image property should be compressed lossily when saved.
+    ImageCompressLossy,
+    /// Hints that an image property should be compressed losslessly when saved.
+    ImageCompressLossless,
+    /// Hints that an object property is too big to be edited inline in the inspector.
+    TooBig,
+}
+
+impl ObjectHint {
+    #[inline]
+    pub fn export_info(self) -> ExportInfo {
+        let hint_kind = match self {
+            ObjectHint::ImageCompressLossy => {
+                sys::godot_property_hint_GODOT_PROPERTY_HINT_IMAGE_COMPRESS_LOSSY
+            }
+            ObjectHint::ImageCompressLossless => {
+                sys::godot_property_hint_GODOT_PROPERTY_HINT_IMAGE_COMPRESS_LOSSLESS
+            }
+            ObjectHint::TooBig => PROPERTY_HINT_OBJECT_TOO_BIG,
+        };
+
+        ExportInfo {
+            variant_type: VariantType::Object,
+            hint_kind,
+            hint_string: GodotString::new(),
+        }
+    }
+}
+
+/// Hints that the elements of an array property are of a specific type, optionally with a
+/// hint of their own. This is how typed arrays are shown in the inspector.
+///
+/// `Vec<T>` uses the export info of `T` automatically, so this is mostly useful for
+/// `VariantArray` properties.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```ignore
+/// use gdnative_core::nativescript::init::property::hint::{ArrayHint, RangeHint};
+///
+/// let hint = ArrayHint::with_element::<f64>(Some(RangeHint::new(0.0, 1.0).into()));
+/// ```
+#[derive(Debug, Default)]
+pub struct ArrayHint {
+    element: Option<ExportInfo>,
+}
+
+impl ArrayHint {
+    /// Creates an `ArrayHint` for elements of any type.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an `ArrayHint` for elements of the exported type `T`, with an optional hint.
+    #[inline]
+    pub fn with_element<T: Export>(hint: Option<T::Hint>) -> Self {
+        Self::with_element_info(T::export_info(hint))
+    }
+
+    /// Creates an `ArrayHint` for elements described by an untyped `ExportInfo`.
+    #[inline]
+    pub fn with_element_info(element: ExportInfo) -> Self {
+        ArrayHint {
+            element: Some(element),
+        }
+    }
+
+    /// Returns export info for an array of the given Variant type, e.g. `VariantType::VariantArray`
+    /// or one of the pool array types.
+    #[inline]
+    pub fn export_info(self, variant_type: VariantType) -> ExportInfo {
+        match self.element {
+            Some(element) => ExportInfo {
+                variant_type,
+                hint_kind: sys::godot_property_hint_GODOT_PROPERTY_HINT_TYPE_STRING,
+                hint_string: type_hint_string(&element).into(),
+            },
+            None => ExportInfo::new(variant_type),
+        }
+    }
+}
+
+/// Hints that the keys and values of a dictionary property are of specific types, optionally
+/// with hints of their own.
+///
+/// The hint string lists the key and value types in the same format as `ArrayHint`, separated
+/// by a semicolon. The 3.x inspector doesn't enforce these types, but they're available to
+/// editor plugins through the property list.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```ignore
+/// use gdnative_core::nativescript::init::property::hint::{DictionaryHint, RangeHint};
+///
+/// let hint = DictionaryHint::with_types::<String, f64>(None, Some(RangeHint::new(0.0, 1.0).into()));
+/// ```
+#[derive(Debug, Default)]
+pub struct DictionaryHint {
+    types: Option<(ExportInfo, ExportInfo)>,
+}
+
+impl DictionaryHint {
+    /// Creates a `DictionaryHint` for keys and values of any type.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a `DictionaryHint` for keys of the exported type `K` and values of the exported
+    /// type `V`, with optional hints.
+    #[inline]
+    pub fn with_types<K: Export, V: Export>(
+        key_hint: Option<K::Hint>,
+        value_hint: Option<V::Hint>,
+    ) -> Self {
+        Self::with_type_info(K::export_info(key_hint), V::export_info(value_hint))
+    }
+
+    /// Creates a `DictionaryHint` for keys and values described by untyped `ExportInfo`s.
+    #[inline]
+    pub fn with_type_info(key: ExportInfo, value: ExportInfo) -> Self {
+        DictionaryHint {
+            types: Some((key, value)),
+        }
+    }
+
+    #[inline]
+    pub fn export_info(self) -> ExportInfo {
+        match self.types {
+            Some((key, value)) => ExportInfo {
+                variant_type: VariantType::Dictionary,
+                hint_kind: sys::godot_property_hint_GODOT_PROPERTY_HINT_TYPE_STRING,
+                hint_string: format!("{};{}", type_hint_string(&key), type_hint_string(&value))
+                    .into(),
+            },
+            None => ExportInfo::new(VariantType::Dictionary),
+        }
+    }
+}
+
+/// Formats export info as a Godot type hint string, of the form `type/hint:hint_string`.
+fn type_hint_string(info: &ExportInfo) -> String {
+    format!(
+        "{}/{}:{}",
+        info.variant_type as i64, info.hint_kind as i64, info.hint_string,
+    )
+}
@@ -0,0 +1,99 @@
+//! Library-level table of static methods, which can be called without an instance of their
+//! class through `GDNative.call_native`.
+
+use std::ptr;
+
+use parking_lot::Mutex;
+
+use crate::core_types::Variant;
+use crate::private::get_api;
+use crate::sys;
+
+use super::ScriptMethodFn;
+
+struct StaticMethod {
+    class_name: &'static str,
+    name: String,
+    method: ScriptMethodFn,
+}
+
+static STATIC_METHODS: Mutex<Vec<StaticMethod>> = parking_lot::const_mutex(Vec::new());
+
+/// Adds a static method to the table. `method` must never access the base object or the user
+/// data, since it's called with null pointers for both.
+pub(crate) fn register(class_name: &'static str, name: &str, method: ScriptMethodFn) {
+    STATIC_METHODS.lock().push(StaticMethod {
+        class_name,
+        name: name.into(),
+        method,
+    });
+}
+
+/// Removes all static methods from the table. Called when the library is terminated.
+pub(crate) fn cleanup() {
+    STATIC_METHODS.lock().clear();
+}
+
+/// Calls a static method with the arguments `[class_name, method_name, args...]`.
+///
+/// # Safety
+///
+/// `args` must point to a valid `godot_array`.
+pub(crate) unsafe fn call(args: *mut sys::godot_array) -> Variant {
+    let api = get_api();
+
+    let len = (api.godot_array_size)(args);
+    let mut args = (0..len)
+        .map(|idx| Variant::from_sys((api.godot_array_get)(args, idx)))
+        .collect::<Vec<_>>();
+
+    if args.len() < 2 {
+        godot_error!(
+            "gdnative-core: static method calls take the class and method names as the first arguments"
+        );
+        return Variant::new();
+    }
+
+    let mut call_args = args.split_off(2);
+    let (class_name, name) = match (args[0].try_to_string(), args[1].try_to_string()) {
+        (Some(class_name), Some(name)) => (class_name, name),
+        _ => {
+            godot_error!("gdnative-core: the class and method names must be strings");
+            return Variant::new();
+        }
+    };
+
+    // The lock isn't held during the call, so static methods can call other static methods.
+    let method = STATIC_METHODS
+        .lock()
+        .iter()
+        .find(|m| m.class_name == class_name && m.name == name)
+        .map(|m| m.method);
+
+    let method = match method {
+        Some(method) => method,
+        None => {
+            godot_error!(
+                "gdnative-core: static method {}::{} not found",
+                class_name,
+                name
+            );
+            return Variant::new();
+        }
+    };
+
+    let mut arg_ptrs = call_args
+        .iter_mut()
+        .map(Variant::sys_mut)
+        .collect::<Vec<_>>();
+
+    let ret = method(
+        ptr::null_mut(),
+        ptr::null_mut(),
+        ptr::null_mut(),
+        arg_ptrs.len() as libc::c_int,
+        arg_ptrs.as_mut_ptr(),
+    );
+
+    Variant::from_sys(ret)
+}
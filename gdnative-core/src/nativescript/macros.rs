@@ -42,6 +42,51 @@ macro_rules! godot_nativescript_init {
             if __result.is_err() {
                 $crate::godot_error!("gdnative-core: nativescript_init callback panicked");
             }
+
+            $crate::private::nativescript_init_internal_state();
+        }
+    };
+}
+
+/// Declare the API endpoint to call static methods without an instance of their class.
+///
+/// By default this declares an extern function named `godot_nativescript_call_static`, which
+/// can be called with `GDNative.call_native` using the `standard_varcall` calling type. The
+/// arguments are the names of the class and the method, followed by the arguments of the
+/// method:
+///
+/// ```ignore
+/// gdn.call_native("standard_varcall", "godot_nativescript_call_static", ["MyType", "lerp", 0.0, 10.0, 0.5])
+/// ```
+///
+/// The name can be overridden, for example:
+///
+/// ```ignore
+/// // Declares an extern function named custom_call_static instead of
+/// // godot_nativescript_call_static.
+/// godot_nativescript_call_static!(custom_call_static);
+/// ```
+///
+/// Static methods are registered with `ClassBuilder::add_static_method`, which is used by
+/// `#[methods]` for exported functions without `self`.
+#[macro_export]
+macro_rules! godot_nativescript_call_static {
+    () => {
+        $crate::godot_nativescript_call_static!(godot_nativescript_call_static);
+    };
+    ($fn_name:ident) => {
+        #[no_mangle]
+        #[doc(hidden)]
+        #[allow(unused_unsafe)]
+        pub unsafe extern "C" fn $fn_name(
+            args: *mut $crate::sys::godot_array,
+        ) -> $crate::sys::godot_variant {
+            if !$crate::private::is_api_bound() {
+                // Nil is represented by a zeroed variant.
+                return ::std::mem::zeroed();
+            }
+
+            $crate::private::call_static_method(args)
         }
     };
 }
@@ -53,18 +98,21 @@ macro_rules! godot_nativescript_init {
 /// ```ignore
 /// godot_gdnative_init!();
 /// godot_nativescript_init!(init);
+/// godot_nativescript_call_static!();
 /// godot_gdnative_terminate!();
 /// ```
 ///
-/// This declares three extern functions, named `godot_gdnative_init`,
-/// `godot_nativescript_init`, and `godot_gdnative_terminate`. If you need different prefixes
-/// to avoid name clashes when multiple GDNative libraries are linked statically, please use
-/// the respective macros directly.
+/// This declares four extern functions, named `godot_gdnative_init`,
+/// `godot_nativescript_init`, `godot_nativescript_call_static`, and
+/// `godot_gdnative_terminate`. If you need different prefixes to avoid name clashes when
+/// multiple GDNative libraries are linked statically, please use the respective macros
+/// directly.
 #[macro_export]
 macro_rules! godot_init {
     ($callback:ident) => {
         $crate::godot_gdnative_init!();
         $crate::godot_nativescript_init!($callback);
+        $crate::godot_nativescript_call_static!();
         $crate::godot_gdnative_terminate!();
     };
 }
@@ -80,6 +128,81 @@ macro_rules! godot_wrap_method_parameter_count {
     }
 }
 
+/// Converts the arguments of a wrapped method, declaring a local variable for each parameter.
+/// Returns a nil `Variant` from the enclosing closure if the arguments can't be converted.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! godot_wrap_method_args {
+    (
+        $num_args:ident,
+        $args:ident,
+        ($($pname:ident : $pty:ty,)*),
+        ($($opt_pname:ident : $opt_pty:ty,)*)
+    ) => {
+        let num_args = $num_args as isize;
+
+        let num_required_params = $crate::godot_wrap_method_parameter_count!($($pname,)*);
+        if num_args < num_required_params {
+            $crate::godot_error!("Incorrect number of parameters: required {} but got {}", num_required_params, num_args);
+            return $crate::core_types::Variant::new();
+        }
+
+        let num_optional_params = $crate::godot_wrap_method_parameter_count!($($opt_pname,)*);
+        let num_max_params = num_required_params + num_optional_params;
+        if num_args > num_max_params {
+            $crate::godot_error!("Incorrect number of parameters: expected at most {} but got {}", num_max_params, num_args);
+            return $crate::core_types::Variant::new();
+        }
+
+        let mut offset = 0;
+        $(
+            let _variant: &$crate::core_types::Variant = ::std::mem::transmute(&mut **($args.offset(offset)));
+            let $pname = match <$pty as $crate::core_types::FromVariant>::from_variant(_variant) {
+                Ok(val) => val,
+                Err(err) => {
+                    $crate::godot_error!(
+                        "Cannot convert argument #{idx} ({name}) to {ty}: {err} (non-primitive types may impose structural checks)",
+                        idx = offset + 1,
+                        name = stringify!($pname),
+                        ty = stringify!($pty),
+                        err = err,
+                    );
+                    return $crate::core_types::Variant::new();
+                },
+            };
+
+            offset += 1;
+        )*
+
+        $(
+            let $opt_pname = if offset < num_args {
+                let _variant: &$crate::core_types::Variant = ::std::mem::transmute(&mut **($args.offset(offset)));
+
+                let $opt_pname = match <$opt_pty as $crate::core_types::FromVariant>::from_variant(_variant) {
+                    Ok(val) => val,
+                    Err(err) => {
+                        $crate::godot_error!(
+                            "Cannot convert argument #{idx} ({name}) to {ty}: {err} (non-primitive types may impose structural checks)",
+                            idx = offset + 1,
+                            name = stringify!($opt_pname),
+                            ty = stringify!($opt_pty),
+                            err = err,
+                        );
+                        return $crate::core_types::Variant::new();
+                    },
+                };
+
+                offset += 1;
+
+                $opt_pname
+            }
+            else {
+                <$opt_pty as ::std::default::Default>::default()
+            };
+        )*
+    };
+}
+
 #[doc(hidden)]
 #[macro_export]
 macro_rules! godot_wrap_method_inner {
@@ -132,67 +255,16 @@ macro_rules! godot_wrap_method_inner {
                     let this: TRef<'_, <$type_name as NativeClass>::Base, _> = this.assume_safe_unchecked();
                     let __instance: RefInstance<'_, $type_name, _> = RefInstance::from_raw_unchecked(this, user_data);
 
-                    let num_args = num_args as isize;
-
-                    let num_required_params = $crate::godot_wrap_method_parameter_count!($($pname,)*);
-                    if num_args < num_required_params {
-                        $crate::godot_error!("Incorrect number of parameters: required {} but got {}", num_required_params, num_args);
-                        return $crate::core_types::Variant::new();
-                    }
+                    $crate::godot_wrap_method_args!(
+                        num_args,
+                        args,
+                        ($($pname: $pty,)*),
+                        ($($opt_pname: $opt_pty,)*)
+                    );
 
-                    let num_optional_params = $crate::godot_wrap_method_parameter_count!($($opt_pname,)*);
-                    let num_max_params = num_required_params + num_optional_params;
-                    if num_args > num_max_params {
-                        $crate::godot_error!("Incorrect number of parameters: expected at most {} but got {}", num_max_params, num_args);
-                        return $crate::core_types::Variant::new();
-                    }
-
-                    let mut offset = 0;
-                    $(
-                        let _variant: &$crate::core_types::Variant = ::std::mem::transmute(&mut **(args.offset(offset)));
-                        let $pname = match <$pty as $crate::core_types::FromVariant>::from_variant(_variant) {
-                            Ok(val) => val,
-                            Err(err) => {
-                                $crate::godot_error!(
-                                    "Cannot convert argument #{idx} ({name}) to {ty}: {err} (non-primitive types may impose structural checks)",
-                                    idx = offset + 1,
-                                    name = stringify!($pname),
-                                    ty = stringify!($pty),
-                                    err = err,
-                                );
-                                return $crate::core_types::Variant::new();
-                            },
-                        };
-
-                        offset += 1;
-                    )*
-
-                    $(
-                        let $opt_pname = if offset < num_args {
-                            let _variant: &$crate::core_types::Variant = ::std::mem::transmute(&mut **(args.offset(offset)));
-
-                            let $opt_pname = match <$opt_pty as $crate::core_types::FromVariant>::from_variant(_variant) {
-                                Ok(val) => val,
-                                Err(err) => {
-                                    $crate::godot_error!(
-                                        "Cannot convert argument #{idx} ({name}) to {ty}: {err} (non-primitive types may impose structural checks)",
-                                        idx = offset + 1,
-                                        name = stringify!($opt_pname),
-                                        ty = stringify!($opt_pty),
-                                        err = err,
-                                    );
-                                    return $crate::core_types::Variant::new();
-                                },
-                            };
-
-                            offset += 1;
-
-                            $opt_pname
-                        }
-                        else {
-                            <$opt_pty as ::std::default::Default>::default()
-                        };
-                    )*
+                    let __method_scope = $crate::nativescript::user_data::diagnostics::enter_method(
+                        stringify!($method_name),
+                    );
 
                     let __ret = __instance
                         .$map_method(|__rust_val, $owner| {
@@ -205,10 +277,14 @@ macro_rules! godot_wrap_method_inner {
                         })
                         .unwrap_or_else(|err| {
                             $crate::godot_error!("gdnative-core: method call failed with error: {}", err);
+                            if let Some(failure) = $crate::nativescript::user_data::diagnostics::take_last_failure() {
+                                $crate::godot_error!("gdnative-core: {}", failure);
+                            }
                             $crate::godot_error!("gdnative-core: check module level documentation on gdnative::user_data for more information");
                             $crate::core_types::Variant::new()
                         });
 
+                    std::mem::drop(__method_scope);
                     std::mem::drop(__instance);
 
                     __ret
@@ -216,7 +292,11 @@ macro_rules! godot_wrap_method_inner {
 
                 __catch_result
                     .unwrap_or_else(|_err| {
-                        $crate::godot_error!("gdnative-core: method panicked (check stderr for output)");
+                        $crate::godot_error!(
+                            "gdnative-core: method {}::{} panicked",
+                            <$type_name as $crate::nativescript::NativeClass>::class_name(),
+                            stringify!($method_name),
+                        );
                         $crate::core_types::Variant::new()
                     })
                     .forget()
@@ -319,6 +399,135 @@ macro_rules! godot_wrap_method {
     };
 }
 
+#[doc(hidden)]
+#[macro_export]
+macro_rules! godot_wrap_static_method_inner {
+    (
+        $type_name:ty,
+        fn $method_name:ident(
+            $($pname:ident : $pty:ty,)*
+            ;
+            $($opt_pname:ident : $opt_pty:ty,)*
+        ) -> $retty:ty
+    ) => {
+        {
+            #[allow(unused_unsafe, unused_variables, unused_assignments, unused_mut)]
+            #[allow(clippy::transmute_ptr_to_ptr)]
+            unsafe extern "C" fn method(
+                _this: *mut $crate::sys::godot_object,
+                _method_data: *mut $crate::libc::c_void,
+                _user_data: *mut $crate::libc::c_void,
+                num_args: $crate::libc::c_int,
+                args: *mut *mut $crate::sys::godot_variant
+            ) -> $crate::sys::godot_variant {
+
+                use std::panic;
+
+                let __catch_result = panic::catch_unwind(move || {
+                    $crate::godot_wrap_method_args!(
+                        num_args,
+                        args,
+                        ($($pname: $pty,)*),
+                        ($($opt_pname: $opt_pty,)*)
+                    );
+
+                    let ret = <$type_name>::$method_name(
+                        $($pname,)*
+                        $($opt_pname,)*
+                    );
+                    <$retty as $crate::core_types::OwnedToVariant>::owned_to_variant(ret)
+                });
+
+                __catch_result
+                    .unwrap_or_else(|_err| {
+                        $crate::godot_error!(
+                            "gdnative-core: method {}::{} panicked",
+                            <$type_name as $crate::nativescript::NativeClass>::class_name(),
+                            stringify!($method_name),
+                        );
+                        $crate::core_types::Variant::new()
+                    })
+                    .forget()
+            }
+
+            method
+        }
+    };
+}
+
+/// Convenience macro to wrap an associated function without a receiver into a function
+/// pointer that can be passed to the engine when registering a class.
+///
+/// Unlike methods wrapped with `godot_wrap_method`, the resulting function never accesses
+/// the base object or the user data of the instance it's called on. It can thus be called
+/// on any instance of the script, including ones whose constructor has failed, such as
+/// instances of `#[no_constructor]` classes created from GDScript, or without an instance
+/// at all when registered with `ClassBuilder::add_static_method`. Argument conversion is
+/// identical to that of instance methods.
+///
+/// # Examples
+///
+/// ```ignore
+/// impl MyType {
+///     fn lerp(from: f64, to: f64, weight: f64) -> f64 {
+///         from + (to - from) * weight
+///     }
+/// }
+///
+/// let method = godot_wrap_static_method!(MyType, fn lerp(from: f64, to: f64, #[opt] weight: f64) -> f64);
+/// builder.add_static_method("lerp", method);
+/// ```
+#[macro_export]
+macro_rules! godot_wrap_static_method {
+    (
+        $type_name:ty,
+        fn $method_name:ident(
+            $($pname:ident : $pty:ty),*
+            $(,#[opt] $opt_pname:ident : $opt_pty:ty)*
+            $(,)?
+        ) -> $retty:ty
+    ) => {
+        $crate::godot_wrap_static_method_inner!(
+            $type_name,
+            fn $method_name(
+                $($pname : $pty,)*
+                ;
+                $($opt_pname : $opt_pty,)*
+            ) -> $retty
+        )
+    };
+    // only optional parameters
+    (
+        $type_name:ty,
+        fn $method_name:ident(
+            $(#[opt] $opt_pname:ident : $opt_pty:ty),+
+            $(,)?
+        ) -> $retty:ty
+    ) => {
+        $crate::godot_wrap_static_method_inner!(
+            $type_name,
+            fn $method_name(
+                ;
+                $($opt_pname : $opt_pty,)*
+            ) -> $retty
+        )
+    };
+    // without return type
+    (
+        $type_name:ty,
+        fn $method_name:ident(
+            $($args:tt)*
+        )
+    ) => {
+        $crate::godot_wrap_static_method!(
+            $type_name,
+            fn $method_name(
+                $($args)*
+            ) -> ()
+        )
+    };
+}
+
 /// Convenience macro to create a profiling signature with a given tag.
 ///
 /// The expanded code will panic at runtime if the file name or `tag` contains `::` or
@@ -6,9 +6,12 @@ mod macros;
 pub mod class;
 pub mod init;
 pub mod profiling;
+pub mod reload;
 pub mod type_tag;
 pub mod user_data;
+pub mod virtuals;
 
 pub use class::*;
 pub use init::*;
+pub use reload::HotReload;
 pub use user_data::{Map, MapMut, UserData};
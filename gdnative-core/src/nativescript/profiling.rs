@@ -125,15 +125,15 @@ impl Signature<'static> {
 /// Add a data point to Godot's built-in profiler. The profiler only has microsecond precision.
 /// Sub-microsecond time is truncated.
 ///
-/// If the GDNative API is not initialized at the point when this is called, the function will
-/// fail silently.
+/// If the GDNative API is not initialized at the point when this is called, or the engine
+/// doesn't provide NativeScript 1.1, the function will fail silently.
 ///
 /// # Panics
 ///
 /// If the number of microseconds in `time` exceeds the range of `u64`.
 #[inline]
 pub fn add_data(signature: Signature<'_>, time: Duration) {
-    if let Some(api) = try_get_api() {
+    if let Some(api) = try_get_api().filter(|api| api.has_nativescript_1_1()) {
         let time_in_usec = u64::try_from(time.as_micros())
             .expect("microseconds in `time` should not exceed the range of u64");
 
@@ -0,0 +1,465 @@
+//! Hot reloading of script classes.
+//!
+//! GDNative libraries marked as `reloadable` in their `GDNativeLibrary` resource are unloaded
+//! when the editor loses focus, and loaded again when it regains focus, so that a rebuilt
+//! library is picked up without restarting the editor. Rust values don't survive this, so
+//! script instances normally lose their state.
+//!
+//! Classes registered with `InitHandle::add_reloadable_class` or
+//! `InitHandle::add_reloadable_tool_class` keep it instead, by implementing `HotReload`:
+//!
+//! ```ignore
+//! #[derive(NativeClass)]
+//! #[inherit(Node)]
+//! struct Counter {
+//!     count: i64,
+//! }
+//!
+//! impl HotReload for Counter {
+//!     type State = i64;
+//!
+//!     fn save_state(&self, _owner: TRef<Node>) -> i64 {
+//!         self.count
+//!     }
+//!
+//!     fn restore_state(_owner: TRef<Node>, count: i64) -> Self {
+//!         Counter { count }
+//!     }
+//! }
+//!
+//! fn init(handle: InitHandle) {
+//!     handle.add_reloadable_tool_class::<Counter>();
+//! }
+//! ```
+//!
+//! During `gdnative_terminate`, the state of every live instance of these classes is saved as
+//! metadata of the `Engine` singleton, which outlives the library, and the instances are
+//! dropped. The engine keeps the script instances attached to their objects while the library
+//! is unloaded, but doesn't create them again when it's loaded. Instead, after the classes are
+//! registered in `nativescript_init`, the script of every object with a saved state is attached
+//! to it again with `Object::set_script`, which constructs the new instance with
+//! `HotReload::restore_state` instead of `NativeClass::init`. Instances whose state couldn't be
+//! saved start from `NativeClass::init`.
+//!
+//! States of objects that were freed while the library was unloaded are discarded. States of
+//! classes that aren't registered again are kept until the engine exits.
+//!
+//! Hot reloading requires the core 1.2 API. Without it, states aren't saved, and instances of
+//! reloadable classes are left alone like those of other classes.
+//!
+//! Saved states must not refer to values owned by the library, like instances of its script
+//! classes, since they don't survive the reload.
+
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr::{self, NonNull};
+
+use parking_lot::Mutex;
+
+use crate::core_types::{
+    Dictionary, FromVariant, GodotString, OwnedToVariant, ToVariant, Variant, VariantArray,
+};
+use crate::nativescript::init::InstanceHooks;
+use crate::nativescript::{Map, NativeClass, UserData};
+use crate::object::{GodotObject, RawObject, TRef};
+use crate::private::{get_api, ObjectMethodTable, ReferenceMethodTable};
+use crate::sys;
+use crate::thread_access::{Shared, Unique};
+
+/// Name of the `Engine` metadata holding the saved states, as a dictionary from instance IDs
+/// to `[class_name, user_data, state]` arrays. `state` is missing if it couldn't be saved.
+const STATES_META: &str = "_gdnative_reload_states";
+
+/// Script classes that keep their state when the library is reloaded.
+///
+/// See the module-level documentation for more info.
+pub trait HotReload: NativeClass {
+    /// The state that is saved before the library is unloaded.
+    type State: ToVariant + FromVariant;
+
+    /// Returns the state of this instance. Called during `gdnative_terminate`.
+    fn save_state(&self, owner: TRef<'_, Self::Base, Shared>) -> Self::State;
+
+    /// Constructs an instance from a state saved by `save_state` before the library was
+    /// reloaded. Called instead of `NativeClass::init`.
+    fn restore_state(owner: TRef<'_, Self::Base, Shared>, state: Self::State) -> Self;
+}
+
+/// A live instance of a class registered with `ReloadHooks`.
+struct LiveInstance {
+    owner: *mut sys::godot_object,
+    user_data: *const libc::c_void,
+    class_name: &'static str,
+    save: unsafe fn(*mut sys::godot_object, *const libc::c_void) -> Option<Variant>,
+    drop: unsafe fn(*const libc::c_void),
+}
+
+// Safety: The pointers are only dereferenced in `save_all`, while the library is terminated.
+unsafe impl Send for LiveInstance {}
+
+static LIVE_INSTANCES: Mutex<Vec<LiveInstance>> = parking_lot::const_mutex(Vec::new());
+
+/// Names of the reloadable classes registered since the library was loaded.
+static CLASSES: Mutex<Vec<&'static str>> = parking_lot::const_mutex(Vec::new());
+
+/// Instance hooks for classes registered with `add_reloadable_class`.
+pub(crate) struct ReloadHooks;
+
+impl<C> InstanceHooks<C> for ReloadHooks
+where
+    C: HotReload,
+    C::UserData: Map,
+{
+    #[inline]
+    fn construct(owner: TRef<'_, C::Base, Shared>) -> Option<C> {
+        let state = unsafe { take_state(owner.as_raw().sys().as_ptr(), C::class_name())? };
+        match C::State::from_variant(&state) {
+            Ok(state) => Some(C::restore_state(owner, state)),
+            Err(err) => {
+                godot_warn!(
+                    "gdnative-core: discarding the saved state of {}: {}",
+                    C::class_name(),
+                    err,
+                );
+                None
+            }
+        }
+    }
+
+    #[inline]
+    unsafe fn created(owner: *mut sys::godot_object, user_data: *const libc::c_void) {
+        LIVE_INSTANCES.lock().push(LiveInstance {
+            owner,
+            user_data,
+            class_name: C::class_name(),
+            save: save::<C>,
+            drop: drop_user_data::<C>,
+        });
+    }
+
+    #[inline]
+    fn registered() {
+        CLASSES.lock().push(C::class_name());
+    }
+
+    #[inline]
+    unsafe fn destroyed(_owner: *mut sys::godot_object, user_data: *const libc::c_void) -> bool {
+        // Instances that aren't live were dropped by `save_all` before the library was
+        // reloaded, and are being replaced by `restore_all`.
+        let mut live = LIVE_INSTANCES.lock();
+        match live.iter().position(|i| i.user_data == user_data) {
+            Some(idx) => {
+                live.swap_remove(idx);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Saves the state of a live instance of `C`.
+unsafe fn save<C>(owner: *mut sys::godot_object, user_data: *const libc::c_void) -> Option<Variant>
+where
+    C: HotReload,
+    C::UserData: Map,
+{
+    let owner = RawObject::<C::Base>::from_sys_ref_unchecked(NonNull::new_unchecked(owner));
+    let owner = TRef::new(C::Base::cast_ref(owner));
+    let user_data = C::UserData::clone_from_user_data_unchecked(user_data);
+
+    match user_data.map(|this| this.save_state(owner).to_variant()) {
+        Ok(state) => Some(state),
+        Err(err) => {
+            godot_error!(
+                "gdnative-core: failed to save the state of {}: {:?}",
+                C::class_name(),
+                err,
+            );
+            None
+        }
+    }
+}
+
+/// Drops the user data of an instance of `C`.
+unsafe fn drop_user_data<C: NativeClass>(user_data: *const libc::c_void) {
+    drop(C::UserData::consume_user_data_unchecked(user_data));
+}
+
+/// Saves the states of all live instances of reloadable classes, and drops them. Should only be
+/// called from `crate::private::cleanup_internal_state`, while the API is still bound.
+pub(crate) unsafe fn save_all() {
+    CLASSES.lock().clear();
+
+    // Objects can't be found again by their IDs without core 1.2, so instances are kept
+    // instead of being dropped without a way to replace them.
+    if !get_api().has_core_1_2() {
+        return;
+    }
+
+    // The lock is released before calling user code, which might create or destroy instances.
+    let live = std::mem::take(&mut *LIVE_INSTANCES.lock());
+    if live.is_empty() {
+        return;
+    }
+
+    let engine = match engine_singleton() {
+        Some(engine) => engine,
+        None => {
+            godot_error!("gdnative-core: Engine singleton not found, states are not saved");
+            return;
+        }
+    };
+
+    let states = saved_states(engine).map_or_else(Dictionary::new, |states| states.duplicate());
+
+    for instance in live {
+        let state = panic::catch_unwind(AssertUnwindSafe(|| {
+            (instance.save)(instance.owner, instance.user_data)
+        }));
+
+        // Instances are recorded even if their states aren't, so that they are replaced by
+        // `restore_all` instead of keeping their dropped user data.
+        let entry = VariantArray::new();
+        entry.push(instance.class_name);
+        entry.push(instance.user_data as i64);
+
+        match state {
+            Ok(Some(state)) => entry.push(state),
+            Ok(None) => {}
+            Err(_) => {
+                godot_error!(
+                    "gdnative-core: saving the state of {} panicked",
+                    instance.class_name,
+                );
+            }
+        }
+
+        states.insert(instance_id(instance.owner), entry.into_shared());
+
+        let dropped = panic::catch_unwind(AssertUnwindSafe(|| (instance.drop)(instance.user_data)));
+        if dropped.is_err() {
+            godot_error!(
+                "gdnative-core: dropping an instance of {} panicked",
+                instance.class_name,
+            );
+        }
+    }
+
+    set_saved_states(engine, states);
+}
+
+/// Attaches the scripts of the objects whose states were saved before the library was reloaded
+/// again, so that their instances are constructed from the saved states. Should only be called
+/// from `crate::private::nativescript_init_internal_state`, after the classes are registered.
+pub(crate) unsafe fn restore_all() {
+    let api = get_api();
+    if !api.has_core_1_2() {
+        return;
+    }
+
+    let classes = CLASSES.lock().clone();
+    if classes.is_empty() {
+        return;
+    }
+
+    let engine = match engine_singleton() {
+        Some(engine) => engine,
+        None => return,
+    };
+
+    let states = match saved_states(engine) {
+        Some(states) => states,
+        None => return,
+    };
+
+    let mut restored = Vec::new();
+    for (id, entry) in states.iter() {
+        let (id, entry) = match (id.try_to_i64(), entry.try_to_array()) {
+            (Some(id), Some(entry)) => (id, entry),
+            _ => continue,
+        };
+
+        let class_name = entry.get(0).try_to_string();
+        if !classes
+            .iter()
+            .any(|&name| Some(name) == class_name.as_deref())
+        {
+            continue;
+        }
+
+        restored.push(id);
+
+        // The object must still hold the instance that was dropped, otherwise its script was
+        // replaced or it was freed and the ID reused.
+        let owner = (api.godot_instance_from_id)(id as sys::godot_int);
+        if owner.is_null()
+            || (api.godot_nativescript_get_userdata)(owner) as i64 != entry.get(1).to_i64()
+        {
+            continue;
+        }
+
+        let methods = ObjectMethodTable::get(api);
+        let mut script: *mut sys::godot_object = ptr::null_mut();
+        call_method(
+            owner,
+            methods.get_script,
+            &[],
+            &mut script as *mut *mut sys::godot_object as *mut _,
+        );
+        if script.is_null() {
+            continue;
+        }
+
+        // Setting the same script again has no effect, so it's removed first. This destroys the
+        // old instance without dropping its user data again.
+        let null: *mut sys::godot_object = ptr::null_mut();
+        call_method(
+            owner,
+            methods.set_script,
+            &[null as *const _],
+            ptr::null_mut(),
+        );
+        call_method(
+            owner,
+            methods.set_script,
+            &[script as *const _],
+            ptr::null_mut(),
+        );
+
+        // The script is still referenced by the object.
+        let mut last = false;
+        call_method(
+            script,
+            ReferenceMethodTable::get(api).unreference,
+            &[],
+            &mut last as *mut bool as *mut _,
+        );
+        if last {
+            (api.godot_object_destroy)(script);
+        }
+    }
+
+    // States are removed by the constructors once restored. The rest belong to instances that
+    // are gone, or that couldn't be saved.
+    if let Some(states) = saved_states(engine) {
+        let states = states.duplicate();
+        for id in restored {
+            states.erase(id);
+        }
+        set_saved_states(engine, states);
+    }
+}
+
+/// Saves the states of all live instances and restores them as reloading the library does,
+/// without unloading it.
+#[cfg(feature = "gd_test")]
+#[doc(hidden)]
+#[inline]
+pub fn test_reload() {
+    unsafe {
+        let classes = CLASSES.lock().clone();
+        save_all();
+        *CLASSES.lock() = classes;
+        restore_all();
+    }
+}
+
+/// Removes and returns the state saved for `owner`, if it's an instance of `class_name`.
+unsafe fn take_state(owner: *mut sys::godot_object, class_name: &str) -> Option<Variant> {
+    let engine = engine_singleton()?;
+    let states = saved_states(engine)?;
+
+    let id = instance_id(owner);
+    let entry = states.get(id).try_to_array()?;
+    if entry.len() < 2 || entry.get(0).try_to_string().as_deref() != Some(class_name) {
+        return None;
+    }
+
+    let states = states.duplicate();
+    states.erase(id);
+    set_saved_states(engine, states);
+
+    if entry.len() > 2 {
+        Some(entry.get(2))
+    } else {
+        None
+    }
+}
+
+/// Returns the saved states stored in the metadata of `engine`, if any.
+unsafe fn saved_states(engine: *mut sys::godot_object) -> Option<Dictionary<Shared>> {
+    let methods = ObjectMethodTable::get(get_api());
+    let name = GodotString::from_str(STATES_META);
+
+    let mut has_meta = false;
+    call_method(
+        engine,
+        methods.has_meta,
+        &[name.sys() as *const _],
+        &mut has_meta as *mut bool as *mut _,
+    );
+    if !has_meta {
+        return None;
+    }
+
+    let mut meta = Variant::new().forget();
+    call_method(
+        engine,
+        methods.get_meta,
+        &[name.sys() as *const _],
+        &mut meta as *mut sys::godot_variant as *mut _,
+    );
+    Variant::from_sys(meta).try_to_dictionary()
+}
+
+/// Replaces the saved states stored in the metadata of `engine`, removing the metadata if
+/// `states` is empty.
+unsafe fn set_saved_states(engine: *mut sys::godot_object, states: Dictionary<Unique>) {
+    let methods = ObjectMethodTable::get(get_api());
+    let name = GodotString::from_str(STATES_META);
+
+    if states.is_empty() {
+        call_method(
+            engine,
+            methods.remove_meta,
+            &[name.sys() as *const _],
+            ptr::null_mut(),
+        );
+    } else {
+        let states = states.owned_to_variant();
+        call_method(
+            engine,
+            methods.set_meta,
+            &[name.sys() as *const _, states.sys() as *const _],
+            ptr::null_mut(),
+        );
+    }
+}
+
+unsafe fn engine_singleton() -> Option<*mut sys::godot_object> {
+    let engine = (get_api().godot_global_get_singleton)(b"Engine\0".as_ptr() as *mut _);
+    if engine.is_null() {
+        None
+    } else {
+        Some(engine)
+    }
+}
+
+unsafe fn instance_id(obj: *mut sys::godot_object) -> i64 {
+    let mut id = 0i64;
+    call_method(
+        obj,
+        ObjectMethodTable::get(get_api()).get_instance_id,
+        &[],
+        &mut id as *mut i64 as *mut _,
+    );
+    id
+}
+
+unsafe fn call_method(
+    obj: *mut sys::godot_object,
+    method_bind: *mut sys::godot_method_bind,
+    args: &[*const libc::c_void],
+    ret: *mut libc::c_void,
+) {
+    (get_api().godot_method_bind_ptrcall)(method_bind, obj, args.as_ptr() as *mut _, ret);
+}
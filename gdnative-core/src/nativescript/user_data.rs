@@ -52,6 +52,13 @@
 //! - All your exported methods take `&self`.
 //! - Your `NativeClass` type is `Send + Sync`.
 //!
+//! ### Use a `ReentrantData<T>` when:
+//!
+//! - The engine may call back into the same instance while one of its methods is running, e.g.
+//!   through signals connected in a non-deferred manner.
+//! - All your exported methods take `&self`, and state is mutated through interior mutability.
+//! - Your `NativeClass` type is `Send`.
+//!
 //! ### Use a `LocalCellData<T>` when:
 //!
 //! - Your `NativeClass` type is not `Send`, and you will only ever use it from the thread where
@@ -61,8 +68,14 @@
 //!
 //! - Your `NativeClass` type is a zero-sized type (ZST) that is `Copy + Default`.
 //! - You don't need to do anything special in `Drop`.
+//!
+//! ## Diagnosing lock failures
+//!
+//! Methods can't be called when the wrapper can't be locked or borrowed, which is reported as an
+//! error. To find out who is holding the lock, and how often instances of each class are
+//! contended, enable the `diagnostics` module.
 
-use parking_lot::{Mutex, RwLock};
+use parking_lot::{Mutex, ReentrantMutex, RwLock};
 use std::fmt::Debug;
 use std::marker::PhantomData;
 use std::mem;
@@ -71,6 +84,8 @@ use std::time::Duration;
 
 use crate::nativescript::NativeClass;
 
+pub mod diagnostics;
+
 /// Trait for customizable user-data wrappers.
 ///
 /// See module-level documentation for detailed explanation on user-data.
@@ -170,7 +185,8 @@ impl std::fmt::Display for Infallible {
 /// - Anything that could emit signals, that are connected to in a non-deferred manner.
 ///
 /// As there is no universal way to deal with such situations, behavior of locking wrappers can
-/// be customized using this enum.
+/// be customized using this enum. Alternatively, `ReentrantData` allows such calls from the
+/// thread already holding the lock, in which case the policy only applies to other threads.
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
 pub enum DeadlockPolicy {
     /// Block on all locks. Deadlocks are possible.
@@ -303,19 +319,29 @@ where
     where
         F: FnOnce(&mut T) -> U,
     {
-        let mut guard = match OPT::DEADLOCK_POLICY {
-            DeadlockPolicy::Allow => self.lock.lock(),
-            DeadlockPolicy::Pessimistic => self.lock.try_lock().ok_or(LockFailed::Pessimistic)?,
-            DeadlockPolicy::Timeout(dur) => self
-                .lock
-                .try_lock_for(dur)
-                .ok_or(LockFailed::Timeout(dur))?,
-        };
+        let (mut guard, _hold) = diagnostics::acquire::<T, _, _>(
+            self.address(),
+            || self.lock.try_lock(),
+            || match OPT::DEADLOCK_POLICY {
+                DeadlockPolicy::Allow => Ok(self.lock.lock()),
+                DeadlockPolicy::Pessimistic => self.lock.try_lock().ok_or(LockFailed::Pessimistic),
+                DeadlockPolicy::Timeout(dur) => {
+                    self.lock.try_lock_for(dur).ok_or(LockFailed::Timeout(dur))
+                }
+            },
+        )?;
 
         Ok(op(&mut *guard))
     }
 }
 
+impl<T, OPT> MutexData<T, OPT> {
+    /// Returns the address identifying this wrapper in diagnostics.
+    fn address(&self) -> usize {
+        Arc::as_ptr(&self.lock) as usize
+    }
+}
+
 impl<T, OPT> Clone for MutexData<T, OPT> {
     #[inline]
     fn clone(&self) -> Self {
@@ -388,14 +414,17 @@ where
     where
         F: FnOnce(&T) -> U,
     {
-        let guard = match OPT::DEADLOCK_POLICY {
-            DeadlockPolicy::Allow => self.lock.read(),
-            DeadlockPolicy::Pessimistic => self.lock.try_read().ok_or(LockFailed::Pessimistic)?,
-            DeadlockPolicy::Timeout(dur) => self
-                .lock
-                .try_read_for(dur)
-                .ok_or(LockFailed::Timeout(dur))?,
-        };
+        let (guard, _hold) = diagnostics::acquire::<T, _, _>(
+            self.address(),
+            || self.lock.try_read(),
+            || match OPT::DEADLOCK_POLICY {
+                DeadlockPolicy::Allow => Ok(self.lock.read()),
+                DeadlockPolicy::Pessimistic => self.lock.try_read().ok_or(LockFailed::Pessimistic),
+                DeadlockPolicy::Timeout(dur) => {
+                    self.lock.try_read_for(dur).ok_or(LockFailed::Timeout(dur))
+                }
+            },
+        )?;
 
         Ok(op(&*guard))
     }
@@ -413,19 +442,29 @@ where
     where
         F: FnOnce(&mut T) -> U,
     {
-        let mut guard = match OPT::DEADLOCK_POLICY {
-            DeadlockPolicy::Allow => self.lock.write(),
-            DeadlockPolicy::Pessimistic => self.lock.try_write().ok_or(LockFailed::Pessimistic)?,
-            DeadlockPolicy::Timeout(dur) => self
-                .lock
-                .try_write_for(dur)
-                .ok_or(LockFailed::Timeout(dur))?,
-        };
+        let (mut guard, _hold) = diagnostics::acquire::<T, _, _>(
+            self.address(),
+            || self.lock.try_write(),
+            || match OPT::DEADLOCK_POLICY {
+                DeadlockPolicy::Allow => Ok(self.lock.write()),
+                DeadlockPolicy::Pessimistic => self.lock.try_write().ok_or(LockFailed::Pessimistic),
+                DeadlockPolicy::Timeout(dur) => {
+                    self.lock.try_write_for(dur).ok_or(LockFailed::Timeout(dur))
+                }
+            },
+        )?;
 
         Ok(op(&mut *guard))
     }
 }
 
+impl<T, OPT> RwLockData<T, OPT> {
+    /// Returns the address identifying this wrapper in diagnostics.
+    fn address(&self) -> usize {
+        Arc::as_ptr(&self.lock) as usize
+    }
+}
+
 impl<T, OPT> Clone for RwLockData<T, OPT> {
     #[inline]
     fn clone(&self) -> Self {
@@ -492,6 +531,118 @@ impl<T> Clone for ArcData<T> {
     }
 }
 
+/// User-data wrapper encapsulating a `Arc<ReentrantMutex<T>>`. Does not implement `MapMut`.
+///
+/// Unlike `MutexData`, the lock can be taken again by the thread already holding it, so the
+/// engine can call back into the instance while one of its methods is running, e.g. when it
+/// emits a signal connected to another method of the same instance. Since such calls can only
+/// be given shared references, only methods taking `&self` can be exported, and state has to
+/// be mutated through interior mutability, like `Cell` or `RefCell`.
+///
+/// This wrapper guarantees that:
+///
+/// - Only one thread can access the value at a time. Other threads wait for the lock according
+///   to the `DeadlockPolicy` of `OPT`.
+/// - Re-entrant calls from the thread holding the lock never block or fail, regardless of the
+///   policy and the depth of nesting.
+///
+/// Note that `RefCell` borrows held across calls that may re-enter will make the nested calls
+/// panic, just like with `LocalCellData`. Keep such borrows short.
+///
+/// The underlying `ReentrantMutex` may change in the future. The current implementation is
+/// `parking_lot`.
+#[derive(Debug)]
+pub struct ReentrantData<T, OPT = DefaultLockPolicy> {
+    lock: Arc<ReentrantMutex<T>>,
+    _marker: PhantomData<OPT>,
+}
+
+unsafe impl<T, OPT> UserData for ReentrantData<T, OPT>
+where
+    T: NativeClass + Send,
+    OPT: LockOptions,
+{
+    type Target = T;
+
+    #[inline]
+    fn new(val: Self::Target) -> Self {
+        ReentrantData {
+            lock: Arc::new(ReentrantMutex::new(val)),
+            _marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    fn into_user_data(self) -> *const libc::c_void {
+        Arc::into_raw(self.lock) as *const libc::c_void
+    }
+
+    #[inline]
+    unsafe fn consume_user_data_unchecked(ptr: *const libc::c_void) -> Self {
+        ReentrantData {
+            lock: Arc::from_raw(ptr as *const ReentrantMutex<T>),
+            _marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    unsafe fn clone_from_user_data_unchecked(ptr: *const libc::c_void) -> Self {
+        let borrowed = Arc::from_raw(ptr as *const ReentrantMutex<T>);
+        let lock = borrowed.clone();
+        mem::forget(borrowed);
+        ReentrantData {
+            lock,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T, OPT> Map for ReentrantData<T, OPT>
+where
+    T: NativeClass + Send,
+    OPT: LockOptions,
+{
+    type Err = LockFailed;
+
+    #[inline]
+    fn map<F, U>(&self, op: F) -> Result<U, LockFailed>
+    where
+        F: FnOnce(&T) -> U,
+    {
+        // Locking is re-entrant, so these only fail when another thread holds the lock.
+        let (guard, _hold) = diagnostics::acquire::<T, _, _>(
+            self.address(),
+            || self.lock.try_lock(),
+            || match OPT::DEADLOCK_POLICY {
+                DeadlockPolicy::Allow => Ok(self.lock.lock()),
+                DeadlockPolicy::Pessimistic => self.lock.try_lock().ok_or(LockFailed::Pessimistic),
+                DeadlockPolicy::Timeout(dur) => {
+                    self.lock.try_lock_for(dur).ok_or(LockFailed::Timeout(dur))
+                }
+            },
+        )?;
+
+        Ok(op(&*guard))
+    }
+}
+
+impl<T, OPT> ReentrantData<T, OPT> {
+    /// Returns the address identifying this wrapper in diagnostics.
+    fn address(&self) -> usize {
+        Arc::as_ptr(&self.lock) as usize
+    }
+}
+
+impl<T, OPT> Clone for ReentrantData<T, OPT> {
+    #[inline]
+    fn clone(&self) -> Self {
+        ReentrantData {
+            lock: self.lock.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
 /// User-data wrapper analogous to a `Arc<RefCell<T>>`, that is restricted to the thread
 /// where it was originally created.
 ///
@@ -633,7 +784,12 @@ where
     where
         F: FnOnce(&Self::Target) -> U,
     {
-        self.inner.try_borrow().map(|r| op(&*r))
+        let (r, _hold) = diagnostics::acquire::<T, _, _>(
+            self.address(),
+            || self.inner.try_borrow().ok(),
+            || self.inner.try_borrow(),
+        )?;
+        Ok(op(&*r))
     }
 }
 
@@ -648,7 +804,19 @@ where
     where
         F: FnOnce(&mut Self::Target) -> U,
     {
-        self.inner.try_borrow_mut().map(|mut w| op(&mut *w))
+        let (mut w, _hold) = diagnostics::acquire::<T, _, _>(
+            self.address(),
+            || self.inner.try_borrow_mut().ok(),
+            || self.inner.try_borrow_mut(),
+        )?;
+        Ok(op(&mut *w))
+    }
+}
+
+impl<T> LocalCellData<T> {
+    /// Returns the address identifying this wrapper in diagnostics.
+    fn address(&self) -> usize {
+        Arc::as_ptr(&self.inner) as usize
     }
 }
 
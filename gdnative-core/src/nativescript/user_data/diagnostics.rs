@@ -0,0 +1,399 @@
+//! Lock contention and borrow failure diagnostics for user-data wrappers.
+//!
+//! When a method of a script class can't be called because its user-data wrapper couldn't be
+//! locked or borrowed, the method wrapper can only report the error and return `Nil`. With
+//! diagnostics enabled, `MutexData`, `RwLockData`, `ReentrantData` and `LocalCellData` also
+//! record who holds them: the class, the exported method being called, the thread and, with
+//! the `backtrace` feature, a short backtrace of where the lock was taken. These holders are
+//! included in the error reported for the failed call.
+//!
+//! Contention is also counted per class. Time spent waiting for a contended lock is added to
+//! Godot's built-in profiler, under the name of the class and the method waiting for it.
+//!
+//! Diagnostics are disabled by default, since recording holders has a cost on every call:
+//!
+//! ```
+//! use gdnative_core::nativescript::user_data::diagnostics;
+//!
+//! diagnostics::set_enabled(true);
+//!
+//! // ...
+//!
+//! for (class_name, contention) in diagnostics::contention_by_class() {
+//!     println!("{}: {:?}", class_name, contention);
+//! }
+//! ```
+
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::thread::{self, ThreadId};
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+
+use crate::nativescript::profiling::{self, Signature};
+use crate::nativescript::NativeClass;
+use crate::private::to_c_string_escaped;
+
+/// Number of frames shown in the backtraces of lock holders.
+#[cfg(feature = "backtrace")]
+const SHORT_BACKTRACE_FRAMES: usize = 8;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Source of unique IDs for `Hold`.
+static NEXT_HOLD_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Locks currently held, as `(hold ID, wrapper address, holder)`. Only a few locks are held at
+/// a time, so a `Vec` is enough.
+static HOLDERS: Mutex<Vec<(u64, usize, LockHolder)>> = parking_lot::const_mutex(Vec::new());
+
+/// Contention counters by class name.
+static CONTENTION: Mutex<Vec<(&'static str, Contention)>> = parking_lot::const_mutex(Vec::new());
+
+thread_local! {
+    static CURRENT_METHOD: Cell<Option<&'static str>> = Cell::new(None);
+    static LAST_FAILURE: RefCell<Option<LockFailure>> = RefCell::new(None);
+}
+
+/// Enables or disables diagnostics for all user-data wrappers.
+///
+/// Locks already held when diagnostics are enabled aren't recorded.
+#[inline]
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Release);
+}
+
+/// Returns whether diagnostics are enabled.
+#[inline]
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Acquire)
+}
+
+/// Contention counters of a class, recorded while diagnostics are enabled.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct Contention {
+    /// Number of times a lock or borrow couldn't be obtained right away.
+    pub contended: u64,
+    /// Number of times a lock or borrow couldn't be obtained at all, according to the
+    /// `DeadlockPolicy` of the wrapper.
+    pub failed: u64,
+    /// Total time spent waiting for contended locks.
+    pub wait_time: Duration,
+}
+
+/// Returns the contention counters of the class `C`.
+#[inline]
+pub fn contention<C: NativeClass>() -> Contention {
+    CONTENTION
+        .lock()
+        .iter()
+        .find(|(class_name, _)| *class_name == C::class_name())
+        .map(|(_, contention)| *contention)
+        .unwrap_or_default()
+}
+
+/// Returns the contention counters of all classes that saw any contention.
+#[inline]
+pub fn contention_by_class() -> Vec<(&'static str, Contention)> {
+    CONTENTION.lock().clone()
+}
+
+/// Resets the contention counters of all classes.
+#[inline]
+pub fn reset_contention() {
+    CONTENTION.lock().clear();
+}
+
+/// Removes and returns the last lock or borrow failure recorded on the current thread.
+///
+/// Method wrappers generated by `godot_wrap_method!` include it in their error reports.
+#[inline]
+pub fn take_last_failure() -> Option<LockFailure> {
+    LAST_FAILURE.with(|failure| failure.borrow_mut().take())
+}
+
+/// Information about a holder of a lock or borrow of a user-data wrapper.
+#[derive(Clone, Debug)]
+pub struct LockHolder {
+    class_name: &'static str,
+    method: Option<&'static str>,
+    thread_id: ThreadId,
+    thread_name: Option<String>,
+    #[cfg(feature = "backtrace")]
+    backtrace: backtrace_rs::Backtrace,
+}
+
+impl LockHolder {
+    fn current<C: NativeClass>() -> Self {
+        let thread = thread::current();
+        LockHolder {
+            class_name: C::class_name(),
+            method: CURRENT_METHOD.with(Cell::get),
+            thread_id: thread.id(),
+            thread_name: thread.name().map(String::from),
+            #[cfg(feature = "backtrace")]
+            backtrace: backtrace_rs::Backtrace::new_unresolved(),
+        }
+    }
+
+    /// Returns the name of the class.
+    #[inline]
+    pub fn class_name(&self) -> &'static str {
+        self.class_name
+    }
+
+    /// Returns the name of the exported method that took the lock, if it was taken by a method
+    /// wrapper.
+    #[inline]
+    pub fn method(&self) -> Option<&'static str> {
+        self.method
+    }
+
+    /// Returns the ID of the thread that took the lock.
+    #[inline]
+    pub fn thread_id(&self) -> ThreadId {
+        self.thread_id
+    }
+
+    /// Returns the name of the thread that took the lock, if it has one.
+    #[inline]
+    pub fn thread_name(&self) -> Option<&str> {
+        self.thread_name.as_deref()
+    }
+}
+
+impl fmt::Display for LockHolder {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_call(f, self.class_name, self.method)?;
+        match &self.thread_name {
+            Some(name) => write!(f, " on thread {:?} ({:?})", name, self.thread_id)?,
+            None => write!(f, " on thread {:?}", self.thread_id)?,
+        }
+
+        #[cfg(feature = "backtrace")]
+        write_short_backtrace(f, &self.backtrace)?;
+
+        Ok(())
+    }
+}
+
+/// A lock or borrow failure, with the holders at the time of the failure.
+#[derive(Clone, Debug)]
+pub struct LockFailure {
+    class_name: &'static str,
+    method: Option<&'static str>,
+    holders: Vec<LockHolder>,
+}
+
+impl LockFailure {
+    /// Returns the name of the class.
+    #[inline]
+    pub fn class_name(&self) -> &'static str {
+        self.class_name
+    }
+
+    /// Returns the name of the exported method that couldn't be called, if the lock was
+    /// requested by a method wrapper.
+    #[inline]
+    pub fn method(&self) -> Option<&'static str> {
+        self.method
+    }
+
+    /// Returns the recorded holders of the lock. This may be empty if the lock was taken
+    /// before diagnostics were enabled.
+    #[inline]
+    pub fn holders(&self) -> &[LockHolder] {
+        &self.holders
+    }
+}
+
+impl fmt::Display for LockFailure {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "could not lock the instance for ")?;
+        write_call(f, self.class_name, self.method)?;
+
+        if self.holders.is_empty() {
+            write!(f, ", no holders were recorded")
+        } else {
+            write!(f, ", held by:")?;
+            for holder in &self.holders {
+                write!(f, "\n  - {}", holder)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+fn write_call(f: &mut fmt::Formatter<'_>, class_name: &str, method: Option<&str>) -> fmt::Result {
+    match method {
+        Some(method) => write!(f, "{}::{}", class_name, method),
+        None => write!(f, "{}", class_name),
+    }
+}
+
+#[cfg(feature = "backtrace")]
+fn write_short_backtrace(
+    f: &mut fmt::Formatter<'_>,
+    backtrace: &backtrace_rs::Backtrace,
+) -> fmt::Result {
+    let mut backtrace = backtrace.clone();
+    backtrace.resolve();
+
+    // Frames of the backtrace crate and the wrappers themselves are skipped.
+    let names = backtrace
+        .frames()
+        .iter()
+        .flat_map(|frame| frame.symbols())
+        .filter_map(|symbol| symbol.name().map(|name| format!("{:#}", name)))
+        .skip_while(|name| {
+            let path = name.trim_start_matches('<');
+            path.starts_with("backtrace::")
+                || path.starts_with("gdnative_core::nativescript::user_data")
+                || path.starts_with("gdnative_core::nativescript::class")
+        })
+        .take(SHORT_BACKTRACE_FRAMES);
+
+    for name in names {
+        write!(f, "\n      at {}", name)?;
+    }
+
+    Ok(())
+}
+
+/// Guard naming the exported method being called on the current thread, for the holders
+/// recorded while it's alive.
+#[doc(hidden)]
+pub struct MethodScope {
+    previous: Option<&'static str>,
+}
+
+impl Drop for MethodScope {
+    #[inline]
+    fn drop(&mut self) {
+        CURRENT_METHOD.with(|method| method.set(self.previous));
+    }
+}
+
+/// Names the exported method being called on the current thread until the returned guard is
+/// dropped, and discards any failure left from earlier calls.
+///
+/// This is intended to be an internal interface.
+#[doc(hidden)]
+#[inline]
+pub fn enter_method(name: &'static str) -> MethodScope {
+    LAST_FAILURE.with(|failure| *failure.borrow_mut() = None);
+    MethodScope {
+        previous: CURRENT_METHOD.with(|method| method.replace(Some(name))),
+    }
+}
+
+/// Returns the exported method being called on the current thread, if any.
+#[inline]
+pub(crate) fn current_method() -> Option<&'static str> {
+    CURRENT_METHOD.with(Cell::get)
+}
+
+/// Recorded holder of a lock, removed when dropped.
+pub(super) struct Hold(Option<u64>);
+
+impl Drop for Hold {
+    #[inline]
+    fn drop(&mut self) {
+        if let Some(id) = self.0 {
+            let mut holders = HOLDERS.lock();
+            if let Some(idx) = holders.iter().position(|(hold_id, _, _)| *hold_id == id) {
+                holders.swap_remove(idx);
+            }
+        }
+    }
+}
+
+/// Obtains a lock or borrow of the wrapper at `address` with `acquire`, recording diagnostics
+/// for the class `C` if enabled. `try_acquire` must not block, and is used to detect
+/// contention.
+#[inline]
+pub(super) fn acquire<C, G, E>(
+    address: usize,
+    try_acquire: impl FnOnce() -> Option<G>,
+    acquire: impl FnOnce() -> Result<G, E>,
+) -> Result<(G, Hold), E>
+where
+    C: NativeClass,
+{
+    if !is_enabled() {
+        return acquire().map(|guard| (guard, Hold(None)));
+    }
+
+    if let Some(guard) = try_acquire() {
+        return Ok((guard, hold::<C>(address)));
+    }
+
+    let start = Instant::now();
+    let result = acquire();
+    let wait_time = start.elapsed();
+
+    count::<C>(|contention| {
+        contention.contended += 1;
+        if result.is_err() {
+            contention.failed += 1;
+        }
+        contention.wait_time += wait_time;
+    });
+    add_profiling_data::<C>(wait_time);
+
+    match result {
+        Ok(guard) => Ok((guard, hold::<C>(address))),
+        Err(err) => {
+            let failure = LockFailure {
+                class_name: C::class_name(),
+                method: CURRENT_METHOD.with(Cell::get),
+                holders: HOLDERS
+                    .lock()
+                    .iter()
+                    .filter(|(_, holder_address, _)| *holder_address == address)
+                    .map(|(_, _, holder)| holder.clone())
+                    .collect(),
+            };
+            LAST_FAILURE.with(|last| *last.borrow_mut() = Some(failure));
+            Err(err)
+        }
+    }
+}
+
+fn hold<C: NativeClass>(address: usize) -> Hold {
+    let id = NEXT_HOLD_ID.fetch_add(1, Ordering::Relaxed);
+    let holder = LockHolder::current::<C>();
+    HOLDERS.lock().push((id, address, holder));
+    Hold(Some(id))
+}
+
+fn count<C: NativeClass>(f: impl FnOnce(&mut Contention)) {
+    let mut counters = CONTENTION.lock();
+    match counters
+        .iter_mut()
+        .find(|(class_name, _)| *class_name == C::class_name())
+    {
+        Some((_, contention)) => f(contention),
+        None => {
+            let mut contention = Contention::default();
+            f(&mut contention);
+            counters.push((C::class_name(), contention));
+        }
+    }
+}
+
+fn add_profiling_data<C: NativeClass>(wait_time: Duration) {
+    // Signatures are in the form of `{file}::{line}::{tag}`, so `::` can't be used in the parts.
+    let class_name = C::class_name().replace("::", "_");
+    let tag = match CURRENT_METHOD.with(Cell::get) {
+        Some(method) => format!("{} (lock wait)", method),
+        None => "lock wait".to_owned(),
+    };
+
+    let sig = to_c_string_escaped(&format!("{}::0::{}", class_name, tag));
+    profiling::add_data(Signature::from_raw_owned(sig), wait_time);
+}
@@ -0,0 +1,166 @@
+//! Compile-time checks for exported methods overriding virtual engine callbacks.
+//!
+//! Godot calls virtual methods like `_process` or `_input` on scripts by name. If an exported
+//! method has the right name but the wrong arguments, the engine call fails at runtime. The
+//! bindings generator emits a trait for each class with virtual methods, e.g. `NodeVirtuals`,
+//! which `#[methods]` uses to check the signatures of exported methods with matching names.
+//!
+//! The types in this module are implementation details of that check, and are not meant to be
+//! used directly.
+
+use std::marker::PhantomData;
+
+use crate::core_types::*;
+use crate::object::{GodotObject, Ref, SubClass};
+use crate::thread_access::{Shared, ThreadAccess};
+
+/// Probe type used to look up virtual methods of the base class `T` during method resolution.
+///
+/// The generated `*Virtuals` traits are implemented for `VirtualProbe<T>` where `T` is a
+/// subclass of the class declaring the virtual methods. A fallback trait is implemented for
+/// `&VirtualProbe<T>`, so that method names that are virtual in unrelated classes are accepted
+/// without any checks.
+#[doc(hidden)]
+pub struct VirtualProbe<T>(PhantomData<fn() -> T>);
+
+impl<T> VirtualProbe<T> {
+    #[inline]
+    pub fn new() -> Self {
+        VirtualProbe(PhantomData)
+    }
+}
+
+impl<T> Default for VirtualProbe<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Argument types `A` and return type `R` of an exported method, as a value.
+#[doc(hidden)]
+pub struct VirtualSignature<A, R>(PhantomData<fn(A) -> R>);
+
+impl<A, R> VirtualSignature<A, R> {
+    #[inline]
+    pub fn new() -> Self {
+        VirtualSignature(PhantomData)
+    }
+}
+
+impl<A, R> Default for VirtualSignature<A, R> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Types that can be used for an argument declared as `E` by a virtual method.
+pub trait VirtualArg<E> {}
+
+/// Tuples of types that can be used for the arguments `E` of a virtual method, excluding
+/// `self` and `owner`.
+pub trait VirtualArgs<E> {}
+
+/// Types that can be returned from a virtual method declared to return `E`.
+pub trait VirtualReturn<E> {}
+
+macro_rules! impl_virtual_arg {
+    ($($engine:ty => $($ty:ty),+;)*) => {
+        $($(
+            impl VirtualArg<$engine> for $ty {}
+            impl VirtualReturn<$engine> for $ty {}
+        )+)*
+    };
+}
+
+impl_virtual_arg! {
+    bool => bool;
+    i64 => i64, i32, i16, i8, isize, u64, u32, u16, u8, usize;
+    f64 => f64, f32;
+    GodotString => GodotString, String;
+    Vector2 => Vector2;
+    Vector3 => Vector3;
+    vector3::Axis => vector3::Axis;
+    Quat => Quat;
+    Transform => Transform;
+    Transform2D => Transform2D;
+    Rect2 => Rect2;
+    Plane => Plane;
+    Basis => Basis;
+    Color => Color;
+    NodePath => NodePath;
+    Aabb => Aabb;
+    Rid => Rid;
+    ByteArray => ByteArray;
+    StringArray => StringArray;
+    Vector2Array => Vector2Array;
+    Vector3Array => Vector3Array;
+    ColorArray => ColorArray;
+    Int32Array => Int32Array;
+    Float32Array => Float32Array;
+    VariantType => VariantType;
+    VariantOperator => VariantOperator;
+}
+
+impl<E> VirtualArg<E> for Variant {}
+impl VirtualArg<VariantArray> for VariantArray {}
+impl VirtualArg<Dictionary> for Dictionary {}
+
+impl<T, U> VirtualArg<Option<Ref<T, Shared>>> for Option<Ref<U, Shared>>
+where
+    T: GodotObject + SubClass<U>,
+    U: GodotObject,
+{
+}
+
+impl<T, U> VirtualArg<Option<Ref<T, Shared>>> for Ref<U, Shared>
+where
+    T: GodotObject + SubClass<U>,
+    U: GodotObject,
+{
+}
+
+impl<T: OwnedToVariant> VirtualReturn<()> for T {}
+impl<T: OwnedToVariant> VirtualReturn<Variant> for T {}
+
+// Many types convert to arrays or dictionaries, like `Vec<T>` or structs deriving `ToVariant`,
+// but which one isn't known at the type level. Any return type is accepted, like for `Variant`.
+impl<T: OwnedToVariant> VirtualReturn<VariantArray> for T {}
+impl<T: OwnedToVariant> VirtualReturn<Dictionary> for T {}
+
+impl<T, U> VirtualReturn<Option<Ref<T, Shared>>> for Option<Ref<U, Shared>>
+where
+    T: GodotObject,
+    U: GodotObject + SubClass<T>,
+{
+}
+
+impl<T, U, Access> VirtualReturn<Option<Ref<T, Shared>>> for Ref<U, Access>
+where
+    T: GodotObject,
+    U: GodotObject + SubClass<T>,
+    Access: ThreadAccess,
+    Ref<U, Access>: OwnedToVariant,
+{
+}
+
+macro_rules! impl_virtual_args {
+    ($($ty:ident : $engine:ident),*) => {
+        impl<$($ty, $engine,)*> VirtualArgs<($($engine,)*)> for ($($ty,)*)
+        where
+            $($ty: VirtualArg<$engine>,)*
+        {
+        }
+    };
+}
+
+impl_virtual_args!();
+impl_virtual_args!(A0: E0);
+impl_virtual_args!(A0: E0, A1: E1);
+impl_virtual_args!(A0: E0, A1: E1, A2: E2);
+impl_virtual_args!(A0: E0, A1: E1, A2: E2, A3: E3);
+impl_virtual_args!(A0: E0, A1: E1, A2: E2, A3: E3, A4: E4);
+impl_virtual_args!(A0: E0, A1: E1, A2: E2, A3: E3, A4: E4, A5: E5);
+impl_virtual_args!(A0: E0, A1: E1, A2: E2, A3: E3, A4: E4, A5: E5, A6: E6);
+impl_virtual_args!(A0: E0, A1: E1, A2: E2, A3: E3, A4: E4, A5: E5, A6: E6, A7: E7);
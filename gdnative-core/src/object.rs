@@ -9,15 +9,20 @@ use crate::private::{get_api, ReferenceCountedClassPlaceholder};
 use crate::ref_kind::{ManuallyManaged, RefCounted, RefKind};
 use crate::sys;
 use crate::thread_access::{
-    LocalThreadAccess, NonUniqueThreadAccess, Shared, ThreadAccess, ThreadLocal, Unique,
+    AnyThread, LocalThreadAccess, MainThreadToken, NonUniqueThreadAccess, Shared, ThreadAccess,
+    ThreadLocal, Unique,
 };
 
 #[cfg(feature = "nativescript")]
 use crate::nativescript::{Instance, NativeClass, RefInstance};
 
+mod call;
 mod raw;
+mod weak;
 
+pub use self::call::{CallArgs, Callable, MethodCallError};
 pub use self::raw::RawObject;
+pub use self::weak::WeakObject;
 
 /// Trait for Godot API objects. This trait is sealed, and implemented for generated wrapper
 /// types.
@@ -160,7 +165,10 @@ pub unsafe trait GodotObject: Sized + crate::private::godot_object::Sealed {
     /// During the entirety of `'a`, the thread from which `try_from_instance_id` is called must
     /// have exclusive access to the underlying object, if it is still alive.
     #[inline]
-    unsafe fn try_from_instance_id<'a>(id: i64) -> Option<TRef<'a, Self, Shared>> {
+    unsafe fn try_from_instance_id<'a>(id: i64) -> Option<TRef<'a, Self, Shared>>
+    where
+        Self: AnyThread,
+    {
         TRef::try_from_instance_id(id)
     }
 
@@ -178,7 +186,10 @@ pub unsafe trait GodotObject: Sized + crate::private::godot_object::Sealed {
     /// During the entirety of `'a`, the thread from which `try_from_instance_id` is called must
     /// have exclusive access to the underlying object, if it is still alive.
     #[inline]
-    unsafe fn from_instance_id<'a>(id: i64) -> TRef<'a, Self, Shared> {
+    unsafe fn from_instance_id<'a>(id: i64) -> TRef<'a, Self, Shared>
+    where
+        Self: AnyThread,
+    {
         TRef::from_instance_id(id)
     }
 }
@@ -252,7 +263,8 @@ pub trait QueueFree: GodotObject {
 /// | - | - | - | - |
 /// | `Unique` | `&'a T` | `Deref` (API methods can be called directly) / `as_ref` | - |
 /// | `ThreadLocal` | `&'a T` | `Deref` (API methods can be called directly) / `as_ref` | Only if `T` is a reference-counted type. |
-/// | `Shared` | `&'a T` | `unsafe assume_safe::<'a>` | The underlying object must be valid, and exclusive to this thread during `'a`. |
+/// | `Shared` | `&'a T` | `unsafe assume_safe::<'a>` | The underlying object must be valid, and exclusive to this thread during `'a`. Only if `T` can be used from any thread. |
+/// | `Shared` | `&'a T` | `unsafe assume_safe_on_main_thread::<'a>` | Same as `assume_safe`, but requires a `MainThreadToken` instead. |
 /// | `Unique` | `ThreadLocal` | `into_thread_local` | - |
 /// | `Unique` | `Shared` | `into_shared` | - |
 /// | `Shared` | `ThreadLocal` | `unsafe assume_thread_local` | The reference must be local to the current thread. |
@@ -509,9 +521,32 @@ impl<T: GodotObject> Ref<T, Shared> {
     ///
     /// Failure to satisfy either of the conditions will lead to undefined behavior.
     ///
+    /// This is only available for classes that can be used from any thread. Use
+    /// `assume_safe_on_main_thread` for nodes and other `MainThreadOnly` classes.
+    ///
     /// [thread-safety]: https://docs.godotengine.org/en/stable/tutorials/threads/thread_safe_apis.html
     #[inline(always)]
     pub unsafe fn assume_safe<'a, 'r>(&'r self) -> TRef<'a, T, Shared>
+    where
+        T: AnyThread,
+        AssumeSafeLifetime<'a, 'r>: LifetimeConstraint<T::RefKind>,
+    {
+        T::RefKind::impl_assume_safe(self)
+    }
+
+    /// Assume that `self` is safe to use on the main thread, returning a reference that can be
+    /// used to call API methods. Unlike `assume_safe`, this is available for all classes,
+    /// including `MainThreadOnly` ones like nodes.
+    ///
+    /// # Safety
+    ///
+    /// The same safety constraints as `assume_safe` apply. The token guarantees that the
+    /// current thread is the main thread, but not that it has exclusive access to the object.
+    #[inline(always)]
+    pub unsafe fn assume_safe_on_main_thread<'a, 'r>(
+        &'r self,
+        _token: MainThreadToken,
+    ) -> TRef<'a, T, Shared>
     where
         AssumeSafeLifetime<'a, 'r>: LifetimeConstraint<T::RefKind>,
     {
@@ -539,6 +574,9 @@ impl<T: GodotObject<RefKind = ManuallyManaged>> Ref<T, Shared> {
     /// Returns `true` if the pointer currently points to a valid object of the correct type.
     /// **This does NOT guarantee that it's safe to use this pointer.**
     ///
+    /// Always returns `false` if the engine doesn't provide the core 1.1 API, since the check
+    /// can't be performed.
+    ///
     /// # Safety
     ///
     /// This thread must have exclusive access to the object during the call.
@@ -546,7 +584,7 @@ impl<T: GodotObject<RefKind = ManuallyManaged>> Ref<T, Shared> {
     #[allow(clippy::trivially_copy_pass_by_ref)]
     pub unsafe fn is_instance_sane(&self) -> bool {
         let api = get_api();
-        if !(api.godot_is_instance_valid)(self.as_ptr()) {
+        if !api.has_core_1_1() || !(api.godot_is_instance_valid)(self.as_ptr()) {
             return false;
         }
 
@@ -561,7 +599,10 @@ impl<T: GodotObject<RefKind = ManuallyManaged>> Ref<T, Shared> {
     /// guarantee that the operation is safe.**
     #[inline]
     #[allow(clippy::trivially_copy_pass_by_ref)]
-    pub unsafe fn assume_safe_if_sane<'a>(&self) -> Option<TRef<'a, T, Shared>> {
+    pub unsafe fn assume_safe_if_sane<'a>(&self) -> Option<TRef<'a, T, Shared>>
+    where
+        T: AnyThread,
+    {
         if self.is_instance_sane() {
             Some(self.assume_safe_unchecked())
         } else {
@@ -922,9 +963,12 @@ where
     }
 }
 
-impl<'a, T: GodotObject> TRef<'a, T, Shared> {
+impl<'a, T: GodotObject + AnyThread> TRef<'a, T, Shared> {
     /// Recovers a instance ID previously returned by `Object::get_instance_id` if the object is
-    /// still alive.
+    /// still alive. Always returns `None` if the engine doesn't provide the core 1.2 API.
+    ///
+    /// This is only available for classes that can be used from any thread. For nodes and
+    /// other `MainThreadOnly` classes, use `WeakObject::upgrade_on_main_thread` instead.
     ///
     /// # Safety
     ///
@@ -933,6 +977,9 @@ impl<'a, T: GodotObject> TRef<'a, T, Shared> {
     #[inline]
     pub unsafe fn try_from_instance_id(id: i64) -> Option<Self> {
         let api = get_api();
+        if !api.has_core_1_2() {
+            return None;
+        }
         let ptr = NonNull::new((api.godot_instance_from_id)(id as sys::godot_int))?;
         let raw = RawObject::try_from_sys_ref(ptr)?;
         Some(TRef::new(T::cast_ref(raw)))
@@ -1163,8 +1210,9 @@ impl RefKindSpec for ManuallyManaged {
     unsafe fn impl_assume_safe<'a, T: GodotObject<RefKind = Self>>(
         this: &Ref<T, Shared>,
     ) -> TRef<'a, T, Shared> {
+        // The check isn't possible without core 1.1.
         debug_assert!(
-            this.is_instance_sane(),
+            !get_api().has_core_1_1() || this.is_instance_sane(),
             "assume_safe called on an invalid pointer"
         );
         this.assume_safe_unchecked()
@@ -1174,8 +1222,9 @@ impl RefKindSpec for ManuallyManaged {
     unsafe fn impl_assume_unique<T: GodotObject<RefKind = Self>>(
         this: Ref<T, Shared>,
     ) -> Ref<T, Unique> {
+        // The check isn't possible without core 1.1.
         debug_assert!(
-            this.is_instance_sane(),
+            !get_api().has_core_1_1() || this.is_instance_sane(),
             "assume_unique called on an invalid pointer"
         );
         this.cast_access()
@@ -0,0 +1,375 @@
+use std::fmt;
+
+use crate::core_types::{
+    CallError, FromVariant, FromVariantError, GodotString, OwnedToVariant, Variant, VariantType,
+};
+use crate::sys;
+use crate::thread_access::{Shared, ThreadAccess};
+
+use super::{GodotObject, TRef};
+
+/// Methods that are refused by `TRef::call_typed` and `Callable`, since they would free the
+/// object while it's still referenced, or call arbitrary methods including `free`. This only
+/// catches common mistakes: other methods, especially script methods, can do the same.
+const UNSAFE_METHODS: &[&str] = &["free", "call", "callv", "call_deferred"];
+
+/// Maximum number of arguments of a deferred call, `VARIANT_ARG_MAX` in the engine.
+const MAX_DEFERRED_ARGS: usize = 5;
+
+/// Arguments of a dynamic method call, e.g. with `TRef::call_typed`.
+///
+/// Implemented for tuples of up to 12 values that can be converted to `Variant`s, and for
+/// slices and `Vec`s of `Variant`s.
+pub trait CallArgs {
+    /// Converts the arguments to `Variant`s.
+    fn into_variants(self) -> Vec<Variant>;
+}
+
+impl CallArgs for Vec<Variant> {
+    #[inline]
+    fn into_variants(self) -> Vec<Variant> {
+        self
+    }
+}
+
+impl CallArgs for &[Variant] {
+    #[inline]
+    fn into_variants(self) -> Vec<Variant> {
+        self.to_vec()
+    }
+}
+
+macro_rules! impl_call_args_for_tuple {
+    ($($name:ident),*) => {
+        impl<$($name: OwnedToVariant),*> CallArgs for ($($name,)*) {
+            #[inline]
+            #[allow(non_snake_case)]
+            fn into_variants(self) -> Vec<Variant> {
+                let ($($name,)*) = self;
+                vec![$($name.owned_to_variant()),*]
+            }
+        }
+    };
+}
+
+impl_call_args_for_tuple!();
+impl_call_args_for_tuple!(A);
+impl_call_args_for_tuple!(A, B);
+impl_call_args_for_tuple!(A, B, C);
+impl_call_args_for_tuple!(A, B, C, D);
+impl_call_args_for_tuple!(A, B, C, D, E);
+impl_call_args_for_tuple!(A, B, C, D, E, F);
+impl_call_args_for_tuple!(A, B, C, D, E, F, G);
+impl_call_args_for_tuple!(A, B, C, D, E, F, G, H);
+impl_call_args_for_tuple!(A, B, C, D, E, F, G, H, I);
+impl_call_args_for_tuple!(A, B, C, D, E, F, G, H, I, J);
+impl_call_args_for_tuple!(A, B, C, D, E, F, G, H, I, J, K);
+impl_call_args_for_tuple!(A, B, C, D, E, F, G, H, I, J, K, L);
+
+/// Error returned by the typed method calls of `TRef` and `Callable`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum MethodCallError {
+    /// The object has no method with the name.
+    InvalidMethod { method: String },
+    /// The argument at `index` isn't of a type accepted by the method.
+    InvalidArgument {
+        method: String,
+        index: usize,
+        expected: VariantType,
+    },
+    /// More arguments were given than the method accepts.
+    TooManyArguments { method: String, expected: usize },
+    /// Fewer arguments were given than the method requires.
+    TooFewArguments { method: String, expected: usize },
+    /// The object was null.
+    InstanceIsNull { method: String },
+    /// The returned value couldn't be converted to the expected type.
+    InvalidReturn {
+        method: String,
+        error: FromVariantError,
+    },
+    /// The method is refused, e.g. `free`.
+    UnsafeMethod { method: String },
+}
+
+impl MethodCallError {
+    /// Returns the name of the method that was called.
+    #[inline]
+    pub fn method(&self) -> &str {
+        use MethodCallError::*;
+        match self {
+            InvalidMethod { method }
+            | InvalidArgument { method, .. }
+            | TooManyArguments { method, .. }
+            | TooFewArguments { method, .. }
+            | InstanceIsNull { method }
+            | InvalidReturn { method, .. }
+            | UnsafeMethod { method } => method,
+        }
+    }
+
+    /// Returns the kind of the error as reported by `Variant::call`, if the error was reported
+    /// by the engine.
+    #[inline]
+    pub fn call_error(&self) -> Option<CallError> {
+        use MethodCallError::*;
+        match self {
+            InvalidMethod { .. } => Some(CallError::InvalidMethod),
+            InvalidArgument { .. } => Some(CallError::InvalidArgument),
+            TooManyArguments { .. } => Some(CallError::TooManyArguments),
+            TooFewArguments { .. } => Some(CallError::TooFewArguments),
+            InstanceIsNull { .. } => Some(CallError::InstanceIsNull),
+            InvalidReturn { .. } | UnsafeMethod { .. } => None,
+        }
+    }
+
+    fn from_sys(method: String, err: sys::godot_variant_call_error) -> Self {
+        let error = CallError::from_sys(err.error).expect_err("call should have failed");
+        let argument = err.argument as usize;
+
+        match error {
+            CallError::InvalidMethod => MethodCallError::InvalidMethod { method },
+            CallError::InvalidArgument => MethodCallError::InvalidArgument {
+                method,
+                index: argument,
+                expected: VariantType::from_sys(err.expected),
+            },
+            CallError::TooManyArguments => MethodCallError::TooManyArguments {
+                method,
+                expected: argument,
+            },
+            CallError::TooFewArguments => MethodCallError::TooFewArguments {
+                method,
+                expected: argument,
+            },
+            CallError::InstanceIsNull => MethodCallError::InstanceIsNull { method },
+        }
+    }
+}
+
+impl fmt::Display for MethodCallError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use MethodCallError::*;
+        match self {
+            InvalidMethod { method } => write!(f, "method {} not found", method),
+            InvalidArgument {
+                method,
+                index,
+                expected,
+            } => write!(
+                f,
+                "invalid argument {} for method {}, expected {:?}",
+                index, method, expected
+            ),
+            TooManyArguments { method, expected } => write!(
+                f,
+                "too many arguments for method {}, expected {}",
+                method, expected
+            ),
+            TooFewArguments { method, expected } => write!(
+                f,
+                "too few arguments for method {}, expected {}",
+                method, expected
+            ),
+            InstanceIsNull { method } => {
+                write!(f, "instance is null when calling method {}", method)
+            }
+            InvalidReturn { method, error } => {
+                write!(f, "invalid return value of method {}: {}", method, error)
+            }
+            UnsafeMethod { method } => write!(f, "method {} can't be called dynamically", method),
+        }
+    }
+}
+
+impl std::error::Error for MethodCallError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            MethodCallError::InvalidReturn { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl<'a, T: GodotObject, Access: ThreadAccess> TRef<'a, T, Access> {
+    /// Calls the method `method` with `args` dynamically, like `Object::call`, and converts
+    /// the result to `R`. Methods of scripts attached to the object can be called this way.
+    ///
+    /// Unlike `Object::call`, the arguments and the result are converted and checked. `free`
+    /// and the dynamic call methods like `call` are refused, but other methods aren't checked.
+    /// The call is typed, but it isn't safe: like `Object::call`, it's `unsafe`, since the
+    /// called method can run arbitrary code. There is no safe way to call methods dynamically.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let sum = unsafe { node.call_typed::<i64>("add", (1, 2))? };
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the method doesn't exist, if the arguments aren't accepted by the
+    /// method, or if the result can't be converted to `R`.
+    ///
+    /// # Safety
+    ///
+    /// The called method, including any script code it runs, must not free this object or any
+    /// other object that is still referenced, e.g. through a `TRef`, and must not use objects
+    /// from threads they can't be used from. The same applies to `Object::call`.
+    #[inline]
+    pub unsafe fn call_typed<R: FromVariant>(
+        self,
+        method: &str,
+        args: impl CallArgs,
+    ) -> Result<R, MethodCallError> {
+        if UNSAFE_METHODS.contains(&method) {
+            return Err(MethodCallError::UnsafeMethod {
+                method: method.into(),
+            });
+        }
+
+        let args = args.into_variants();
+
+        let mut object = Variant::from_object_ptr(self.as_ptr());
+        let ret = object
+            .call_raw(&GodotString::from_str(method), &args)
+            .map_err(|err| MethodCallError::from_sys(method.into(), err))?;
+
+        R::from_variant(&ret).map_err(|error| MethodCallError::InvalidReturn {
+            method: method.into(),
+            error,
+        })
+    }
+
+    /// Calls the method `method` with `args` during idle time, like `Object::call_deferred`.
+    /// At most 5 arguments can be passed to a deferred call.
+    ///
+    /// Since the method isn't called immediately, only errors that can be detected in advance
+    /// are reported: methods that don't exist, too many arguments, and refused methods.
+    ///
+    /// # Safety
+    ///
+    /// See `call_typed`. The method is called when the engine processes deferred calls, and
+    /// must not free objects that are referenced at that point.
+    #[inline]
+    pub unsafe fn call_deferred_typed(
+        self,
+        method: &str,
+        args: impl CallArgs,
+    ) -> Result<(), MethodCallError> {
+        if UNSAFE_METHODS.contains(&method) {
+            return Err(MethodCallError::UnsafeMethod {
+                method: method.into(),
+            });
+        }
+
+        let mut object = Variant::from_object_ptr(self.as_ptr());
+        if !object.has_method(method) {
+            return Err(MethodCallError::InvalidMethod {
+                method: method.into(),
+            });
+        }
+
+        let args = args.into_variants();
+        if args.len() > MAX_DEFERRED_ARGS {
+            return Err(MethodCallError::TooManyArguments {
+                method: method.into(),
+                expected: MAX_DEFERRED_ARGS,
+            });
+        }
+
+        let mut deferred_args = Vec::with_capacity(args.len() + 1);
+        deferred_args.push(Variant::from_str(method));
+        deferred_args.extend(args);
+
+        object
+            .call_raw(&GodotString::from_str("call_deferred"), &deferred_args)
+            .map(|_| ())
+            .map_err(|err| MethodCallError::from_sys(method.into(), err))
+    }
+
+    /// Returns a handle to the method `method` of this object, which can be called later with
+    /// `Callable::call`.
+    #[inline]
+    pub fn callable(self, method: impl Into<String>) -> Callable<'a, T, Access> {
+        Callable {
+            object: self,
+            method: method.into(),
+        }
+    }
+}
+
+/// A method bound to an object, similar to `Callable` in GDScript 2.0 or `FuncRef`, which
+/// can be called with `Callable::call`.
+///
+/// Created with `TRef::callable`. Creating a `Callable` is safe, but calling it is `unsafe`
+/// for the same reasons as `TRef::call_typed`.
+pub struct Callable<'a, T: GodotObject, Access: ThreadAccess = Shared> {
+    object: TRef<'a, T, Access>,
+    method: String,
+}
+
+impl<'a, T: GodotObject, Access: ThreadAccess> Callable<'a, T, Access> {
+    /// Returns the object the method is bound to.
+    #[inline]
+    pub fn object(&self) -> TRef<'a, T, Access> {
+        self.object
+    }
+
+    /// Returns the name of the method.
+    #[inline]
+    pub fn method(&self) -> &str {
+        &self.method
+    }
+
+    /// Returns `true` if the object has the method, including methods of the script attached
+    /// to it.
+    #[inline]
+    pub fn is_valid(&self) -> bool {
+        // Safety: `TRef` is valid for `'a`.
+        let object = unsafe { Variant::from_object_ptr(self.object.as_ptr()) };
+        object.has_method(self.method.as_str())
+    }
+
+    /// Calls the method with `args`, and converts the result to `R`. See `TRef::call_typed`.
+    ///
+    /// # Safety
+    ///
+    /// See `TRef::call_typed`.
+    #[inline]
+    pub unsafe fn call<R: FromVariant>(&self, args: impl CallArgs) -> Result<R, MethodCallError> {
+        self.object.call_typed(&self.method, args)
+    }
+
+    /// Calls the method with `args` during idle time. See `TRef::call_deferred_typed`.
+    ///
+    /// # Safety
+    ///
+    /// See `TRef::call_deferred_typed`.
+    #[inline]
+    pub unsafe fn call_deferred(&self, args: impl CallArgs) -> Result<(), MethodCallError> {
+        self.object.call_deferred_typed(&self.method, args)
+    }
+}
+
+impl<'a, T: GodotObject, Access: ThreadAccess> Clone for Callable<'a, T, Access> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Callable {
+            object: self.object,
+            method: self.method.clone(),
+        }
+    }
+}
+
+impl<'a, T: GodotObject, Access: ThreadAccess> fmt::Debug for Callable<'a, T, Access> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Callable")
+            .field("object", &self.object)
+            .field("method", &self.method)
+            .finish()
+    }
+}
@@ -0,0 +1,144 @@
+use std::fmt::{self, Debug};
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::ptr::NonNull;
+
+use crate::private::get_api;
+use crate::thread_access::{AnyThread, MainThreadToken, Shared, ThreadAccess};
+
+use super::{GodotObject, RawObject, TRef};
+
+/// A weak reference to a Godot object of type `T`, identified by its instance ID.
+///
+/// Unlike `Ref`, a `WeakObject` never keeps the object alive, and never dangles: the object is
+/// looked up by its ID each time the reference is upgraded, which fails if the object has
+/// been freed, or is no longer of type `T`. Instance IDs are never reused by the engine.
+///
+/// This makes `WeakObject` suitable for keeping handles to objects that may be freed at any time,
+/// like nodes in the scene tree. To also check the script attached to the object, see
+/// `WeakInstance`.
+///
+/// Upgrading requires the core 1.2 API, and always fails if the engine doesn't provide it.
+///
+/// This is unrelated to the engine's `WeakRef` class, which only works for `Reference`s and
+/// has to be allocated as an object itself.
+pub struct WeakObject<T: GodotObject> {
+    id: i64,
+    _marker: PhantomData<*const T>,
+}
+
+// Safety: `WeakObject` is only an instance ID. Access to the object is only given by the unsafe
+// `upgrade`.
+unsafe impl<T: GodotObject> Send for WeakObject<T> {}
+unsafe impl<T: GodotObject> Sync for WeakObject<T> {}
+
+impl<T: GodotObject> WeakObject<T> {
+    /// Creates a weak reference to the object with the instance ID `id`. The object isn't
+    /// required to exist, or to be of type `T`.
+    #[inline]
+    pub fn from_instance_id(id: i64) -> Self {
+        WeakObject {
+            id,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the instance ID of the object.
+    #[inline]
+    pub fn instance_id(&self) -> i64 {
+        self.id
+    }
+
+    /// Returns `true` if the object is still alive. The type of the object is not checked.
+    ///
+    /// Note that the object may be freed by another thread right after this returns. Use
+    /// `upgrade` to access the object instead.
+    #[inline]
+    pub fn is_alive(&self) -> bool {
+        let api = get_api();
+        api.has_core_1_2() && unsafe { !(api.godot_instance_from_id)(self.id as _).is_null() }
+    }
+
+    /// Returns a reference to the object if it's still alive and of type `T`, or `None`
+    /// otherwise.
+    ///
+    /// # Safety
+    ///
+    /// Unlike `Ref::assume_safe`, it's checked that the object is alive when this is called.
+    /// It must stay alive during the entirety of `'a`, i.e. it must not be freed by the caller
+    /// or by another thread, and the thread from which `upgrade` is called must have exclusive
+    /// access to it.
+    ///
+    /// This is only available for classes that can be used from any thread. Use
+    /// `upgrade_on_main_thread` for nodes and other `MainThreadOnly` classes.
+    #[inline]
+    pub unsafe fn upgrade<'a>(&self) -> Option<TRef<'a, T, Shared>>
+    where
+        T: AnyThread,
+    {
+        self.upgrade_unchecked()
+    }
+
+    /// Returns a reference to the object if it's still alive and of type `T`, or `None`
+    /// otherwise. Unlike `upgrade`, this is available for all classes, including
+    /// `MainThreadOnly` ones like nodes.
+    ///
+    /// # Safety
+    ///
+    /// The same safety constraints as `upgrade` apply.
+    #[inline]
+    pub unsafe fn upgrade_on_main_thread<'a>(
+        &self,
+        _token: MainThreadToken,
+    ) -> Option<TRef<'a, T, Shared>> {
+        self.upgrade_unchecked()
+    }
+
+    unsafe fn upgrade_unchecked<'a>(&self) -> Option<TRef<'a, T, Shared>> {
+        let api = get_api();
+        if !api.has_core_1_2() {
+            return None;
+        }
+        let ptr = NonNull::new((api.godot_instance_from_id)(self.id as _))?;
+        let raw = RawObject::<T>::try_from_sys_ref(ptr)?;
+        Some(TRef::new(T::cast_ref(raw)))
+    }
+}
+
+impl<'a, T: GodotObject, Access: ThreadAccess> TRef<'a, T, Access> {
+    /// Creates a `WeakObject` to this object.
+    #[inline]
+    pub fn downgrade(self) -> WeakObject<T> {
+        WeakObject::from_instance_id(self.as_ref().as_raw().instance_id())
+    }
+}
+
+impl<T: GodotObject> Copy for WeakObject<T> {}
+impl<T: GodotObject> Clone for WeakObject<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: GodotObject> Eq for WeakObject<T> {}
+impl<T: GodotObject> PartialEq for WeakObject<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<T: GodotObject> Hash for WeakObject<T> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state)
+    }
+}
+
+impl<T: GodotObject> Debug for WeakObject<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "WeakObject<{}>({})", T::class_name(), self.id)
+    }
+}
@@ -0,0 +1,103 @@
+//! Panic hook reporting Rust panics to the engine.
+//!
+//! By default, panics are only printed to stderr, which isn't visible in the editor. The hook
+//! installed by `install` additionally prints the panic message through `godot_print_error`,
+//! with the file and line where the panic happened and the exported method being called, if any,
+//! so that it shows up in the debugger. The previously installed hook is still called afterwards.
+//!
+//! With the `backtrace` feature enabled, a backtrace is included in the report if the
+//! `RUST_BACKTRACE` environment variable is set to anything but `0`, like with the default hook.
+//!
+//! The hook is installed automatically by `godot_gdnative_init!` (and thus `godot_init!`).
+
+use std::any::Any;
+// `PanicHookInfo` replaces `PanicInfo` in newer Rust versions, but isn't available in the
+// minimum supported one.
+#[allow(deprecated)]
+use std::panic::{self, PanicInfo};
+use std::sync::Once;
+
+use crate::private::{to_c_string_escaped, try_get_api};
+
+/// Installs the panic hook, if it isn't already installed. The previous hook is called after
+/// the panic is reported to the engine.
+///
+/// This is called by `godot_gdnative_init!`, and only needs to be called manually when the
+/// `gdnative_init` endpoint is declared by other means.
+#[inline]
+pub fn install() {
+    static INSTALL: Once = Once::new();
+
+    INSTALL.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            report(info);
+            previous(info);
+        }));
+    });
+}
+
+/// Returns the message of a panic payload, if it's a string.
+fn payload_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(s) = payload.downcast_ref::<&'static str>() {
+        s
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.as_str()
+    } else {
+        "Box<Any>"
+    }
+}
+
+#[cfg(feature = "backtrace")]
+fn backtrace() -> Option<backtrace_rs::Backtrace> {
+    match std::env::var_os("RUST_BACKTRACE") {
+        Some(var) if var != "0" => Some(backtrace_rs::Backtrace::new()),
+        _ => None,
+    }
+}
+
+/// Prints a panic using the engine's logging system, if the API is bound.
+#[allow(deprecated)]
+fn report(info: &PanicInfo<'_>) {
+    let api = match try_get_api() {
+        Some(api) => api,
+        None => return,
+    };
+
+    let thread = std::thread::current();
+    #[allow(unused_mut)]
+    let mut description = format!(
+        "Rust panic in thread '{}': {}",
+        thread.name().unwrap_or("<unnamed>"),
+        payload_message(info.payload()),
+    );
+
+    #[cfg(feature = "backtrace")]
+    {
+        if let Some(backtrace) = backtrace() {
+            description.push_str(&format!("\n{:?}", backtrace));
+        }
+    }
+
+    let (file, line) = info.location().map_or(("<unknown>", 0), |location| {
+        (location.file(), location.line())
+    });
+
+    #[cfg(feature = "nativescript")]
+    let func = crate::nativescript::user_data::diagnostics::current_method();
+    #[cfg(not(feature = "nativescript"))]
+    let func = None;
+
+    let description = to_c_string_escaped(&description);
+    let func = to_c_string_escaped(func.unwrap_or("<unknown>"));
+    let file = to_c_string_escaped(file);
+
+    unsafe {
+        (api.godot_print_error)(
+            description.as_ptr() as *const _,
+            func.as_ptr() as *const _,
+            file.as_ptr() as *const _,
+            line as _,
+        );
+    }
+}
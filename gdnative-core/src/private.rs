@@ -25,9 +25,26 @@ pub unsafe fn bind_api(options: *mut sys::godot_gdnative_init_options) -> bool {
         }
     };
 
+    #[cfg(feature = "nativescript")]
+    {
+        if !api.has_nativescript_1_0() {
+            report_init_error(
+                options,
+                sys::InitError::Generic {
+                    message: "the NativeScript 1.0 API is required by the `nativescript` feature"
+                        .into(),
+                },
+            );
+            return false;
+        }
+    }
+
     GODOT_API = Some(api);
     GDNATIVE_LIBRARY_SYS = Some((*options).gd_native_library);
 
+    // `gdnative_init` is always called by the engine on the main thread.
+    crate::thread_access::set_main_thread();
+
     ObjectMethodTable::get(get_api());
     ReferenceMethodTable::get(get_api());
     #[cfg(feature = "nativescript")]
@@ -38,6 +55,16 @@ pub unsafe fn bind_api(options: *mut sys::godot_gdnative_init_options) -> bool {
     true
 }
 
+/// Binds an API struct created by the `mock_api` module.
+///
+/// # Safety
+///
+/// No other thread may be using the API while this function is called.
+#[cfg(feature = "mock_api")]
+pub(crate) unsafe fn bind_mock_api(api: sys::GodotApi) {
+    GODOT_API = Some(api);
+}
+
 unsafe fn check_api_compatibility(
     options: *const sys::godot_gdnative_init_options,
 ) -> Result<(), sys::InitError> {
@@ -93,6 +120,13 @@ pub fn is_api_bound() -> bool {
     unsafe { GODOT_API.is_some() }
 }
 
+/// Converts `s` to a `CString`, escaping any NUL bytes instead of failing. For use in code that
+/// must not panic, like loggers and panic hooks.
+#[inline]
+pub(crate) fn to_c_string_escaped(s: &str) -> CString {
+    CString::new(s.replace('\0', "\\0")).expect("NUL bytes should be escaped")
+}
+
 /// Returns a pointer to the `GDNativeLibrary` object for the current library.
 ///
 /// This is intended to be an internal interface.
@@ -109,11 +143,40 @@ pub fn get_gdnative_library_sys() -> *mut sys::godot_object {
 #[inline]
 pub unsafe fn cleanup_internal_state() {
     #[cfg(feature = "nativescript")]
-    crate::nativescript::type_tag::cleanup();
+    {
+        crate::nativescript::reload::save_all();
+        crate::nativescript::type_tag::cleanup();
+        crate::nativescript::init::static_methods::cleanup();
+    }
 
+    // Method tables are kept: method binds belong to the engine, and stay valid when the
+    // library is loaded again.
+    GDNATIVE_LIBRARY_SYS = None;
     GODOT_API = None;
 }
 
+/// Performs library-wide setup after the `nativescript_init` callback registered the classes.
+///
+/// # Safety
+///
+/// This is intended to be an internal interface.
+#[cfg(feature = "nativescript")]
+#[inline]
+pub unsafe fn nativescript_init_internal_state() {
+    crate::nativescript::reload::restore_all();
+}
+
+/// Calls a static method with the arguments `[class_name, method_name, args...]`.
+///
+/// # Safety
+///
+/// This is intended to be an internal interface.
+#[cfg(feature = "nativescript")]
+#[inline]
+pub unsafe fn call_static_method(args: *mut sys::godot_array) -> sys::godot_variant {
+    crate::nativescript::init::static_methods::call(args).forget()
+}
+
 /// Reports an `InitError` to Godot.
 #[inline]
 unsafe fn report_init_error(
@@ -211,6 +274,13 @@ macro_rules! make_method_table {
 make_method_table!(struct ObjectMethodTable for Object {
     get_class,
     is_class,
+    get_instance_id,
+    has_meta,
+    get_meta,
+    set_meta,
+    remove_meta,
+    get_script,
+    set_script,
 });
 
 make_method_table!(struct ReferenceMethodTable for Reference {
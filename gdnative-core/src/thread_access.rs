@@ -1,5 +1,10 @@
 //! Typestates to express thread safety of Godot types.
 
+use std::cell::Cell;
+use std::marker::PhantomData;
+
+use crate::object::GodotObject;
+
 /// Marker that indicates that a value currently only has a
 /// single unique reference.
 ///
@@ -46,6 +51,65 @@ impl LocalThreadAccess for ThreadLocal {}
 impl NonUniqueThreadAccess for ThreadLocal {}
 impl private::Sealed for ThreadLocal {}
 
+/// Marker trait for engine classes that should only be used from the main thread, like nodes
+/// and the scene tree. See the official [thread-safety guidelines][thread-safety].
+///
+/// Shared references to these classes can only be assumed safe with
+/// `Ref::assume_safe_on_main_thread`, which takes a
+/// [`MainThreadToken`](struct.MainThreadToken.html). `Node::get_tree`, `Node::get_viewport`
+/// and `Engine::get_main_loop` take a token as well.
+///
+/// [thread-safety]: https://docs.godotengine.org/en/stable/tutorials/threads/thread_safe_apis.html
+pub trait MainThreadOnly: GodotObject {}
+
+/// Marker trait for engine classes that can be used from any thread, i.e. all classes that
+/// aren't [`MainThreadOnly`](trait.MainThreadOnly.html). `Ref::assume_safe` is only available
+/// for these classes.
+pub trait AnyThread: GodotObject {}
+
+thread_local! {
+    static IS_MAIN_THREAD: Cell<bool> = Cell::new(false);
+}
+
+/// Records the current thread as the main thread. Called when the library is initialized.
+pub(crate) fn set_main_thread() {
+    IS_MAIN_THREAD.with(|is_main| is_main.set(true));
+}
+
+/// Proof that the current thread is the main thread of the engine, required by methods that
+/// can only be called from the main thread.
+///
+/// Tokens can't be sent to or shared with other threads. They can be obtained with
+/// [`MainThreadToken::try_get`](#method.try_get), e.g. in callbacks like `_ready` or
+/// `_process`, which the engine always invokes from the main thread.
+#[derive(Copy, Clone, Debug)]
+pub struct MainThreadToken(PhantomData<*const ()>);
+
+impl MainThreadToken {
+    /// Returns a token if called from the main thread, or `None` otherwise.
+    ///
+    /// The main thread is the thread the library was initialized from, so this always
+    /// returns `None` before `godot_gdnative_init` is called, e.g. in unit tests.
+    #[inline]
+    pub fn try_get() -> Option<Self> {
+        if IS_MAIN_THREAD.with(Cell::get) {
+            Some(MainThreadToken(PhantomData))
+        } else {
+            None
+        }
+    }
+
+    /// Returns a token without checking the current thread.
+    ///
+    /// # Safety
+    ///
+    /// Must be called from the main thread.
+    #[inline]
+    pub unsafe fn assume_main_thread() -> Self {
+        MainThreadToken(PhantomData)
+    }
+}
+
 mod private {
     pub trait Sealed {}
 }
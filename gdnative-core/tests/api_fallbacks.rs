@@ -0,0 +1,162 @@
+//! Uses the fallbacks for optional APIs with a mock API that doesn't provide them. The functions
+//! of the missing APIs abort the process when called. This is an integration test, so that the
+//! other tests keep all APIs.
+
+use std::ptr::NonNull;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use gdnative_core::core_types::{Variant, VariantArray, VariantOperator};
+use gdnative_core::nativescript::init::InitHandle;
+use gdnative_core::nativescript::profiling::{self, Signature};
+use gdnative_core::nativescript::user_data::MutexData;
+use gdnative_core::nativescript::{
+    ClassBuilder, HotReload, NativeClass, NativeClassMethods, RefInstance,
+};
+use gdnative_core::object::{GodotObject, Ref, TRef, WeakObject};
+use gdnative_core::ref_kind::ManuallyManaged;
+use gdnative_core::thread_access::{AnyThread, Shared};
+use gdnative_core::{
+    api_version, godot_gdnative_terminate, godot_nativescript_init, mock_api, private, sys,
+};
+
+/// Stand-in for the `Object` class of the generated bindings.
+struct Object;
+
+unsafe impl GodotObject for Object {
+    type RefKind = ManuallyManaged;
+
+    fn class_name() -> &'static str {
+        "Object"
+    }
+}
+
+impl private::godot_object::Sealed for Object {}
+impl AnyThread for Object {}
+
+struct Probe;
+
+static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+impl Drop for Probe {
+    fn drop(&mut self) {
+        DROPPED.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+impl NativeClass for Probe {
+    type Base = Object;
+    type UserData = MutexData<Probe>;
+
+    fn class_name() -> &'static str {
+        "Probe"
+    }
+
+    fn init(_owner: TRef<'_, Object, Shared>) -> Self {
+        Probe
+    }
+}
+
+impl NativeClassMethods for Probe {
+    fn register(_builder: &ClassBuilder<Self>) {}
+}
+
+impl HotReload for Probe {
+    type State = ();
+
+    fn save_state(&self, _owner: TRef<'_, Object, Shared>) {}
+
+    fn restore_state(_owner: TRef<'_, Object, Shared>, _state: ()) -> Self {
+        Probe
+    }
+}
+
+fn init(handle: InitHandle) {
+    handle.add_reloadable_class::<Probe>();
+}
+
+godot_nativescript_init!(init);
+godot_gdnative_terminate!();
+
+unsafe fn new_object() -> NonNull<sys::godot_object> {
+    let constructor =
+        (private::get_api().godot_get_class_constructor)(b"Object\0".as_ptr() as *const _)
+            .expect("Object should be constructible");
+    NonNull::new(constructor()).expect("constructor should not return null")
+}
+
+#[test]
+fn fallbacks_without_optional_apis() {
+    unsafe {
+        let mut api = mock_api::api();
+        api.remove_core_1_1();
+        api.remove_core_1_2();
+        api.remove_nativescript_1_1();
+        mock_api::bind_api(api);
+
+        // Type tags are not registered without NativeScript 1.1.
+        godot_nativescript_init(std::ptr::null_mut());
+
+        assert!(!api_version::has_core_1_1());
+        assert!(!api_version::has_core_1_2());
+        assert!(api_version::has_nativescript_1_0());
+        assert!(!api_version::has_nativescript_1_1());
+
+        // Core 1.1
+        let array = VariantArray::new();
+        array.push(1);
+        array.push("foo");
+        let array = array.into_shared();
+        let copy = array.duplicate();
+        copy.push(2);
+        assert_eq!(2, array.len());
+        assert_eq!(3, copy.len());
+        assert_eq!(array.get(1), copy.get(1));
+        drop((array, copy));
+
+        let one = Variant::from_i64(1);
+        let two = Variant::from_i64(2);
+        let compare = |op, a: &Variant, b: &Variant| a.evaluate(op, b).unwrap().try_to_bool();
+        assert_eq!(Some(true), compare(VariantOperator::Less, &one, &two));
+        assert_eq!(Some(false), compare(VariantOperator::Greater, &one, &two));
+        assert_eq!(Some(true), compare(VariantOperator::LessEqual, &one, &one));
+        assert_eq!(
+            Some(true),
+            compare(VariantOperator::GreaterEqual, &two, &one)
+        );
+        assert_eq!(Some(false), compare(VariantOperator::Equal, &one, &two));
+        assert_eq!(Some(true), compare(VariantOperator::NotEqual, &one, &two));
+        assert!(one.evaluate(VariantOperator::Add, &two).is_err());
+        drop((one, two));
+
+        let owner = new_object();
+        let owner_ref = Ref::<Object, Shared>::from_sys(owner);
+        assert!(!owner_ref.is_instance_sane());
+
+        // Core 1.2
+        assert!(TRef::<Object, Shared>::try_from_instance_id(1).is_none());
+        let weak = WeakObject::<Object>::from_instance_id(1);
+        assert!(!weak.is_alive());
+        assert!(weak.upgrade().is_none());
+
+        // NativeScript 1.1
+        assert!(mock_api::attach_script(owner.as_ptr(), "Probe"));
+        assert!(RefInstance::<Probe, Shared>::try_from_base(owner_ref.assume_safe()).is_none());
+        profiling::add_data(
+            Signature::new(file!(), line!(), "fallback"),
+            Duration::from_micros(1),
+        );
+
+        // Instances of reloadable classes are not dropped when the library is unloaded, since
+        // they can't be found again to be replaced.
+        let mut options = sys::godot_gdnative_terminate_options { in_editor: true };
+        godot_gdnative_terminate(&mut options);
+        assert_eq!(0, DROPPED.load(Ordering::SeqCst));
+
+        // They are dropped once their scripts are detached instead.
+        mock_api::bind_api(mock_api::api());
+        mock_api::detach_script(owner.as_ptr());
+        assert_eq!(1, DROPPED.load(Ordering::SeqCst));
+        (private::get_api().godot_object_destroy)(owner.as_ptr());
+    }
+}
@@ -0,0 +1,171 @@
+//! Loads, unloads and reloads a library with a reloadable class using the mock API. This is an
+//! integration test, so that unbinding the API doesn't affect other tests.
+
+use std::ptr::NonNull;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use gdnative_core::core_types::GodotString;
+use gdnative_core::libc;
+use gdnative_core::nativescript::init::InitHandle;
+use gdnative_core::nativescript::user_data::MutexData;
+use gdnative_core::nativescript::{
+    ClassBuilder, HotReload, NativeClass, NativeClassMethods, RefInstance,
+};
+use gdnative_core::object::{GodotObject, Ref, TRef};
+use gdnative_core::ref_kind::ManuallyManaged;
+use gdnative_core::thread_access::{AnyThread, Shared};
+use gdnative_core::{godot_gdnative_terminate, godot_nativescript_init, mock_api, private, sys};
+
+/// Stand-in for the `Object` class of the generated bindings.
+struct Object;
+
+unsafe impl GodotObject for Object {
+    type RefKind = ManuallyManaged;
+
+    fn class_name() -> &'static str {
+        "Object"
+    }
+}
+
+impl private::godot_object::Sealed for Object {}
+impl AnyThread for Object {}
+
+struct Counter {
+    count: i64,
+}
+
+static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+impl Drop for Counter {
+    fn drop(&mut self) {
+        DROPPED.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+impl NativeClass for Counter {
+    type Base = Object;
+    type UserData = MutexData<Counter>;
+
+    fn class_name() -> &'static str {
+        "Counter"
+    }
+
+    fn init(_owner: TRef<'_, Object, Shared>) -> Self {
+        Counter { count: 0 }
+    }
+}
+
+impl NativeClassMethods for Counter {
+    fn register(_builder: &ClassBuilder<Self>) {}
+}
+
+impl HotReload for Counter {
+    type State = i64;
+
+    fn save_state(&self, _owner: TRef<'_, Object, Shared>) -> i64 {
+        self.count
+    }
+
+    fn restore_state(_owner: TRef<'_, Object, Shared>, count: i64) -> Self {
+        Counter { count }
+    }
+}
+
+fn init(handle: InitHandle) {
+    handle.add_reloadable_class::<Counter>();
+}
+
+godot_nativescript_init!(init);
+godot_gdnative_terminate!();
+
+unsafe fn load() {
+    mock_api::bind_api(mock_api::api());
+    godot_nativescript_init(std::ptr::null_mut());
+}
+
+unsafe fn unload() {
+    let mut options = sys::godot_gdnative_terminate_options { in_editor: true };
+    godot_gdnative_terminate(&mut options);
+}
+
+/// Increments the counter of the instance attached to `owner`, returning the old count.
+unsafe fn increment(owner: NonNull<sys::godot_object>) -> i64 {
+    let owner = Ref::<Object, Shared>::from_sys(owner);
+    let instance = RefInstance::<Counter, Shared>::try_from_base(owner.assume_safe())
+        .expect("script should be attached");
+    instance
+        .map_mut(|counter, _| {
+            counter.count += 1;
+            counter.count - 1
+        })
+        .expect("counter should not be locked")
+}
+
+/// Returns whether the `Engine` singleton holds any saved states.
+unsafe fn has_saved_states() -> bool {
+    let api = private::get_api();
+    let engine = (api.godot_global_get_singleton)(b"Engine\0".as_ptr() as *mut _);
+    let has_meta = (api.godot_method_bind_get_method)(
+        b"Object\0".as_ptr() as *const _,
+        b"has_meta\0".as_ptr() as *const _,
+    );
+
+    let name = GodotString::from_str("_gdnative_reload_states");
+    let mut args = [name.sys() as *const libc::c_void];
+    let mut ret = false;
+    (api.godot_method_bind_ptrcall)(
+        has_meta,
+        engine,
+        args.as_mut_ptr(),
+        &mut ret as *mut bool as *mut _,
+    );
+    ret
+}
+
+#[test]
+fn state_survives_reloads() {
+    unsafe {
+        load();
+
+        let constructor =
+            (private::get_api().godot_get_class_constructor)(b"Object\0".as_ptr() as *const _)
+                .expect("Object should be constructible");
+        let owner = NonNull::new(constructor()).expect("constructor should not return null");
+
+        assert!(mock_api::attach_script(owner.as_ptr(), "Counter"));
+        assert_eq!(0, increment(owner));
+        assert_eq!(1, increment(owner));
+
+        for reload in 1..=2 {
+            let dropped = DROPPED.load(Ordering::SeqCst);
+            unload();
+            assert!(!private::is_api_bound());
+            assert_eq!(dropped + 1, DROPPED.load(Ordering::SeqCst));
+
+            // The script stays attached while the library is unloaded, and is attached again
+            // once the classes are registered, without dropping the old instance twice.
+            load();
+            assert_eq!(dropped + 1, DROPPED.load(Ordering::SeqCst));
+            assert!(!has_saved_states());
+            assert_eq!(reload + 1, increment(owner));
+        }
+
+        // States are only restored once.
+        assert!(mock_api::attach_script(owner.as_ptr(), "Counter"));
+        assert_eq!(0, increment(owner));
+
+        // States of objects freed while the library is unloaded are discarded.
+        let freed = NonNull::new(constructor()).expect("constructor should not return null");
+        assert!(mock_api::attach_script(freed.as_ptr(), "Counter"));
+        assert_eq!(0, increment(freed));
+        unload();
+        (mock_api::api().godot_object_destroy)(freed.as_ptr());
+        load();
+        assert!(!has_saved_states());
+        assert_eq!(1, increment(owner));
+
+        mock_api::detach_script(owner.as_ptr());
+        (private::get_api().godot_object_destroy)(owner.as_ptr());
+        unload();
+    }
+}
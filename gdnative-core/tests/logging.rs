@@ -0,0 +1,184 @@
+//! Forwards messages from the `log` and `tracing` backends to a mock API that records the print
+//! calls. This is an integration test, so that the global logger doesn't affect other tests.
+
+use std::cell::RefCell;
+use std::ffi::CStr;
+use std::sync::Once;
+
+use gdnative_core::core_types::GodotString;
+use gdnative_core::logging::{init_log, GodotLayer};
+use gdnative_core::{libc, mock_api, sys};
+use tracing_subscriber::layer::SubscriberExt;
+
+#[derive(Debug, PartialEq)]
+enum Severity {
+    Print,
+    Warning,
+    Error,
+}
+
+#[derive(Debug, PartialEq)]
+struct Call {
+    severity: Severity,
+    message: String,
+    function: Option<String>,
+    file: Option<String>,
+    line: Option<u32>,
+}
+
+thread_local! {
+    static CALLS: RefCell<Vec<Call>> = RefCell::new(Vec::new());
+}
+
+/// Returns the print calls made on the current thread since the last call.
+fn take_calls() -> Vec<Call> {
+    CALLS.with(|calls| calls.borrow_mut().drain(..).collect())
+}
+
+unsafe extern "C" fn print(message: *const sys::godot_string) {
+    let message = GodotString::clone_from_sys(*message).to_string();
+    CALLS.with(|calls| {
+        calls.borrow_mut().push(Call {
+            severity: Severity::Print,
+            message,
+            function: None,
+            file: None,
+            line: None,
+        })
+    });
+}
+
+unsafe fn record(
+    severity: Severity,
+    description: *const libc::c_char,
+    function: *const libc::c_char,
+    file: *const libc::c_char,
+    line: libc::c_int,
+) {
+    let to_string = |s: *const libc::c_char| CStr::from_ptr(s).to_string_lossy().into_owned();
+    CALLS.with(|calls| {
+        calls.borrow_mut().push(Call {
+            severity,
+            message: to_string(description),
+            function: Some(to_string(function)),
+            file: Some(to_string(file)),
+            line: Some(line as u32),
+        })
+    });
+}
+
+unsafe extern "C" fn print_warning(
+    description: *const libc::c_char,
+    function: *const libc::c_char,
+    file: *const libc::c_char,
+    line: libc::c_int,
+) {
+    record(Severity::Warning, description, function, file, line);
+}
+
+unsafe extern "C" fn print_error(
+    description: *const libc::c_char,
+    function: *const libc::c_char,
+    file: *const libc::c_char,
+    line: libc::c_int,
+) {
+    record(Severity::Error, description, function, file, line);
+}
+
+fn bind() {
+    static BIND: Once = Once::new();
+
+    BIND.call_once(|| {
+        let mut api = mock_api::api();
+        api.godot_print = print;
+        api.godot_print_warning = print_warning;
+        api.godot_print_error = print_error;
+        unsafe { mock_api::bind_api(api) };
+    });
+}
+
+fn print_call(message: &str) -> Call {
+    Call {
+        severity: Severity::Print,
+        message: message.into(),
+        function: None,
+        file: None,
+        line: None,
+    }
+}
+
+fn located_call(severity: Severity, message: &str, line: u32) -> Call {
+    Call {
+        severity,
+        message: message.into(),
+        function: Some(module_path!().into()),
+        file: Some(file!().into()),
+        line: Some(line),
+    }
+}
+
+#[test]
+fn log_backend() {
+    bind();
+    init_log(log::LevelFilter::Debug).expect("no logger should be installed yet");
+
+    let error_line = line!() + 1;
+    log::error!("error {}", 1);
+    let warn_line = line!() + 1;
+    log::warn!("warning {}", 2);
+    log::info!("info {}", 3);
+    log::debug!("debug {}", 4);
+    log::trace!("filtered");
+
+    assert_eq!(
+        vec![
+            located_call(Severity::Error, "error 1", error_line),
+            located_call(Severity::Warning, "warning 2", warn_line),
+            print_call("[INFO] logging: info 3"),
+            print_call("[DEBUG] logging: debug 4"),
+        ],
+        take_calls(),
+    );
+
+    log::set_max_level(log::LevelFilter::Warn);
+    log::info!("filtered");
+    log::warn!("not filtered");
+    assert_eq!(1, take_calls().len());
+}
+
+#[test]
+fn tracing_backend() {
+    bind();
+
+    let subscriber =
+        tracing_subscriber::registry().with(GodotLayer::new(tracing_core::LevelFilter::DEBUG));
+
+    tracing::subscriber::with_default(subscriber, || {
+        let error_line = line!() + 1;
+        tracing::error!(answer = 42, "error {}", 1);
+        let warn_line = line!() + 1;
+        tracing::warn!("warning {}", 2);
+        tracing::info!(answer = 42);
+        tracing::debug!("debug {}", 4);
+        tracing::trace!("filtered");
+
+        assert_eq!(
+            vec![
+                located_call(Severity::Error, "error 1 answer=42", error_line),
+                located_call(Severity::Warning, "warning 2", warn_line),
+                print_call("[INFO] logging: answer=42"),
+                print_call("[DEBUG] logging: debug 4"),
+            ],
+            take_calls(),
+        );
+    });
+
+    let subscriber =
+        tracing_subscriber::registry().with(GodotLayer::new(tracing_core::LevelFilter::WARN));
+
+    tracing::subscriber::with_default(subscriber, || {
+        tracing::info!("filtered");
+        tracing::warn!("not filtered");
+        assert_eq!(1, take_calls().len());
+    });
+}
@@ -0,0 +1,100 @@
+//! Reports panics to a mock API that records the errors printed. This is an integration test,
+//! so that the panic hook doesn't affect other tests.
+
+use std::cell::RefCell;
+use std::ffi::CStr;
+use std::panic;
+use std::sync::Once;
+
+use gdnative_core::nativescript::user_data::diagnostics;
+use gdnative_core::{libc, mock_api, panic_hook};
+
+#[derive(Debug, PartialEq)]
+struct Error {
+    description: String,
+    function: String,
+    file: String,
+    line: u32,
+}
+
+thread_local! {
+    static ERRORS: RefCell<Vec<Error>> = RefCell::new(Vec::new());
+}
+
+unsafe extern "C" fn print_error(
+    description: *const libc::c_char,
+    function: *const libc::c_char,
+    file: *const libc::c_char,
+    line: libc::c_int,
+) {
+    let to_string = |s: *const libc::c_char| CStr::from_ptr(s).to_string_lossy().into_owned();
+    ERRORS.with(|errors| {
+        errors.borrow_mut().push(Error {
+            description: to_string(description),
+            function: to_string(function),
+            file: to_string(file),
+            line: line as u32,
+        })
+    });
+}
+
+fn setup() {
+    static SETUP: Once = Once::new();
+
+    SETUP.call_once(|| {
+        let mut api = mock_api::api();
+        api.godot_print_error = print_error;
+        unsafe { mock_api::bind_api(api) };
+        panic_hook::install();
+    });
+}
+
+/// Runs `f`, which must panic, returning the errors printed.
+fn catch_errors(f: impl FnOnce() + panic::UnwindSafe) -> Vec<Error> {
+    assert!(panic::catch_unwind(f).is_err());
+    ERRORS.with(|errors| errors.borrow_mut().drain(..).collect())
+}
+
+#[test]
+fn reports_location() {
+    setup();
+
+    let line = line!() + 2;
+    let errors = catch_errors(|| {
+        panic!("boom {}", 42);
+    });
+
+    assert_eq!(
+        vec![Error {
+            description: "Rust panic in thread 'reports_location': boom 42".into(),
+            function: "<unknown>".into(),
+            file: file!().into(),
+            line,
+        }],
+        errors,
+    );
+}
+
+#[test]
+fn reports_current_method() {
+    setup();
+
+    let line = line!() + 3;
+    let errors = catch_errors(|| {
+        let _scope = diagnostics::enter_method("explode");
+        panic!("boom");
+    });
+
+    assert_eq!(
+        vec![Error {
+            description: "Rust panic in thread 'reports_current_method': boom".into(),
+            function: "explode".into(),
+            file: file!().into(),
+            line,
+        }],
+        errors,
+    );
+
+    let errors = catch_errors(|| panic!("after the call"));
+    assert_eq!("<unknown>", errors[0].function);
+}
@@ -0,0 +1,124 @@
+//! Calls static methods through the library-level table using the mock API, without creating
+//! any instance of their class. This is an integration test, so that unbinding the API doesn't
+//! affect other tests.
+
+use gdnative_core::core_types::{Variant, VariantArray};
+use gdnative_core::nativescript::init::InitHandle;
+use gdnative_core::nativescript::user_data::MutexData;
+use gdnative_core::nativescript::{ClassBuilder, NativeClass, NativeClassMethods};
+use gdnative_core::object::{GodotObject, TRef};
+use gdnative_core::ref_kind::ManuallyManaged;
+use gdnative_core::thread_access::Shared;
+use gdnative_core::{
+    godot_gdnative_terminate, godot_nativescript_call_static, godot_nativescript_init,
+    godot_wrap_static_method, mock_api, private, sys,
+};
+
+/// Stand-in for the `Object` class of the generated bindings.
+struct Object;
+
+unsafe impl GodotObject for Object {
+    type RefKind = ManuallyManaged;
+
+    fn class_name() -> &'static str {
+        "Object"
+    }
+}
+
+impl private::godot_object::Sealed for Object {}
+
+struct MathUtils;
+
+impl NativeClass for MathUtils {
+    type Base = Object;
+    type UserData = MutexData<MathUtils>;
+
+    fn class_name() -> &'static str {
+        "MathUtils"
+    }
+
+    fn init(_owner: TRef<'_, Object, Shared>) -> Self {
+        panic!("static methods should not construct instances");
+    }
+}
+
+impl MathUtils {
+    fn lerp(from: f64, to: f64, weight: f64) -> f64 {
+        from + (to - from) * weight
+    }
+}
+
+impl NativeClassMethods for MathUtils {
+    fn register(builder: &ClassBuilder<Self>) {
+        let method = godot_wrap_static_method!(
+            MathUtils,
+            fn lerp(from: f64, to: f64, #[opt] weight: f64) -> f64
+        );
+        builder.add_static_method("lerp", method);
+    }
+}
+
+fn init(handle: InitHandle) {
+    handle.add_class::<MathUtils>();
+}
+
+godot_nativescript_init!(init);
+godot_nativescript_call_static!();
+godot_gdnative_terminate!();
+
+unsafe fn call_static(args: &[Variant]) -> Variant {
+    let array = VariantArray::new();
+    for arg in args {
+        array.push(arg);
+    }
+    let array = array.into_shared();
+    Variant::from_sys(godot_nativescript_call_static(array.sys() as *mut _))
+}
+
+#[test]
+fn call_without_instance() {
+    unsafe {
+        mock_api::bind_api(mock_api::api());
+        godot_nativescript_init(std::ptr::null_mut());
+
+        let class = Variant::from_str("MathUtils");
+        let lerp = Variant::from_str("lerp");
+
+        assert_eq!(
+            Some(5.0),
+            call_static(&[
+                class.clone(),
+                lerp.clone(),
+                Variant::from_f64(0.0),
+                Variant::from_f64(10.0),
+                Variant::from_f64(0.5),
+            ])
+            .try_to_f64()
+        );
+
+        // Optional arguments and argument checks are the same as for instance methods.
+        assert_eq!(
+            Some(2.0),
+            call_static(&[
+                class.clone(),
+                lerp.clone(),
+                Variant::from_f64(2.0),
+                Variant::from_f64(10.0),
+            ])
+            .try_to_f64()
+        );
+        assert!(call_static(&[class.clone(), lerp.clone()]).is_nil());
+
+        assert!(call_static(&[class.clone(), Variant::from_str("missing")]).is_nil());
+
+        let mut options = sys::godot_gdnative_terminate_options { in_editor: true };
+        godot_gdnative_terminate(&mut options);
+
+        // The table is cleared when the library is terminated.
+        mock_api::bind_api(mock_api::api());
+        assert!(
+            call_static(&[class, lerp, Variant::from_f64(0.0), Variant::from_f64(1.0)]).is_nil()
+        );
+        godot_gdnative_terminate(&mut options);
+    }
+}
@@ -9,6 +9,7 @@ use proc_macro::TokenStream;
 
 mod methods;
 mod native_script;
+mod node_path;
 mod profiled;
 mod variant;
 
@@ -52,6 +53,27 @@ pub fn profiled(meta: TokenStream, input: TokenStream) -> TokenStream {
     profiled::derive_profiled(meta, input)
 }
 
+/// Creates a `NodePathBuf` from a string literal, e.g.
+/// `node_path!("Player/Sprite:modulate:a")`. The path is parsed at compile time, and invalid
+/// paths are reported as compile errors.
+///
+/// See the documentation on `NodePathBuf` for the syntax of paths.
+///
+/// # Examples
+///
+/// ```ignore
+/// let sprite = unsafe { owner.get_node_as::<Sprite>(node_path!("Player/Sprite")) };
+/// let alpha = node_path!("Player/Sprite:modulate:a");
+/// assert_eq!(&["modulate", "a"], alpha.subnames());
+///
+/// // error: invalid node path "Player/Sprite::a": empty subname
+/// let invalid = node_path!("Player/Sprite::a");
+/// ```
+#[proc_macro]
+pub fn node_path(input: TokenStream) -> TokenStream {
+    node_path::derive_node_path(input)
+}
+
 /// Makes it possible to use a type as a NativeScript.
 ///
 /// ## Required attributes
@@ -117,6 +139,19 @@ pub fn profiled(meta: TokenStream, input: TokenStream) -> TokenStream {
 /// Puts the property under the `my_category` category and renames it to
 /// `my_property_name` in the inspector and for GDScript.
 ///
+/// - `group = "Movement"`
+///
+/// Shows the property under the `Movement` group in the inspector. Properties without a
+/// group are registered first, followed by each group in the order it first appears.
+/// The last group is ended afterwards, so properties registered in `register_with` are
+/// shown without a group.
+///
+/// - `prefix = "movement_"`
+///
+/// Hides `movement_` from the names of the properties in the group in the inspector. Only
+/// valid together with `group`. The names of all properties in the group must start with the
+/// prefix. It only needs to be set on one of them.
+///
 /// - `default = 42.0`
 ///
 /// Sets the default value *in the inspector* for this property. The setter is *not*
@@ -126,6 +161,34 @@ pub fn profiled(meta: TokenStream, input: TokenStream) -> TokenStream {
 ///
 /// Call hook methods with `self` and `owner` before and/or after the generated property
 /// accessors.
+///
+/// ### `#[node("Path/To/Node")]`
+///
+/// Resolves the field to the node at the given path, relative to the owner, at the start of the
+/// exported `_ready` method. The field must be an `Option<Ref<T, Shared>>` for a node class `T`,
+/// or an `Option<Instance<C, Shared>>` for a script class `C` extending a node. Fields that can't
+/// be resolved are reported as errors, and keep their values.
+///
+/// The type must have an exported `_ready` method taking `&mut self` and a named owner
+/// argument, otherwise compilation fails with an unsatisfied `ResolvesNodesInReady` bound.
+/// This requires the `bindings` feature of `gdnative`.
+///
+/// ```ignore
+/// #[derive(NativeClass)]
+/// #[inherit(Node2D)]
+/// struct Player {
+///     #[node("Sprite")]
+///     sprite: Option<Ref<AnimatedSprite>>,
+/// }
+///
+/// #[methods]
+/// impl Player {
+///     #[export]
+///     fn _ready(&mut self, _owner: &Node2D) {
+///         // `self.sprite` is set here.
+///     }
+/// }
+/// ```
 #[proc_macro_derive(
     NativeClass,
     attributes(
@@ -135,7 +198,8 @@ pub fn profiled(meta: TokenStream, input: TokenStream) -> TokenStream {
         user_data,
         property,
         register_with,
-        no_constructor
+        no_constructor,
+        node
     )
 )]
 pub fn derive_native_class(input: TokenStream) -> TokenStream {
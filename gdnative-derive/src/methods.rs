@@ -53,6 +53,8 @@ impl ToTokens for RpcMode {
 pub(crate) struct ClassMethodExport {
     pub(crate) class_ty: Box<Type>,
     pub(crate) methods: Vec<ExportMethod>,
+    /// Whether `#[node]` fields are resolved in an exported `_ready` method.
+    pub(crate) resolves_nodes: bool,
 }
 
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
@@ -76,6 +78,14 @@ pub(crate) fn derive_methods(meta: TokenStream, input: TokenStream) -> TokenStre
     let output = {
         let class_name = export.class_ty;
 
+        let resolves_nodes = if export.resolves_nodes {
+            Some(quote!(
+                ::gdnative::__gdnative_resolves_nodes_in_ready!(#class_name);
+            ))
+        } else {
+            None
+        };
+
         let builder = syn::Ident::new("builder", proc_macro2::Span::call_site());
 
         let methods = export
@@ -83,6 +93,7 @@ pub(crate) fn derive_methods(meta: TokenStream, input: TokenStream) -> TokenStre
             .into_iter()
             .map(|ExportMethod { sig, args }| {
                 let sig_span = sig.ident.span();
+                let is_static = !has_receiver(&sig);
 
                 let name = sig.ident;
                 let name_string = name.to_string();
@@ -94,7 +105,10 @@ pub(crate) fn derive_methods(meta: TokenStream, input: TokenStream) -> TokenStre
 
                 let arg_count = sig.inputs.len();
 
-                if arg_count < 2 {
+                // self and owner
+                let reserved_args = if is_static { 0 } else { 2 };
+
+                if arg_count < reserved_args {
                     return syn::Error::new(
                         sig_span,
                         "exported methods must take self and owner as arguments",
@@ -104,7 +118,7 @@ pub(crate) fn derive_methods(meta: TokenStream, input: TokenStream) -> TokenStre
 
                 let optional_args = match args.optional_args {
                     Some(count) => {
-                        let max_optional = arg_count - 2; // self and owner
+                        let max_optional = arg_count - reserved_args;
                         if count > max_optional {
                             let message = format!(
                                 "there can be at most {} optional arguments, got {}",
@@ -117,6 +131,11 @@ pub(crate) fn derive_methods(meta: TokenStream, input: TokenStream) -> TokenStre
                     None => 0,
                 };
 
+                if is_static && args.rpc_mode != RpcMode::Disabled {
+                    return syn::Error::new(sig_span, "static methods can't be called over RPC")
+                        .to_compile_error();
+                }
+
                 let rpc = args.rpc_mode;
 
                 let args = sig.inputs.iter().enumerate().map(|(n, arg)| {
@@ -128,14 +147,59 @@ pub(crate) fn derive_methods(meta: TokenStream, input: TokenStream) -> TokenStre
                     }
                 });
 
-                quote_spanned!( sig_span=>
-                    {
+                // Check exported methods that override virtual methods of the base class against
+                // the engine signatures, if the name matches one.
+                let virtual_check = if is_static {
+                    None
+                } else {
+                    let arg_tys =
+                        sig.inputs
+                            .iter()
+                            .skip(reserved_args)
+                            .filter_map(|arg| match arg {
+                                FnArg::Typed(arg) => Some(&arg.ty),
+                                FnArg::Receiver(_) => None,
+                            });
+
+                    Some(quote_spanned!( sig_span=>
+                        ::gdnative::__gdnative_check_virtual_method!(
+                            #name,
+                            ::gdnative::nativescript::virtuals::VirtualProbe::<
+                                <Self as ::gdnative::nativescript::NativeClass>::Base,
+                            >::new(),
+                            ::gdnative::nativescript::virtuals::VirtualSignature::<
+                                ( #( #arg_tys, )* ),
+                                #ret_ty,
+                            >::new()
+                        );
+                    ))
+                };
+
+                let register = if is_static {
+                    quote_spanned!( sig_span=>
+                        let method = ::gdnative::godot_wrap_static_method!(
+                            #class_name,
+                            fn #name ( #( #args )* ) -> #ret_ty
+                        );
+
+                        #builder.add_static_method(#name_string, method);
+                    )
+                } else {
+                    quote_spanned!( sig_span=>
                         let method = ::gdnative::godot_wrap_method!(
                             #class_name,
                             fn #name ( #( #args )* ) -> #ret_ty
                         );
 
                         #builder.add_method_with_rpc_mode(#name_string, method, #rpc);
+                    )
+                };
+
+                quote_spanned!( sig_span=>
+                    {
+                        #register
+
+                        #virtual_check
                     }
                 )
             })
@@ -155,12 +219,36 @@ pub(crate) fn derive_methods(meta: TokenStream, input: TokenStream) -> TokenStre
 
             }
 
+            #resolves_nodes
+
         )
     };
 
     TokenStream::from(output)
 }
 
+/// Returns a statement resolving the `#[node]` fields of the class, if `sig` takes `&mut self`
+/// and an owner argument bound to an identifier.
+fn resolve_nodes_stmt(sig: &Signature) -> Option<syn::Stmt> {
+    let mut inputs = sig.inputs.iter();
+
+    match inputs.next()? {
+        FnArg::Receiver(receiver)
+            if receiver.reference.is_some() && receiver.mutability.is_some() => {}
+        _ => return None,
+    }
+
+    let owner = match inputs.next()? {
+        FnArg::Typed(arg) => match &*arg.pat {
+            Pat::Ident(PatIdent { ident, .. }) => ident,
+            _ => return None,
+        },
+        FnArg::Receiver(_) => return None,
+    };
+
+    Some(parse_quote!(::gdnative::__gdnative_resolve_nodes!(self, #owner);))
+}
+
 /// Parse the input.
 ///
 /// Returns the TokenStream of the impl block together with a description of methods to export.
@@ -193,6 +281,7 @@ fn impl_gdnative_expose(ast: ItemImpl) -> (ItemImpl, ClassMethodExport) {
     let mut export = ClassMethodExport {
         class_ty: ast.self_ty,
         methods: vec![],
+        resolves_nodes: false,
     };
 
     let mut methods_to_export: Vec<ExportMethod> = Vec::new();
@@ -324,6 +413,7 @@ fn impl_gdnative_expose(ast: ItemImpl) -> (ItemImpl, ClassMethodExport) {
 
                 if let Some(mut export_args) = export_args.take() {
                     let mut optional_args = None;
+                    let reserved_args = if has_receiver(&method.sig) { 2 } else { 0 };
 
                     for (n, arg) in method.sig.inputs.iter_mut().enumerate() {
                         let attrs = match arg {
@@ -343,7 +433,7 @@ fn impl_gdnative_expose(ast: ItemImpl) -> (ItemImpl, ClassMethodExport) {
                         });
 
                         if is_optional {
-                            if n < 2 {
+                            if n < reserved_args {
                                 errors.push(syn::Error::new(
                                     arg.span(),
                                     "self or owner cannot be optional",
@@ -368,6 +458,14 @@ fn impl_gdnative_expose(ast: ItemImpl) -> (ItemImpl, ClassMethodExport) {
                         sig: method.sig.clone(),
                         args: export_args,
                     });
+
+                    // `#[node]` fields of the class are resolved at the start of `_ready`.
+                    if method.sig.ident == "_ready" {
+                        if let Some(stmt) = resolve_nodes_stmt(&method.sig) {
+                            method.block.stmts.insert(0, stmt);
+                            export.resolves_nodes = true;
+                        }
+                    }
                 }
 
                 errors
@@ -452,3 +550,9 @@ fn impl_gdnative_expose(ast: ItemImpl) -> (ItemImpl, ClassMethodExport) {
 
     (result, export)
 }
+
+/// Returns `true` if the function takes `self` in any form, i.e. if it should be exported as
+/// an instance method. Functions without a receiver are exported as static methods.
+fn has_receiver(sig: &Signature) -> bool {
+    matches!(sig.inputs.first(), Some(FnArg::Receiver(_)))
+}
@@ -1,7 +1,6 @@
 use proc_macro::TokenStream;
-use std::collections::HashMap;
 use syn::spanned::Spanned;
-use syn::{Data, DeriveInput, Fields, Ident, Meta, MetaList, NestedMeta, Path, Stmt, Type};
+use syn::{Data, DeriveInput, Fields, Ident, LitStr, Meta, MetaList, NestedMeta, Path, Stmt, Type};
 
 mod property_args;
 use property_args::{PropertyAttrArgs, PropertyAttrArgsBuilder};
@@ -11,16 +10,71 @@ pub(crate) struct DeriveData {
     pub(crate) base: Type,
     pub(crate) register_callback: Option<Path>,
     pub(crate) user_data: Type,
-    pub(crate) properties: HashMap<Ident, PropertyAttrArgs>,
+    pub(crate) properties: Vec<(Ident, PropertyAttrArgs)>,
+    pub(crate) nodes: Vec<(Ident, LitStr)>,
     pub(crate) no_constructor: bool,
 }
 
+/// Properties sharing a `group`, in order of declaration.
+struct PropertyGroup {
+    name: String,
+    prefix: Option<String>,
+    members: Vec<(Ident, PropertyAttrArgs)>,
+}
+
 pub(crate) fn derive_native_class(input: TokenStream) -> TokenStream {
     let data = match parse_derive_input(input) {
         Ok(val) => val,
         Err(err) => return err,
     };
 
+    // string variant needed for the `class_name` function.
+    let name_str = {
+        let name = &data.name;
+        quote!(#name).to_string()
+    };
+
+    // generate ResolveNodes impl, if there are any `#[node]` fields
+    let resolve_nodes_impl = if data.nodes.is_empty() {
+        None
+    } else {
+        let name = &data.name;
+        let nodes = data.nodes.iter().map(|(ident, path)| {
+            let ident_str = ident.to_string();
+            quote! {
+                match ::gdnative::api::utils::NodeField::get_node(
+                    owner,
+                    ::gdnative::core_types::NodePath::from_str(#path),
+                ) {
+                    Ok(node) => self.#ident = node,
+                    Err(err) => ::gdnative::godot_error!("{}::{}: {}", #name_str, #ident_str, err),
+                }
+            }
+        });
+
+        // Fails to compile unless `#[methods]` resolves the nodes in `_ready`.
+        let (first_node, _) = &data.nodes[0];
+        let check_ready = quote_spanned! { first_node.span()=>
+            const _: () = {
+                fn resolves_nodes_in_ready<T: ::gdnative::api::utils::ResolvesNodesInReady>() {}
+                #[allow(dead_code)]
+                fn check() {
+                    resolves_nodes_in_ready::<#name>();
+                }
+            };
+        };
+
+        Some(quote! {
+            impl ::gdnative::api::utils::ResolveNodes for #name {
+                unsafe fn resolve_nodes(&mut self, owner: &::gdnative::api::Node) {
+                    #(#nodes)*
+                }
+            }
+
+            #check_ready
+        })
+    };
+
     // generate NativeClass impl
     let trait_impl = {
         let name = data.name;
@@ -30,7 +84,7 @@ pub(crate) fn derive_native_class(input: TokenStream) -> TokenStream {
             .register_callback
             .map(|function_path| quote!(#function_path(builder);))
             .unwrap_or(quote!({}));
-        let properties = data.properties.into_iter().map(|(ident, config)| {
+        let property = |(ident, config): (Ident, PropertyAttrArgs)| {
             let with_default = if let Some(default_value) = &config.default {
                 Some(quote!(.with_default(#default_value)))
             } else {
@@ -70,10 +124,83 @@ pub(crate) fn derive_native_class(input: TokenStream) -> TokenStream {
                     })
                     .done();
             })
-        });
+        };
+
+        // Ungrouped properties are registered first, since everything registered after a
+        // group is shown under it. Groups are then registered in order of first appearance,
+        // and ended afterwards so that properties from `register_with` aren't grouped.
+        let mut ungrouped = Vec::new();
+        let mut groups: Vec<PropertyGroup> = Vec::new();
+        for (ident, config) in data.properties {
+            let group = match &config.group {
+                Some(group) => group.clone(),
+                None => {
+                    if let Some(prefix) = &config.prefix {
+                        panic!("property {} has a prefix {:?} but no group", ident, prefix);
+                    }
+                    ungrouped.push((ident, config));
+                    continue;
+                }
+            };
+
+            let prefix = config.prefix.clone();
+            match groups.iter_mut().find(|g| g.name == group) {
+                Some(existing) => {
+                    if prefix.is_some() && existing.prefix.is_some() && prefix != existing.prefix {
+                        panic!(
+                            "group {:?} already has a different prefix: {:?}",
+                            group, existing.prefix
+                        );
+                    }
+                    if existing.prefix.is_none() {
+                        existing.prefix = prefix;
+                    }
+                    existing.members.push((ident, config));
+                }
+                None => groups.push(PropertyGroup {
+                    name: group,
+                    prefix,
+                    members: vec![(ident, config)],
+                }),
+            }
+        }
+
+        // The inspector ends a group at the first property not starting with its prefix.
+        for group in &groups {
+            let prefix = group.prefix.as_deref().unwrap_or("");
+            for (ident, config) in &group.members {
+                let label = config.path.clone().unwrap_or_else(|| ident.to_string());
+                if !label.starts_with(prefix) {
+                    panic!(
+                        "property {} does not start with the prefix {:?} of group {:?}",
+                        label, prefix, group.name
+                    );
+                }
+            }
+        }
 
-        // string variant needed for the `class_name` function.
-        let name_str = quote!(#name).to_string();
+        let has_groups = !groups.is_empty();
+        let mut properties = ungrouped.into_iter().map(property).collect::<Vec<_>>();
+        for group in groups {
+            let name = group.name;
+            let prefix = group.prefix.unwrap_or_default();
+            properties.push(quote!(builder.add_property_group(#name, #prefix);));
+            properties.extend(group.members.into_iter().map(property));
+        }
+        if has_groups {
+            properties.push(quote!(builder.end_property_group();));
+        }
+
+        // Registers `_get`, `_set` and `_get_property_list` if the type implements
+        // `DynamicProperties`, and does nothing otherwise.
+        let dynamic_properties = quote! {
+            {
+                use ::gdnative::nativescript::init::property::dynamic::{
+                    DynamicPropertiesProbe, RegisterDynamicProperties, SkipDynamicProperties,
+                };
+                (&DynamicPropertiesProbe::<Self>::new()).register_dynamic_properties(builder);
+            }
+        };
 
         let init = if data.no_constructor {
             None
@@ -98,6 +225,7 @@ pub(crate) fn derive_native_class(input: TokenStream) -> TokenStream {
 
                 fn register_properties(builder: &::gdnative::nativescript::init::ClassBuilder<Self>) {
                     #(#properties)*;
+                    #dynamic_properties
                     #register_callback
                 }
             }
@@ -105,7 +233,11 @@ pub(crate) fn derive_native_class(input: TokenStream) -> TokenStream {
     };
 
     // create output token stream
-    trait_impl.into()
+    quote!(
+        #trait_impl
+        #resolve_nodes_impl
+    )
+    .into()
 }
 
 fn parse_derive_input(input: TokenStream) -> Result<DeriveData, TokenStream> {
@@ -171,14 +303,28 @@ fn parse_derive_input(input: TokenStream) -> Result<DeriveData, TokenStream> {
         );
     };
 
-    // Find all fields with a `#[property]` attribute
-    let mut properties = HashMap::new();
+    // Find all fields with a `#[property]` or `#[node]` attribute
+    let mut properties = Vec::new();
+    let mut nodes = Vec::new();
 
     if let Fields::Named(names) = &struct_data.fields {
         for field in &names.named {
             let mut property_args = None;
 
             for attr in field.attrs.iter() {
+                if attr.path.is_ident("node") {
+                    let path = attr
+                        .parse_args::<LitStr>()
+                        .map_err(|err| err.to_compile_error())?;
+
+                    let ident = field.ident.clone().ok_or_else(|| {
+                        syn::Error::new(field.ident.span(), "Fields should be named")
+                            .to_compile_error()
+                    })?;
+                    nodes.push((ident, path));
+                    continue;
+                }
+
                 if !attr.path.is_ident("property") {
                     continue;
                 }
@@ -215,7 +361,7 @@ fn parse_derive_input(input: TokenStream) -> Result<DeriveData, TokenStream> {
                 let ident = field.ident.clone().ok_or_else(|| {
                     syn::Error::new(field.ident.span(), "Fields should be named").to_compile_error()
                 })?;
-                properties.insert(ident, builder.done());
+                properties.push((ident, builder.done()));
             }
         }
     };
@@ -226,6 +372,7 @@ fn parse_derive_input(input: TokenStream) -> Result<DeriveData, TokenStream> {
         register_callback,
         user_data,
         properties,
+        nodes,
         no_constructor,
     })
 }
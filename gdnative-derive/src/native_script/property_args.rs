@@ -1,5 +1,7 @@
 pub struct PropertyAttrArgs {
     pub path: Option<String>,
+    pub group: Option<String>,
+    pub prefix: Option<String>,
     pub default: Option<syn::Lit>,
     pub before_get: Option<syn::Path>,
     pub after_get: Option<syn::Path>,
@@ -10,6 +12,8 @@ pub struct PropertyAttrArgs {
 #[derive(Default)]
 pub struct PropertyAttrArgsBuilder {
     path: Option<String>,
+    group: Option<String>,
+    prefix: Option<String>,
     default: Option<syn::Lit>,
     before_get: Option<syn::Path>,
     after_get: Option<syn::Path>,
@@ -45,6 +49,28 @@ impl<'a> Extend<&'a syn::MetaNameValue> for PropertyAttrArgsBuilder {
                         panic!("there is already a path set: {:?}", old);
                     }
                 }
+                "group" => {
+                    let string = if let syn::Lit::Str(lit_str) = &pair.lit {
+                        lit_str.value()
+                    } else {
+                        panic!("group value is not a string literal");
+                    };
+
+                    if let Some(old) = self.group.replace(string) {
+                        panic!("there is already a group set: {:?}", old);
+                    }
+                }
+                "prefix" => {
+                    let string = if let syn::Lit::Str(lit_str) = &pair.lit {
+                        lit_str.value()
+                    } else {
+                        panic!("prefix value is not a string literal");
+                    };
+
+                    if let Some(old) = self.prefix.replace(string) {
+                        panic!("there is already a prefix set: {:?}", old);
+                    }
+                }
                 "before_get" => {
                     let string = if let syn::Lit::Str(lit_str) = &pair.lit {
                         lit_str.value()
@@ -107,6 +133,8 @@ impl PropertyAttrArgsBuilder {
     pub fn done(self) -> PropertyAttrArgs {
         PropertyAttrArgs {
             path: self.path,
+            group: self.group,
+            prefix: self.prefix,
             default: self.default,
             before_get: self.before_get,
             after_get: self.after_get,
@@ -0,0 +1,51 @@
+use proc_macro::TokenStream;
+use syn::LitStr;
+
+pub(crate) fn derive_node_path(input: TokenStream) -> TokenStream {
+    let lit = parse_macro_input!(input as LitStr);
+    let path = lit.value();
+
+    match parse(&path) {
+        Some((absolute, names, subnames)) => quote!(
+            ::gdnative::core_types::NodePathBuf::from_parts_unchecked(
+                #absolute,
+                &[#(#names),*],
+                &[#(#subnames),*],
+            )
+        )
+        .into(),
+        None => {
+            let msg = format!("invalid node path {:?}: empty subname", path);
+            syn::Error::new(lit.span(), msg).to_compile_error().into()
+        }
+    }
+}
+
+/// Splits `path` into the absolute flag, node names and subnames. Must match the `FromStr`
+/// implementation of `NodePathBuf`.
+fn parse(path: &str) -> Option<(bool, Vec<&str>, Vec<&str>)> {
+    let (node_path, subpath) = match path.find(':') {
+        Some(index) => (&path[..index], Some(&path[index + 1..])),
+        None => (path, None),
+    };
+
+    let mut subnames = Vec::new();
+    if let Some(subpath) = subpath {
+        let mut parts = subpath.split(':').peekable();
+        while let Some(part) = parts.next() {
+            if !part.is_empty() {
+                subnames.push(part);
+            } else if parts.peek().is_some() {
+                return None;
+            }
+        }
+    }
+
+    let absolute = node_path.starts_with('/');
+    let names = node_path
+        .split('/')
+        .filter(|name| !name.is_empty())
+        .collect();
+
+    Some((absolute, names, subnames))
+}
@@ -319,6 +319,12 @@ mod api_wrapper {
         fn godot_api_struct(&self) -> Ident {
             godot_api_struct_ident(&self.type_, self.version.major, self.version.minor)
         }
+
+        /// Only `CORE` 1.0 is required. Other versions and extensions are optional, and their
+        /// functions are replaced with stubs if the engine doesn't provide them.
+        fn is_required(&self) -> bool {
+            self.type_ == "CORE" && self.version.major == 1 && self.version.minor == 0
+        }
     }
 
     impl Function {
@@ -464,13 +470,18 @@ mod api_wrapper {
 
         let struct_fields = godot_api_functions(&api_root);
         let impl_constructor = api_constructor(&api_root);
+        let api_availability = api_availability(&api_root);
+        let unavailable_api = unavailable_api(&api_root);
         let wrapper = quote! {
             pub struct GodotApi{
                 #struct_fields
+                available: AvailableApis,
             }
             impl GodotApi {
                 #impl_constructor
             }
+            #api_availability
+            #unavailable_api
         };
         let mut wrapper_file = File::create(to.join(file_name))
             .unwrap_or_else(|_| panic!("Couldn't create output file: {:?}", to.join(file_name)));
@@ -492,15 +503,26 @@ mod api_wrapper {
         let mut godot_apis = TokenStream::new();
         let mut struct_field_bindings = TokenStream::new();
         let mut constructed_struct_fields = TokenStream::new();
+        let mut available_apis = TokenStream::new();
         for api in api.all_apis() {
             let i = api.macro_ident();
             let gd_api_type = api.godot_api_type();
             let v_maj = api.version.major;
             let v_min = api.version.minor;
             let gd_api_struct = api.godot_api_struct();
-            godot_apis.extend(quote! {
-                let #i = find_api_ptr(core_api_struct, #gd_api_type, #v_maj, #v_min)? as *const #gd_api_struct;
-            });
+            if api.is_required() {
+                godot_apis.extend(quote! {
+                    let #i = find_api_ptr(core_api_struct, #gd_api_type, #v_maj, #v_min)? as *const #gd_api_struct;
+                });
+                available_apis.extend(quote!(#i: true,));
+            } else {
+                godot_apis.extend(quote! {
+                    let #i = find_api_ptr(core_api_struct, #gd_api_type, #v_maj, #v_min)
+                        .ok()
+                        .map(|api| api as *const #gd_api_struct);
+                });
+                available_apis.extend(quote!(#i: #i.is_some(),));
+            }
             for function in &api.functions {
                 let function_name = function.rust_name();
                 let message = format!(
@@ -512,9 +534,20 @@ mod api_wrapper {
                 // Workaround: rustc has trouble dealing with a large amount of returns within the
                 // same expression when optimization is enabled, causing the build to appear to halt.
                 // Separating the try expressions into let bindings resolved this problem.
-                struct_field_bindings.extend(quote! {
-                    let #function_name = map_option_to_init_error((*#i).#function_name, #message)?;
-                });
+                if api.is_required() {
+                    struct_field_bindings.extend(quote! {
+                        let #function_name = map_option_to_init_error((*#i).#function_name, #message)?;
+                    });
+                } else {
+                    // Functions of optional APIs that aren't provided by the engine are replaced
+                    // with stubs that abort when called.
+                    struct_field_bindings.extend(quote! {
+                        let #function_name = match #i {
+                            Some(api) => map_option_to_init_error((*api).#function_name, #message)?,
+                            None => unavailable::#function_name,
+                        };
+                    });
+                }
                 constructed_struct_fields.extend(quote! {
                     #function_name,
                 });
@@ -526,11 +559,134 @@ mod api_wrapper {
                 #struct_field_bindings
                 Ok(GodotApi{
                     #constructed_struct_fields
+                    available: AvailableApis {
+                        #available_apis
+                    },
                 })
             }
         }
     }
 
+    /// Generates `AvailableApis`, which records the optional APIs provided by the engine, and
+    /// the public `has_*` queries on `GodotApi`.
+    fn api_availability(api: &ApiRoot) -> TokenStream {
+        let mut fields = TokenStream::new();
+        let mut queries = TokenStream::new();
+        for api in api.all_apis() {
+            let i = api.macro_ident();
+            let query = format_ident!("has_{}", i);
+            let doc = if api.is_required() {
+                format!(
+                    "Returns `true`. Version {}.{} of the `{}` API is required, so it's always available.",
+                    api.version.major, api.version.minor, api.type_
+                )
+            } else {
+                format!(
+                    "Returns `true` if version {}.{} of the `{}` API is provided by the engine.",
+                    api.version.major, api.version.minor, api.type_
+                )
+            };
+            fields.extend(quote!(#i: bool,));
+            queries.extend(quote! {
+                #[doc = #doc]
+                #[inline]
+                pub fn #query(&self) -> bool {
+                    self.available.#i
+                }
+            });
+        }
+        quote! {
+            #[derive(Copy, Clone, Debug)]
+            struct AvailableApis {
+                #fields
+            }
+            impl GodotApi {
+                #queries
+            }
+        }
+    }
+
+    /// Generates the stubs used in place of functions that aren't available, and
+    /// `GodotApi::unimplemented`, which creates an API struct without the engine where every
+    /// function is a stub. Stubs for required APIs are only needed by the latter, which is only
+    /// compiled with the `mock_api` feature, like the `remove_*` methods making optional APIs
+    /// unavailable for testing.
+    fn unavailable_api(api: &ApiRoot) -> TokenStream {
+        let mut stubs = TokenStream::new();
+        let mut constructed_struct_fields = TokenStream::new();
+        let mut available_apis = TokenStream::new();
+        let mut removers = TokenStream::new();
+        for api in api.all_apis() {
+            let i = api.macro_ident();
+            let api_name = format!("{} {}.{}", api.type_, api.version.major, api.version.minor);
+            let cfg = if api.is_required() {
+                quote!(#[cfg(feature = "mock_api")])
+            } else {
+                TokenStream::new()
+            };
+            let mut replaced_functions = TokenStream::new();
+            for function in &api.functions {
+                let function_name = function.rust_name();
+                let name = &function.name;
+                let arg_types = function.arguments.iter().map(Argument::rust_type);
+                let return_type = function.rust_return_type();
+
+                stubs.extend(quote! {
+                    #cfg
+                    pub(super) unsafe extern "C" fn #function_name(#(_: #arg_types),*) -> #return_type {
+                        crate::api_function_unavailable(#name, #api_name)
+                    }
+                });
+                constructed_struct_fields.extend(quote! {
+                    #function_name: unavailable::#function_name,
+                });
+                replaced_functions.extend(quote! {
+                    self.#function_name = unavailable::#function_name;
+                });
+            }
+            available_apis.extend(quote!(#i: true,));
+
+            if !api.is_required() {
+                let remover = format_ident!("remove_{}", i);
+                let doc = format!(
+                    "Makes version {}.{} of the `{}` API unavailable, like on an engine that doesn't provide it. Its functions are replaced with stubs that abort when called.",
+                    api.version.major, api.version.minor, api.type_
+                );
+                removers.extend(quote! {
+                    #[doc = #doc]
+                    pub fn #remover(&mut self) {
+                        self.available.#i = false;
+                        #replaced_functions
+                    }
+                });
+            }
+        }
+        quote! {
+            #[cfg(feature = "mock_api")]
+            impl GodotApi {
+                /// Creates an API struct where every function prints its name and aborts the
+                /// process when called. Individual functions can then be replaced with mock
+                /// implementations, allowing code to be tested without the engine.
+                ///
+                /// All APIs are reported as available.
+                pub fn unimplemented() -> Self {
+                    GodotApi {
+                        #constructed_struct_fields
+                        available: AvailableApis {
+                            #available_apis
+                        },
+                    }
+                }
+
+                #removers
+            }
+            mod unavailable {
+                use super::*;
+                #stubs
+            }
+        }
+    }
+
     fn parse_c_type(mut c_type: &str) -> (bool, i8, &str) {
         c_type = c_type.trim();
         let is_const = c_type.starts_with("const ");
@@ -20,6 +20,13 @@ pub enum InitError {
     },
 }
 
+/// Called by stubs in place of API functions that aren't available. Unwinding isn't possible
+/// across the FFI boundary, so this aborts.
+fn api_function_unavailable(name: &str, api: &str) -> ! {
+    eprintln!("gdnative-sys: {} ({} API) is not available", name, api);
+    std::process::abort()
+}
+
 fn map_option_to_init_error<T>(t: Option<T>, message: &'static str) -> Result<T, InitError> {
     match t {
         Some(t) => Ok(t),
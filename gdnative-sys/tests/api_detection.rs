@@ -0,0 +1,120 @@
+//! Detects the available APIs from fake API structs, like the ones passed by the engine to
+//! `gdnative_init`.
+
+use std::mem::{self, MaybeUninit};
+use std::ptr;
+
+use gdnative_sys::*;
+
+/// Allocates an API struct of type `S` with the given header, and all function pointers set
+/// to a dummy value. The functions must not be called. The struct is leaked.
+unsafe fn fake_api<S>(
+    type_: GDNATIVE_API_TYPES,
+    major: u32,
+    minor: u32,
+    next: *const godot_gdnative_api_struct,
+) -> *mut S {
+    let api = Box::into_raw(Box::new(MaybeUninit::<S>::uninit())) as *mut S;
+    ptr::write_bytes(api as *mut u8, 0x01, mem::size_of::<S>());
+
+    let header = api as *mut godot_gdnative_api_struct;
+    (*header).type_ = type_ as _;
+    (*header).version = godot_gdnative_api_version { major, minor };
+    (*header).next = next;
+    api
+}
+
+/// Allocates a core 1.0 API struct with the given minor versions and extensions.
+unsafe fn fake_core_api(
+    next: *const godot_gdnative_api_struct,
+    extensions: Vec<*const godot_gdnative_api_struct>,
+) -> *mut godot_gdnative_core_api_struct {
+    let core =
+        fake_api::<godot_gdnative_core_api_struct>(GDNATIVE_API_TYPES_GDNATIVE_CORE, 1, 0, next);
+    (*core).num_extensions = extensions.len() as _;
+    (*core).extensions = Box::leak(extensions.into_boxed_slice()).as_mut_ptr();
+    core
+}
+
+#[test]
+fn only_required_apis() {
+    unsafe {
+        let nativescript = fake_api::<godot_gdnative_ext_nativescript_api_struct>(
+            GDNATIVE_API_TYPES_GDNATIVE_EXT_NATIVESCRIPT,
+            1,
+            0,
+            ptr::null(),
+        );
+        let core = fake_core_api(ptr::null(), vec![nativescript as *const _]);
+
+        let api = GodotApi::from_raw(core).expect("core 1.0 should be enough");
+        assert!(api.has_core_1_0());
+        assert!(!api.has_core_1_1());
+        assert!(!api.has_core_1_2());
+        assert!(api.has_nativescript_1_0());
+        assert!(!api.has_nativescript_1_1());
+        assert!(!api.has_pluginscript_1_0());
+        assert!(!api.has_net_3_2());
+    }
+}
+
+#[test]
+fn minor_versions_and_extensions() {
+    unsafe {
+        let core_1_2 = fake_api::<godot_gdnative_core_1_2_api_struct>(
+            GDNATIVE_API_TYPES_GDNATIVE_CORE,
+            1,
+            2,
+            ptr::null(),
+        );
+        let core_1_1 = fake_api::<godot_gdnative_core_1_1_api_struct>(
+            GDNATIVE_API_TYPES_GDNATIVE_CORE,
+            1,
+            1,
+            core_1_2 as *const _,
+        );
+        let nativescript_1_1 = fake_api::<godot_gdnative_ext_nativescript_1_1_api_struct>(
+            GDNATIVE_API_TYPES_GDNATIVE_EXT_NATIVESCRIPT,
+            1,
+            1,
+            ptr::null(),
+        );
+        let nativescript = fake_api::<godot_gdnative_ext_nativescript_api_struct>(
+            GDNATIVE_API_TYPES_GDNATIVE_EXT_NATIVESCRIPT,
+            1,
+            0,
+            nativescript_1_1 as *const _,
+        );
+        let core = fake_core_api(core_1_1 as *const _, vec![nativescript as *const _]);
+
+        let api = GodotApi::from_raw(core).expect("all APIs should be found");
+        assert!(api.has_core_1_1());
+        assert!(api.has_core_1_2());
+        assert!(api.has_nativescript_1_0());
+        assert!(api.has_nativescript_1_1());
+        assert!(!api.has_pluginscript_1_0());
+    }
+}
+
+#[test]
+fn missing_functions() {
+    unsafe {
+        // Functions of the required API must be provided.
+        let core = fake_core_api(ptr::null(), vec![]);
+        (*core).godot_color_new_rgba = None;
+        let err = GodotApi::from_raw(core).err().expect("should fail");
+        assert!(format!("{:?}", err).contains("godot_color_new_rgba"));
+
+        // Functions of optional APIs must be provided if the API is.
+        let core_1_1 = fake_api::<godot_gdnative_core_1_1_api_struct>(
+            GDNATIVE_API_TYPES_GDNATIVE_CORE,
+            1,
+            1,
+            ptr::null(),
+        );
+        (*core_1_1).godot_color_to_abgr32 = None;
+        let core = fake_core_api(core_1_1 as *const _, vec![]);
+        let err = GodotApi::from_raw(core).err().expect("should fail");
+        assert!(format!("{:?}", err).contains("godot_color_to_abgr32"));
+    }
+}
@@ -26,6 +26,25 @@
 //! with another version of the engine, see the instructions [here][custom-version] on
 //! generating custom bindings.
 //!
+//! ### Generating a subset of the classes
+//!
+//! Generating and compiling all API types takes a significant part of the build time. To only
+//! generate the classes a project uses, set the `GODOT_BINDINGS_CLASSES` environment variable
+//! to their names, separated by commas or whitespace, e.g. `GODOT_BINDINGS_CLASSES="Area2D
+//! AnimatedSprite"`, when building. The classes they depend on, through inheritance or in
+//! method signatures, are generated as well, along with the classes in the prelude. Other
+//! classes are left out of the `api` module, which keeps its layout otherwise.
+//!
+//! ### Using a custom API description
+//!
+//! To generate the `api` module for an engine fork or a build with custom modules, set the
+//! `GODOT_API_JSON` environment variable to the path of the JSON description of its API, which
+//! can be generated with `godot --gdnative-generate-json-api api.json`. The documentation of
+//! the classes is read from the folder in `GODOT_API_DOCS` if it's set, e.g. `doc/classes` in
+//! the engine repository. Relative paths are resolved from the directory of the
+//! `gdnative-bindings` crate, so absolute paths should be used. The build fails with a list of
+//! the unsupported types if the description uses types the generator doesn't know.
+//!
 //! ### Memory management
 //!
 //! API types may be reference-counted or manually-managed. This is indicated by the
@@ -39,6 +58,21 @@
 //! information on how to use persistent references safely, see the type-level documentation
 //! on `Ref`.
 //!
+//! ### Thread safety
+//!
+//! Following the official [thread-safety guidelines][thread-safety], nodes and the scene tree
+//! should only be used from the main thread. These classes implement the `MainThreadOnly`
+//! marker trait, and shared references to them can only be used through
+//! `Ref::assume_safe_on_main_thread`, `Instance::assume_safe_on_main_thread` and
+//! `WeakObject::upgrade_on_main_thread`, which take a `MainThreadToken`. So do
+//! `Node::get_tree`, `Node::get_viewport` and `Engine::get_main_loop`. Tokens can be obtained
+//! with `MainThreadToken::try_get` on the main thread, e.g. in `_ready` or `_process`. All
+//! other classes implement `AnyThread`, and can be used with `Ref::assume_safe`.
+//!
+//! The rendering and physics servers can only be used from other threads if thread-safe
+//! operations are enabled in the project settings, so their `godot_singleton` getters are
+//! unsafe. `godot_singleton_main_thread` takes a `MainThreadToken` instead.
+//!
 //! ## Feature flags
 //!
 //! ### `bindings`
@@ -55,11 +89,19 @@
 //             re-exported. Manually re-exporting the public items works around that.
 #[doc(inline)]
 pub use gdnative_core::{
-    core_types, godot_dbg, godot_error, godot_gdnative_init, godot_gdnative_terminate, godot_init,
-    godot_nativescript_init, godot_print, godot_warn, godot_wrap_method, nativescript, object,
-    ref_kind, thread_access, GodotObject, GodotResult, NewRef, Null, Ref, TRef,
+    api_version, core_types, godot_dbg, godot_error, godot_gdnative_init, godot_gdnative_terminate,
+    godot_init, godot_nativescript_init, godot_print, godot_warn, godot_wrap_method, nativescript,
+    object, ref_kind, thread_access, GodotObject, GodotResult, NewRef, Null, Ref, TRef,
 };
 
+#[doc(inline)]
+#[cfg(any(feature = "log", feature = "tracing"))]
+pub use gdnative_core::logging;
+
+#[doc(inline)]
+#[cfg(feature = "mock_api")]
+pub use gdnative_core::mock_api;
+
 #[doc(hidden)]
 pub use gdnative_core::*;
 
@@ -73,3 +115,38 @@ pub mod prelude;
 #[cfg(feature = "bindings")]
 /// Bindings for the Godot Class API.
 pub use gdnative_bindings as api;
+
+#[doc(hidden)]
+#[cfg(feature = "bindings")]
+pub use gdnative_bindings::__gdnative_check_virtual_method;
+
+#[doc(hidden)]
+#[cfg(feature = "bindings")]
+pub use gdnative_bindings::__gdnative_resolve_nodes;
+
+#[doc(hidden)]
+#[cfg(feature = "bindings")]
+pub use gdnative_bindings::__gdnative_resolves_nodes_in_ready;
+
+/// `#[node]` fields can't be resolved without the generated bindings.
+#[doc(hidden)]
+#[cfg(not(feature = "bindings"))]
+#[macro_export]
+macro_rules! __gdnative_resolve_nodes {
+    ($($tokens:tt)*) => {};
+}
+
+#[doc(hidden)]
+#[cfg(not(feature = "bindings"))]
+#[macro_export]
+macro_rules! __gdnative_resolves_nodes_in_ready {
+    ($($tokens:tt)*) => {};
+}
+
+/// Signatures of virtual methods can't be checked without the generated bindings.
+#[doc(hidden)]
+#[cfg(not(feature = "bindings"))]
+#[macro_export]
+macro_rules! __gdnative_check_virtual_method {
+    ($($tokens:tt)*) => {};
+}
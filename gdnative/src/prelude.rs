@@ -1,9 +1,9 @@
 pub use gdnative_core::core_types::{
     self, error::GodotError, Aabb, Angle, Basis, ByteArray, Color, ColorArray, Dictionary,
-    Float32Array, GodotString, Int32Array, NodePath, Plane, Point2, Point3, Quat, Rect2, Rid,
-    Rotation2D, Rotation3D, Size2, StringArray, StringName, Transform, Transform2D, TypedArray,
-    Variant, VariantArray, VariantOperator, VariantType, Vector2, Vector2Array, Vector3,
-    Vector3Array,
+    Float32Array, GodotString, Int32Array, NodePath, NodePathBuf, Plane, Point2, Point3, Quat,
+    Rect2, Rid, Rotation2D, Rotation3D, Size2, StringArray, StringName, Transform, Transform2D,
+    TypedArray, Variant, VariantArray, VariantOperator, VariantType, Vector2, Vector2Array,
+    Vector3, Vector3Array,
 };
 pub use gdnative_core::core_types::{
     FromVariant, FromVariantError, OwnedToVariant, ToVariant, ToVariantEq, Vector2Godot,
@@ -11,18 +11,19 @@ pub use gdnative_core::core_types::{
 };
 
 pub use gdnative_core::object::{
-    AsArg, GodotObject, Instanciable, Null, QueueFree, Ref, SubClass, TRef,
+    AsArg, Callable, GodotObject, Instanciable, Null, QueueFree, Ref, SubClass, TRef, WeakObject,
 };
 pub use gdnative_core::ref_kind::{ManuallyManaged, RefCounted};
-pub use gdnative_core::thread_access::{Shared, ThreadLocal, Unique};
+pub use gdnative_core::thread_access::{MainThreadToken, Shared, ThreadLocal, Unique};
 pub use gdnative_core::NewRef;
 
 pub use gdnative_core::nativescript::{
     self,
-    class::{Instance, RefInstance},
+    class::{Instance, RefInstance, WeakInstance},
     init::{ClassBuilder, InitHandle, Signal, SignalArgument},
-    user_data::{self, Aether, ArcData, LocalCellData, MutexData, RwLockData},
-    ExportInfo, NativeClass, NativeClassMethods, PropertyUsage,
+    user_data::{self, Aether, ArcData, LocalCellData, MutexData, ReentrantData, RwLockData},
+    DynamicProperties, ExportInfo, HotReload, NativeClass, NativeClassMethods, PropertyInfo,
+    PropertyUsage,
 };
 
 pub use gdnative_core::{
@@ -38,3 +39,6 @@ pub use gdnative_bindings::{
     KinematicBody, KinematicBody2D, Label, Node, Node2D, Object, PackedScene, Reference,
     ResourceLoader, SceneTree, Shader, Spatial, Sprite, Texture, Timer, Tween, Viewport,
 };
+
+#[cfg(feature = "bindings")]
+pub use gdnative_bindings::utils::{NodeExt, PackedSceneExt};
@@ -0,0 +1,25 @@
+use gdnative_bindings_generator::{generate_script_bindings, Api, ScriptClass};
+
+use std::env;
+use std::path::PathBuf;
+
+// Generates the wrappers for the sample script used by `test_script_bindings`, so that they're
+// compiled against `gdnative`.
+fn main() {
+    let api_path = "../gdnative-bindings/api.json";
+    let script_path = "project/script_bindings.gd";
+
+    let api = Api::new(&std::fs::read_to_string(api_path).unwrap());
+    let script = ScriptClass::from_gdscript(
+        "res://script_bindings.gd",
+        &std::fs::read_to_string(script_path).unwrap(),
+    );
+
+    let code = generate_script_bindings(&api, &[script]);
+    let out_path = PathBuf::from(env::var("OUT_DIR").unwrap());
+    std::fs::write(out_path.join("script_bindings.rs"), code.to_string()).unwrap();
+
+    println!("cargo:rerun-if-changed={}", api_path);
+    println!("cargo:rerun-if-changed={}", script_path);
+    println!("cargo:rerun-if-changed=build.rs");
+}
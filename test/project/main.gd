@@ -14,6 +14,10 @@ func _ready():
 
         status = status && _test_argument_passing_sanity()
         status = status && _test_optional_args()
+        status = status && _test_static_methods()
+
+        if status:
+            status = yield(_test_weak_ref_queue_free(), "completed")
 
         gdn.terminate()
     else:
@@ -89,6 +93,53 @@ func _test_optional_args():
 
     return status
 
+func _test_static_methods():
+    print(" -- _test_static_methods")
+
+    # No instance of VariantCallArgs is created.
+    var status = true
+
+    status = status && _assert_static(42, "VariantCallArgs", "static_zero", [])
+    status = status && _assert_static(-42, "VariantCallArgs", "static_two", [-1])
+    status = status && _assert_static(-10, "VariantCallArgs", "static_two", [-1, 32])
+
+    if !status:
+        printerr("   !! _test_static_methods failed")
+
+    return status
+
+func _assert_static(expected, class_name, method, args):
+    var got_value = gdn.call_native("standard_varcall", "godot_nativescript_call_static", [class_name, method] + args)
+    if got_value == expected:
+        return true
+    printerr("   !! expected ", expected, ", got ", got_value)
+    return false
+
+func _test_weak_ref_queue_free():
+    print(" -- _test_weak_ref_queue_free")
+
+    var script = NativeScript.new()
+    script.set_library(gdn.library)
+    script.set_class_name("WeakTracker")
+    var tracker = Reference.new()
+    tracker.set_script(script)
+
+    var node = Node.new()
+    tracker.track(node)
+    node.queue_free()
+
+    var status = tracker.is_alive()
+
+    # Queued objects are deleted at the end of the frame.
+    yield(get_tree(), "idle_frame")
+
+    status = status && !tracker.is_alive()
+
+    if !status:
+        printerr("   !! _test_weak_ref_queue_free failed")
+
+    return status
+
 func _assert_opt_args(expected, opt_args, args):
     var got_value = opt_args.callv("opt_sum", args);
     if got_value == expected:
@@ -0,0 +1,14 @@
+extends Node
+
+signal damaged(amount)
+
+export var health := 100
+var target: Node
+var last_damage := 0
+
+func take_damage(amount: int) -> int:
+    health -= amount
+    return health
+
+func _on_damaged(amount):
+    last_damage = amount
@@ -3,13 +3,24 @@
 use gdnative::api;
 use gdnative::prelude::*;
 
+mod test_call;
 mod test_derive;
 mod test_free_ub;
+mod test_load;
+mod test_lock_diagnostics;
+mod test_node_ext;
+mod test_properties;
+mod test_reentrant;
 mod test_register;
+mod test_reload;
 mod test_return_leak;
+mod test_script_bindings;
+mod test_thread;
 mod test_vararray_return;
 mod test_variant_call_args;
 mod test_variant_ops;
+mod test_virtuals;
+mod test_weak;
 
 #[no_mangle]
 pub extern "C" fn run_tests(
@@ -54,18 +65,31 @@ pub extern "C" fn run_tests(
     status &= gdnative::core_types::test_vector3_array_access();
     status &= gdnative::core_types::test_vector3_array_debug();
 
+    status &= gdnative::core_types::test_node_path_buf_conversion();
+
     status &= test_constructor();
     status &= test_underscore_method_binding();
     status &= test_rust_class_construction();
     status &= test_from_instance_id();
 
+    status &= test_call::run_tests();
     status &= test_derive::run_tests();
     status &= test_free_ub::run_tests();
+    status &= test_load::run_tests();
+    status &= test_lock_diagnostics::run_tests();
+    status &= test_node_ext::run_tests();
+    status &= test_properties::run_tests();
+    status &= test_reentrant::run_tests();
     status &= test_register::run_tests();
+    status &= test_reload::run_tests();
     status &= test_return_leak::run_tests();
+    status &= test_script_bindings::run_tests();
+    status &= test_thread::run_tests();
     status &= test_variant_call_args::run_tests();
     status &= test_variant_ops::run_tests();
     status &= test_vararray_return::run_tests();
+    status &= test_virtuals::run_tests();
+    status &= test_weak::run_tests();
 
     gdnative::core_types::Variant::from_bool(status).forget()
 }
@@ -212,27 +236,33 @@ fn test_from_instance_id() -> bool {
     println!(" -- test_from_instance_id");
 
     let ok = std::panic::catch_unwind(|| {
-        assert!(unsafe { Node::try_from_instance_id(22).is_none() });
-        assert!(unsafe { Node::try_from_instance_id(42).is_none() });
-        assert!(unsafe { Node::try_from_instance_id(503).is_none() });
+        let token = MainThreadToken::try_get().expect("tests should run on the main thread");
+
+        assert!(unsafe { Object::try_from_instance_id(22).is_none() });
+        assert!(unsafe { Object::try_from_instance_id(42).is_none() });
+        assert!(unsafe { Object::try_from_instance_id(503).is_none() });
 
         let instance_id;
 
         {
-            let foo = unsafe { Node::new().into_shared().assume_safe() };
+            let foo = unsafe { Node::new().into_shared().assume_safe_on_main_thread(token) };
             foo.set_name("foo");
 
             instance_id = foo.get_instance_id();
 
             assert!(unsafe { Reference::try_from_instance_id(instance_id).is_none() });
 
-            let reconstructed = unsafe { Node::from_instance_id(instance_id) };
+            // Nodes can only be recovered on the main thread.
+            let reconstructed = unsafe {
+                WeakObject::<Node>::from_instance_id(instance_id).upgrade_on_main_thread(token)
+            }
+            .expect("node should be alive");
             assert_eq!("foo", reconstructed.name().to_string());
 
             unsafe { foo.assume_unique().free() };
         }
 
-        assert!(unsafe { Node::try_from_instance_id(instance_id).is_none() });
+        assert!(unsafe { Object::try_from_instance_id(instance_id).is_none() });
 
         let instance_id;
 
@@ -243,7 +273,10 @@ fn test_from_instance_id() -> bool {
 
             instance_id = foo.get_instance_id();
 
-            assert!(unsafe { Node::try_from_instance_id(instance_id).is_none() });
+            assert!(unsafe {
+                WeakObject::<Node>::from_instance_id(instance_id).upgrade_on_main_thread(token)
+            }
+            .is_none());
 
             let reconstructed = unsafe { Reference::from_instance_id(instance_id) };
             assert_eq!(
@@ -267,13 +300,24 @@ fn init(handle: InitHandle) {
     handle.add_class::<Foo>();
     handle.add_class::<OptionalArgs>();
 
+    test_call::register(handle);
     test_derive::register(handle);
     test_free_ub::register(handle);
+    test_load::register(handle);
+    test_lock_diagnostics::register(handle);
+    test_node_ext::register(handle);
+    test_properties::register(handle);
+    test_reentrant::register(handle);
     test_register::register(handle);
+    test_reload::register(handle);
     test_return_leak::register(handle);
+    test_script_bindings::register(handle);
+    test_thread::register(handle);
     test_variant_call_args::register(handle);
     test_variant_ops::register(handle);
     test_vararray_return::register(handle);
+    test_virtuals::register(handle);
+    test_weak::register(handle);
 }
 
 gdnative::godot_init!(init);
@@ -0,0 +1,191 @@
+use gdnative::core_types::{CallError, FromVariantError, VariantType};
+use gdnative::object::MethodCallError;
+use gdnative::prelude::*;
+
+pub(crate) fn run_tests() -> bool {
+    let mut status = true;
+
+    status &= test_call_typed();
+    status &= test_call_typed_errors();
+    status &= test_callable();
+
+    status
+}
+
+pub(crate) fn register(handle: InitHandle) {
+    handle.add_class::<CallTyped>();
+}
+
+#[derive(NativeClass)]
+#[inherit(Reference)]
+struct CallTyped;
+
+impl CallTyped {
+    fn new(_owner: &Reference) -> Self {
+        CallTyped
+    }
+}
+
+#[methods]
+impl CallTyped {
+    #[export]
+    fn add(&self, _owner: &Reference, a: i64, b: i64) -> i64 {
+        a + b
+    }
+
+    #[export]
+    fn greet(&self, _owner: &Reference, name: String) -> String {
+        format!("Hello, {}!", name)
+    }
+}
+
+fn test_call_typed() -> bool {
+    println!(" -- test_call_typed");
+
+    let ok = std::panic::catch_unwind(|| {
+        let obj = CallTyped::new_instance().into_base().into_shared();
+        let obj = unsafe { obj.assume_safe() };
+
+        assert_eq!(Ok(42), unsafe { obj.call_typed::<i64>("add", (40, 2)) });
+        assert_eq!(Ok("Hello, Godot!".to_string()), unsafe {
+            obj.call_typed::<String>("greet", ("Godot",))
+        });
+        assert_eq!(Ok(3), unsafe {
+            obj.call_typed::<i64>("add", &[Variant::from_i64(1), Variant::from_i64(2)][..])
+        });
+
+        let token = MainThreadToken::try_get().expect("tests should run on the main thread");
+        let node = Node::new();
+        let node = unsafe { node.into_shared().assume_safe_on_main_thread(token) };
+
+        assert_eq!(Ok("Node".to_string()), unsafe {
+            node.call_typed::<String>("get_class", ())
+        });
+        assert_eq!(Ok(()), unsafe {
+            node.call_typed::<()>("set_name", ("Named",))
+        });
+        assert_eq!("Named", node.name().to_string());
+        assert_eq!(Ok(()), unsafe {
+            node.call_deferred_typed("set_name", ("Deferred",))
+        });
+
+        unsafe { node.assume_unique() }.free();
+    })
+    .is_ok();
+
+    if !ok {
+        gdnative::godot_error!("   !! Test test_call_typed failed");
+    }
+
+    ok
+}
+
+fn test_call_typed_errors() -> bool {
+    println!(" -- test_call_typed_errors");
+
+    let ok = std::panic::catch_unwind(|| {
+        let token = MainThreadToken::try_get().expect("tests should run on the main thread");
+        let node = Node::new();
+        let node = unsafe { node.into_shared().assume_safe_on_main_thread(token) };
+
+        let err = unsafe { node.call_typed::<()>("missing", ()).unwrap_err() };
+        assert_eq!(
+            MethodCallError::InvalidMethod {
+                method: "missing".into()
+            },
+            err
+        );
+        assert_eq!(Some(CallError::InvalidMethod), err.call_error());
+
+        assert_eq!(
+            Err(MethodCallError::TooFewArguments {
+                method: "set_name".into(),
+                expected: 1,
+            }),
+            unsafe { node.call_typed::<()>("set_name", ()) }
+        );
+        assert_eq!(
+            Err(MethodCallError::TooManyArguments {
+                method: "set_name".into(),
+                expected: 1,
+            }),
+            unsafe { node.call_typed::<()>("set_name", ("a", "b")) }
+        );
+        assert_eq!(
+            Err(MethodCallError::InvalidArgument {
+                method: "add_child".into(),
+                index: 0,
+                expected: VariantType::Object,
+            }),
+            unsafe { node.call_typed::<()>("add_child", (42,)) }
+        );
+        assert_eq!(
+            Err(MethodCallError::InvalidReturn {
+                method: "get_class".into(),
+                error: FromVariantError::InvalidVariantType {
+                    variant_type: VariantType::GodotString,
+                    expected: VariantType::I64,
+                },
+            }),
+            unsafe { node.call_typed::<i64>("get_class", ()) }
+        );
+
+        for method in &["free", "call", "callv", "call_deferred"] {
+            assert_eq!(
+                Err(MethodCallError::UnsafeMethod {
+                    method: method.to_string()
+                }),
+                unsafe { node.call_typed::<()>(method, ()) }
+            );
+        }
+
+        assert_eq!(
+            Err(MethodCallError::InvalidMethod {
+                method: "missing".into()
+            }),
+            unsafe { node.call_deferred_typed("missing", ()) }
+        );
+        assert_eq!(
+            Err(MethodCallError::TooManyArguments {
+                method: "set_name".into(),
+                expected: 5,
+            }),
+            unsafe { node.call_deferred_typed("set_name", (1, 2, 3, 4, 5, 6)) }
+        );
+
+        unsafe { node.assume_unique() }.free();
+    })
+    .is_ok();
+
+    if !ok {
+        gdnative::godot_error!("   !! Test test_call_typed_errors failed");
+    }
+
+    ok
+}
+
+fn test_callable() -> bool {
+    println!(" -- test_callable");
+
+    let ok = std::panic::catch_unwind(|| {
+        let obj = CallTyped::new_instance().into_base().into_shared();
+        let obj = unsafe { obj.assume_safe() };
+
+        let add = obj.callable("add");
+        assert!(add.is_valid());
+        assert_eq!("add", add.method());
+        assert_eq!(obj.as_ptr(), add.object().as_ptr());
+        assert_eq!(Ok(5), unsafe { add.call::<i64>((2, 3)) });
+
+        let missing: Callable<Reference> = obj.callable("missing");
+        assert!(!missing.is_valid());
+        assert!(unsafe { missing.call::<()>(()).is_err() });
+    })
+    .is_ok();
+
+    if !ok {
+        gdnative::godot_error!("   !! Test test_callable failed");
+    }
+
+    ok
+}
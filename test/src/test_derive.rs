@@ -1,4 +1,5 @@
 use std::cell::Cell;
+use std::collections::HashMap;
 
 use gdnative::prelude::*;
 
@@ -9,6 +10,8 @@ pub(crate) fn run_tests() -> bool {
     status &= test_derive_owned_to_variant();
     status &= test_derive_nativeclass_with_property_hooks();
     status &= test_derive_nativeclass_without_constructor();
+    status &= test_derive_nativeclass_with_dynamic_properties();
+    status &= test_derive_nativeclass_with_property_groups();
 
     status
 }
@@ -16,6 +19,8 @@ pub(crate) fn run_tests() -> bool {
 pub(crate) fn register(handle: InitHandle) {
     handle.add_class::<PropertyHooks>();
     handle.add_class::<EmplacementOnly>();
+    handle.add_class::<DynamicProps>();
+    handle.add_class::<PropertyGroups>();
 }
 
 fn test_derive_to_variant() -> bool {
@@ -314,3 +319,200 @@ fn test_derive_nativeclass_without_constructor() -> bool {
 
     ok
 }
+
+#[derive(NativeClass)]
+#[inherit(Node)]
+struct DynamicProps {
+    values: HashMap<String, i64>,
+}
+
+#[methods]
+impl DynamicProps {
+    fn new(_owner: &Node) -> Self {
+        let mut values = HashMap::new();
+        values.insert("foo".to_string(), 42);
+        DynamicProps { values }
+    }
+}
+
+impl DynamicProperties for DynamicProps {
+    fn get_property(&self, _owner: TRef<Node>, name: &str) -> Option<Variant> {
+        self.values.get(name).map(|value| value.to_variant())
+    }
+
+    fn set_property(&mut self, _owner: TRef<Node>, name: &str, value: &Variant) -> bool {
+        match (self.values.get_mut(name), value.try_to_i64()) {
+            (Some(slot), Some(value)) => {
+                *slot = value;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn property_list(&self, _owner: TRef<Node>) -> Vec<PropertyInfo> {
+        self.values
+            .keys()
+            .map(|name| PropertyInfo::builder::<i64>(name).done())
+            .collect()
+    }
+}
+
+fn test_derive_nativeclass_with_dynamic_properties() -> bool {
+    println!(" -- test_derive_nativeclass_with_dynamic_properties");
+
+    let ok = std::panic::catch_unwind(|| {
+        use gdnative::nativescript::user_data::Map;
+
+        let thing = Instance::<DynamicProps, _>::new();
+        let (owner, script) = thing.decouple();
+
+        assert_eq!(Some(42), owner.get("foo").try_to_i64());
+        assert!(owner.get("bar").is_nil());
+
+        owner.set("foo", 54);
+        assert_eq!(Some(54), owner.get("foo").try_to_i64());
+        script
+            .map(|script| {
+                assert_eq!(Some(&54), script.values.get("foo"));
+            })
+            .unwrap();
+
+        let has_foo = owner
+            .get_property_list()
+            .iter()
+            .filter_map(|info| info.try_to_dictionary())
+            .any(|info| info.get("name").try_to_string().as_deref() == Some("foo"));
+        assert!(has_foo);
+
+        owner.free();
+    })
+    .is_ok();
+
+    if !ok {
+        gdnative::godot_error!("   !! Test test_derive_nativeclass_with_dynamic_properties failed");
+    }
+
+    ok
+}
+
+#[derive(NativeClass)]
+#[inherit(Node)]
+#[register_with(Self::register_extra)]
+struct PropertyGroups {
+    #[property(group = "Movement")]
+    speed: f64,
+    #[property]
+    label: GodotString,
+    #[property(group = "Movement")]
+    acceleration: f64,
+    #[property(group = "Visuals", prefix = "visual_")]
+    visual_layer: i64,
+}
+
+#[methods]
+impl PropertyGroups {
+    fn new(_owner: &Node) -> Self {
+        PropertyGroups {
+            speed: 1.0,
+            label: GodotString::new(),
+            acceleration: 2.0,
+            visual_layer: 3,
+        }
+    }
+
+    fn register_extra(builder: &ClassBuilder<Self>) {
+        // The derived groups are already ended.
+        builder.end_property_group();
+
+        builder
+            .add_property::<i64>("extra")
+            .with_getter(|_, _| 42)
+            .done();
+
+        builder.add_property_group("Extra", "");
+        builder
+            .add_property::<i64>("grouped_extra")
+            .with_getter(|_, _| 43)
+            .done();
+        builder.end_property_group();
+        builder.end_property_group();
+
+        builder
+            .add_property::<i64>("after")
+            .with_getter(|_, _| 44)
+            .done();
+    }
+}
+
+fn test_derive_nativeclass_with_property_groups() -> bool {
+    println!(" -- test_derive_nativeclass_with_property_groups");
+
+    let ok = std::panic::catch_unwind(|| {
+        let thing = Instance::<PropertyGroups, _>::new();
+        let (owner, _script) = thing.decouple();
+
+        let names = owner
+            .get_property_list()
+            .iter()
+            .filter_map(|info| info.try_to_dictionary())
+            .filter_map(|info| {
+                let name = info.get("name").try_to_string()?;
+                let usage = info.get("usage").try_to_i64()?;
+                let hint_string = info.get("hint_string").try_to_string()?;
+                Some((name, usage, hint_string))
+            })
+            .collect::<Vec<_>>();
+
+        let position = |name: &str| {
+            names
+                .iter()
+                .position(|(n, _, _)| n == name)
+                .expect("property should be listed")
+        };
+
+        let group = position("Movement");
+        assert_ne!(0, names[group].1 & PropertyUsage::GROUP.bits() as i64);
+        assert!(position("label") < group);
+        assert!(group < position("speed"));
+        assert!(position("speed") < position("acceleration"));
+
+        let visuals = position("Visuals");
+        assert_eq!("visual_", names[visuals].2);
+        assert!(position("acceleration") < visuals);
+        assert!(visuals < position("visual_layer"));
+
+        // Groups are ended with a group whose prefix no property matches, and only once.
+        let end = position("/end_group_0");
+        assert_ne!(0, names[end].1 & PropertyUsage::GROUP.bits() as i64);
+        assert_eq!("/end_group_0", names[end].2);
+        assert!(position("visual_layer") < end);
+        assert!(end < position("extra"));
+
+        let end = position("/end_group_1");
+        assert!(position("grouped_extra") < end);
+        assert!(end < position("after"));
+
+        assert!(!names
+            .iter()
+            .any(|(n, _, _)| n.is_empty() || n == "/end_group_2"));
+
+        assert_eq!(Some(1.0), owner.get("speed").try_to_f64());
+        assert_eq!(Some(2.0), owner.get("acceleration").try_to_f64());
+        assert_eq!(Some(3), owner.get("visual_layer").try_to_i64());
+        assert_eq!(Some(42), owner.get("extra").try_to_i64());
+
+        // Separators can't be read or written.
+        owner.set("Movement", 5.0);
+        assert!(owner.get("Movement").is_nil());
+
+        owner.free();
+    })
+    .is_ok();
+
+    if !ok {
+        gdnative::godot_error!("   !! Test test_derive_nativeclass_with_property_groups failed");
+    }
+
+    ok
+}
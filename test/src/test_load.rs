@@ -0,0 +1,146 @@
+use gdnative::api::utils::{load, InstanceError, LoadError, PackedSceneExt, Preload};
+use gdnative::prelude::*;
+
+pub(crate) fn run_tests() -> bool {
+    let mut status = true;
+
+    status &= test_load();
+    status &= test_preload();
+    status &= test_instance_as();
+
+    status
+}
+
+pub(crate) fn register(handle: InitHandle) {
+    handle.add_class::<SceneRoot>();
+}
+
+#[derive(NativeClass)]
+#[inherit(Node2D)]
+struct SceneRoot;
+
+impl SceneRoot {
+    fn new(_owner: &Node2D) -> Self {
+        SceneRoot
+    }
+}
+
+#[methods]
+impl SceneRoot {}
+
+fn test_load() -> bool {
+    println!(" -- test_load");
+
+    let ok = std::panic::catch_unwind(|| {
+        let scene = load::<PackedScene>("res://Scene.tscn").expect("should load");
+        let scene = unsafe { scene.assume_safe() };
+        assert_eq!("res://Scene.tscn", scene.path().to_string());
+
+        assert_eq!(
+            Err(LoadError::NotFound {
+                path: "res://missing.tscn".into()
+            }),
+            load::<PackedScene>("res://missing.tscn").map(|_| ())
+        );
+
+        assert_eq!(
+            Err(LoadError::WrongClass {
+                path: "res://main.gd".into(),
+                class: "GDScript".into(),
+                expected: "PackedScene",
+            }),
+            load::<PackedScene>("res://main.gd").map(|_| ())
+        );
+    })
+    .is_ok();
+
+    if !ok {
+        gdnative::godot_error!("   !! Test test_load failed");
+    }
+
+    ok
+}
+
+fn test_preload() -> bool {
+    println!(" -- test_preload");
+
+    static SCENE: Preload<PackedScene> = Preload::new("res://Scene.tscn");
+    static MISSING: Preload<PackedScene> = Preload::new("res://missing.tscn");
+
+    let ok = std::panic::catch_unwind(|| {
+        let first = SCENE.get().expect("should load");
+        let second = SCENE.get().expect("should be kept");
+        assert_eq!(first, second);
+
+        assert!(MISSING.get().is_err());
+        assert!(!MISSING.is_loaded());
+        assert_eq!("res://missing.tscn", MISSING.path());
+
+        SCENE.clear();
+        assert!(!SCENE.is_loaded());
+        assert!(SCENE.get().is_ok());
+    })
+    .is_ok();
+
+    // The statics outlive the test, so the resources are released here.
+    SCENE.clear();
+    MISSING.clear();
+
+    if !ok {
+        gdnative::godot_error!("   !! Test test_preload failed");
+    }
+
+    ok
+}
+
+fn test_instance_as() -> bool {
+    println!(" -- test_instance_as");
+
+    let ok = std::panic::catch_unwind(|| {
+        let plain = pack(Node2D::new());
+        let plain = plain.as_ref();
+
+        let root = plain.instance_as::<Node2D>().expect("should instance");
+        root.free();
+
+        assert_eq!(
+            Err(InstanceError::WrongClass {
+                path: "".into(),
+                class: "Node2D".into(),
+                expected: "Spatial",
+            }),
+            plain.instance_as::<Spatial>().map(|_| ())
+        );
+
+        assert_eq!(
+            Err(InstanceError::WrongScript {
+                path: "".into(),
+                expected: "SceneRoot",
+            }),
+            plain.instance_as_script::<SceneRoot>().map(|_| ())
+        );
+
+        let scripted = pack(SceneRoot::new_instance().into_base());
+        let root = scripted
+            .as_ref()
+            .instance_as_script::<SceneRoot>()
+            .expect("should instance");
+        root.into_base().free();
+    })
+    .is_ok();
+
+    if !ok {
+        gdnative::godot_error!("   !! Test test_instance_as failed");
+    }
+
+    ok
+}
+
+/// Packs `root` into a new scene, and frees it.
+fn pack(root: Ref<Node2D, Unique>) -> Ref<PackedScene, Unique> {
+    let root = root.into_shared();
+    let scene = PackedScene::new();
+    scene.pack(root).expect("should pack");
+    unsafe { root.assume_unique() }.free();
+    scene
+}
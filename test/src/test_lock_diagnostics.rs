@@ -0,0 +1,110 @@
+use std::cell::RefCell;
+
+use gdnative::nativescript::user_data::diagnostics::{self, LockFailure};
+use gdnative::nativescript::user_data::{DeadlockPolicy, LockOptions};
+use gdnative::prelude::*;
+
+thread_local! {
+    /// Failure recorded by `LockProbe::outer` when locking the instance directly.
+    static DIRECT_FAILURE: RefCell<Option<LockFailure>> = RefCell::new(None);
+}
+
+pub(crate) fn run_tests() -> bool {
+    let mut status = true;
+
+    status &= test_lock_diagnostics();
+
+    status
+}
+
+pub(crate) fn register(handle: InitHandle) {
+    handle.add_class::<LockProbe>();
+}
+
+struct FailFast;
+
+impl LockOptions for FailFast {
+    const DEADLOCK_POLICY: DeadlockPolicy = DeadlockPolicy::Pessimistic;
+}
+
+/// Calls itself while holding the lock, which fails with the pessimistic policy.
+struct LockProbe;
+
+impl NativeClass for LockProbe {
+    type Base = Reference;
+    type UserData = user_data::MutexData<LockProbe, FailFast>;
+    fn class_name() -> &'static str {
+        "LockProbe"
+    }
+    fn init(_owner: TRef<Reference>) -> LockProbe {
+        LockProbe
+    }
+    fn register_properties(_builder: &ClassBuilder<Self>) {}
+}
+
+#[methods]
+impl LockProbe {
+    #[export]
+    fn outer(&mut self, owner: TRef<Reference>) -> bool {
+        // The lock failure is reported by the method wrapper of `inner`, which returns `Nil`.
+        let ret = unsafe { owner.call("inner", &[]) };
+
+        // Locking the instance directly leaves the failure to be inspected by the test.
+        let instance = owner
+            .cast_instance::<LockProbe>()
+            .expect("owner should be a LockProbe");
+        let locked = instance.map(|_, _| ()).is_ok();
+        DIRECT_FAILURE.with(|failure| *failure.borrow_mut() = diagnostics::take_last_failure());
+
+        ret.is_nil() && !locked
+    }
+
+    #[export]
+    fn inner(&mut self, _owner: TRef<Reference>) -> bool {
+        true
+    }
+}
+
+fn test_lock_diagnostics() -> bool {
+    println!(" -- test_lock_diagnostics");
+
+    let ok = std::panic::catch_unwind(|| {
+        let was_enabled = diagnostics::is_enabled();
+        diagnostics::set_enabled(true);
+
+        let obj = LockProbe::new_instance();
+        let base = obj.into_base();
+
+        let before = diagnostics::contention::<LockProbe>();
+
+        assert_eq!(Some(true), unsafe { base.call("outer", &[]).try_to_bool() });
+
+        let after = diagnostics::contention::<LockProbe>();
+        assert_eq!(before.contended + 2, after.contended);
+        assert_eq!(before.failed + 2, after.failed);
+
+        let failure = DIRECT_FAILURE
+            .with(|failure| failure.borrow_mut().take())
+            .expect("a failure should be recorded");
+        assert_eq!("LockProbe", failure.class_name());
+        assert_eq!(Some("outer"), failure.method());
+
+        let holders = failure.holders();
+        assert_eq!(1, holders.len());
+        assert_eq!("LockProbe", holders[0].class_name());
+        assert_eq!(Some("outer"), holders[0].method());
+        assert_eq!(std::thread::current().id(), holders[0].thread_id());
+
+        // The failure was already taken by the method wrapper.
+        assert!(diagnostics::take_last_failure().is_none());
+
+        diagnostics::set_enabled(was_enabled);
+    })
+    .is_ok();
+
+    if !ok {
+        gdnative::godot_error!("   !! Test test_lock_diagnostics failed");
+    }
+
+    ok
+}
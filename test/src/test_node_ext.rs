@@ -0,0 +1,196 @@
+use gdnative::api::utils::GetNodeError;
+use gdnative::prelude::*;
+
+pub(crate) fn run_tests() -> bool {
+    let mut status = true;
+
+    status &= test_get_node_as();
+    status &= test_get_node_as_instance();
+    status &= test_node_fields();
+
+    status
+}
+
+pub(crate) fn register(handle: InitHandle) {
+    handle.add_class::<NodeExtProbe>();
+    handle.add_class::<NodeFields>();
+}
+
+#[derive(NativeClass)]
+#[inherit(Node)]
+struct NodeExtProbe {
+    value: i64,
+}
+
+impl NodeExtProbe {
+    fn new(_owner: &Node) -> Self {
+        NodeExtProbe { value: 42 }
+    }
+}
+
+#[methods]
+impl NodeExtProbe {}
+
+#[derive(NativeClass)]
+#[inherit(Node)]
+struct NodeFields {
+    #[node("sprite")]
+    sprite: Option<Ref<Node2D>>,
+    #[node("probe")]
+    probe: Option<Instance<NodeExtProbe, Shared>>,
+    #[node("missing")]
+    missing: Option<Ref<Node>>,
+}
+
+impl NodeFields {
+    fn new(_owner: &Node) -> Self {
+        NodeFields {
+            sprite: None,
+            probe: None,
+            missing: None,
+        }
+    }
+}
+
+#[methods]
+impl NodeFields {
+    #[export]
+    fn _ready(&mut self, _owner: &Node) {}
+
+    #[export]
+    fn resolved(&self, _owner: &Node) -> bool {
+        let token = MainThreadToken::try_get().expect("tests should run on the main thread");
+        let probe_value = self.probe.as_ref().map(|probe| {
+            let probe = unsafe { probe.assume_safe_on_main_thread(token) };
+            probe.map(|probe, _| probe.value).unwrap()
+        });
+
+        self.sprite.is_some() && probe_value == Some(42) && self.missing.is_none()
+    }
+}
+
+/// Creates a node with the children `sprite` (a `Node2D`) and `probe` (a `NodeExtProbe`).
+fn make_tree(parent: TRef<Node>) {
+    let sprite = Node2D::new();
+    sprite.set_name("sprite");
+    parent.add_child(sprite, false);
+
+    let probe = NodeExtProbe::new_instance().into_base();
+    probe.set_name("probe");
+    parent.add_child(probe, false);
+}
+
+fn test_get_node_as() -> bool {
+    println!(" -- test_get_node_as");
+
+    let ok = std::panic::catch_unwind(|| {
+        let token = MainThreadToken::try_get().expect("tests should run on the main thread");
+        let parent = Node::new().into_shared();
+        let parent = unsafe { parent.assume_safe_on_main_thread(token) };
+        make_tree(parent);
+
+        let sprite = unsafe { parent.get_node_as::<Node2D>("sprite") }.expect("should exist");
+        assert_eq!("sprite", sprite.name().to_string());
+        assert!(unsafe { parent.get_node_as::<Node>(node_path!("sprite")) }.is_some());
+
+        assert_eq!(
+            Err(GetNodeError::NotFound {
+                path: "missing".into()
+            }),
+            unsafe { parent.try_get_node_as::<Node>("missing") }.map(|_| ())
+        );
+
+        assert_eq!(
+            Err(GetNodeError::WrongClass {
+                path: "sprite".into(),
+                class: "Node2D".into(),
+                expected: "Spatial",
+            }),
+            unsafe { parent.try_get_node_as::<Spatial>("sprite") }.map(|_| ())
+        );
+
+        unsafe { parent.claim().assume_unique().free() };
+    })
+    .is_ok();
+
+    if !ok {
+        gdnative::godot_error!("   !! Test test_get_node_as failed");
+    }
+
+    ok
+}
+
+fn test_get_node_as_instance() -> bool {
+    println!(" -- test_get_node_as_instance");
+
+    let ok = std::panic::catch_unwind(|| {
+        let token = MainThreadToken::try_get().expect("tests should run on the main thread");
+        let parent = Node::new().into_shared();
+        let parent = unsafe { parent.assume_safe_on_main_thread(token) };
+        make_tree(parent);
+
+        let probe =
+            unsafe { parent.get_node_as_instance::<NodeExtProbe>("probe") }.expect("should exist");
+        assert_eq!(42, probe.map(|probe, _| probe.value).unwrap());
+
+        assert_eq!(
+            Err(GetNodeError::WrongScript {
+                path: "sprite".into(),
+                expected: "NodeExtProbe",
+            }),
+            unsafe { parent.try_get_node_as_instance::<NodeExtProbe>("sprite") }.map(|_| ())
+        );
+
+        assert_eq!(
+            Err(GetNodeError::WrongClass {
+                path: "probe".into(),
+                class: "Node".into(),
+                expected: "Node2D",
+            }),
+            unsafe { parent.try_get_node_as::<Node2D>("probe") }.map(|_| ())
+        );
+
+        unsafe { parent.claim().assume_unique().free() };
+    })
+    .is_ok();
+
+    if !ok {
+        gdnative::godot_error!("   !! Test test_get_node_as_instance failed");
+    }
+
+    ok
+}
+
+fn test_node_fields() -> bool {
+    println!(" -- test_node_fields");
+
+    let ok = std::panic::catch_unwind(|| {
+        let token = MainThreadToken::try_get().expect("tests should run on the main thread");
+        let instance = NodeFields::new_instance().into_shared();
+        let instance = unsafe { instance.assume_safe_on_main_thread(token) };
+        let base = instance.base();
+        make_tree(base);
+
+        assert_eq!(
+            Some(false),
+            unsafe { base.call("resolved", &[]) }.try_to_bool()
+        );
+
+        // `missing` is reported as an error, but doesn't prevent other fields from resolving.
+        unsafe { base.call("_ready", &[]) };
+        assert_eq!(
+            Some(true),
+            unsafe { base.call("resolved", &[]) }.try_to_bool()
+        );
+
+        drop(instance);
+        unsafe { base.claim().assume_unique().free() };
+    })
+    .is_ok();
+
+    if !ok {
+        gdnative::godot_error!("   !! Test test_node_fields failed");
+    }
+
+    ok
+}
@@ -0,0 +1,59 @@
+use gdnative::api::light::Param;
+use gdnative::api::sprite::SpriteProperty;
+use gdnative::api::{OmniLight, Sprite};
+use gdnative::prelude::*;
+
+pub(crate) fn run_tests() -> bool {
+    let mut status = true;
+
+    status &= test_indexed_property_accessors();
+    status &= test_property_enum();
+
+    status
+}
+
+pub(crate) fn register(_handle: InitHandle) {}
+
+fn test_indexed_property_accessors() -> bool {
+    println!(" -- test_indexed_property_accessors");
+
+    let ok = std::panic::catch_unwind(|| {
+        let light = OmniLight::new();
+
+        light.set_light_energy(2.0);
+        assert!((light.light_energy() - 2.0).abs() < f64::EPSILON);
+        assert!((light.param(Param::ENERGY.0) - 2.0).abs() < f64::EPSILON);
+
+        light.free();
+    })
+    .is_ok();
+
+    if !ok {
+        gdnative::godot_error!("   !! Test test_indexed_property_accessors failed");
+    }
+
+    ok
+}
+
+fn test_property_enum() -> bool {
+    println!(" -- test_property_enum");
+
+    let ok = std::panic::catch_unwind(|| {
+        assert_eq!("flip_h", SpriteProperty::FlipH.as_str());
+
+        let sprite = Sprite::new();
+
+        sprite.set(SpriteProperty::FlipH, true);
+        assert!(sprite.is_flipped_h());
+        assert_eq!(Some(true), sprite.get(SpriteProperty::FlipH).try_to_bool());
+
+        sprite.free();
+    })
+    .is_ok();
+
+    if !ok {
+        gdnative::godot_error!("   !! Test test_property_enum failed");
+    }
+
+    ok
+}
@@ -0,0 +1,113 @@
+use std::cell::Cell;
+
+use gdnative::api::object::ConnectFlags;
+use gdnative::prelude::*;
+
+pub(crate) fn run_tests() -> bool {
+    let mut status = true;
+
+    status &= test_reentrant_signal();
+
+    status
+}
+
+pub(crate) fn register(handle: InitHandle) {
+    handle.add_class::<ReentrantEmitter>();
+}
+
+/// Emits a signal connected to its own `emit` method, so that every call re-enters the instance
+/// while it's already in use, until `depth` reaches zero.
+#[derive(Default)]
+struct ReentrantEmitter {
+    received: Cell<i64>,
+    nesting: Cell<i64>,
+    max_nesting: Cell<i64>,
+}
+
+impl NativeClass for ReentrantEmitter {
+    type Base = Reference;
+    type UserData = user_data::ReentrantData<ReentrantEmitter>;
+    fn class_name() -> &'static str {
+        "ReentrantEmitter"
+    }
+    fn init(_owner: TRef<Reference>) -> ReentrantEmitter {
+        ReentrantEmitter::default()
+    }
+    fn register_properties(builder: &ClassBuilder<Self>) {
+        builder.add_signal(Signal {
+            name: "reenter",
+            args: &[SignalArgument {
+                name: "depth",
+                default: Variant::new(),
+                export_info: ExportInfo::new(VariantType::I64),
+                usage: PropertyUsage::DEFAULT,
+            }],
+        });
+    }
+}
+
+#[methods]
+impl ReentrantEmitter {
+    #[export]
+    fn emit(&self, owner: TRef<Reference>, depth: i64) {
+        self.received.set(self.received.get() + 1);
+        self.nesting.set(self.nesting.get() + 1);
+        self.max_nesting
+            .set(self.max_nesting.get().max(self.nesting.get()));
+
+        if depth > 0 {
+            owner.emit_signal("reenter", &[(depth - 1).to_variant()]);
+        }
+
+        self.nesting.set(self.nesting.get() - 1);
+    }
+}
+
+fn test_reentrant_signal() -> bool {
+    println!(" -- test_reentrant_signal");
+
+    let ok = std::panic::catch_unwind(|| {
+        const ITERATIONS: i64 = 100;
+        const DEPTH: i64 = 32;
+
+        let obj = ReentrantEmitter::new_instance().into_shared();
+        let obj = unsafe { obj.assume_safe() };
+        let base = obj.base();
+
+        base.connect(
+            "reenter",
+            base,
+            "emit",
+            VariantArray::new_shared(),
+            ConnectFlags::empty(),
+        )
+        .unwrap();
+
+        for i in 1..=ITERATIONS {
+            unsafe { base.call("emit", &[DEPTH.to_variant()]) };
+
+            let (received, nesting, max_nesting) = obj
+                .map(|this, _| {
+                    (
+                        this.received.get(),
+                        this.nesting.get(),
+                        this.max_nesting.get(),
+                    )
+                })
+                .expect("lock should be free after the calls return");
+
+            assert_eq!(i * (DEPTH + 1), received);
+            assert_eq!(0, nesting);
+            assert_eq!(DEPTH + 1, max_nesting);
+        }
+
+        base.disconnect("reenter", base, "emit");
+    })
+    .is_ok();
+
+    if !ok {
+        gdnative::godot_error!("   !! Test test_reentrant_signal failed");
+    }
+
+    ok
+}
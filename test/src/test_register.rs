@@ -1,9 +1,11 @@
+use gdnative::nativescript::init::property::{DictionaryHint, NodePathHint};
 use gdnative::prelude::*;
 
 pub(crate) fn run_tests() -> bool {
     let mut status = true;
 
     status &= test_register_property();
+    status &= test_register_property_hints();
 
     status
 }
@@ -11,6 +13,7 @@ pub(crate) fn run_tests() -> bool {
 pub(crate) fn register(handle: InitHandle) {
     handle.add_class::<RegisterSignal>();
     handle.add_class::<RegisterProperty>();
+    handle.add_class::<RegisterPropertyHints>();
 }
 
 #[derive(Copy, Clone, Debug, Default)]
@@ -109,3 +112,108 @@ fn test_register_property() -> bool {
 
     ok
 }
+
+#[derive(Default)]
+struct RegisterPropertyHints {
+    values: Vec<i64>,
+    target: NodePath,
+    weights: Dictionary,
+}
+
+impl NativeClass for RegisterPropertyHints {
+    type Base = Reference;
+    type UserData = user_data::MutexData<RegisterPropertyHints>;
+    fn class_name() -> &'static str {
+        "RegisterPropertyHints"
+    }
+    fn init(_owner: TRef<Reference>) -> RegisterPropertyHints {
+        RegisterPropertyHints::default()
+    }
+    fn register_properties(builder: &ClassBuilder<Self>) {
+        builder
+            .add_property::<Vec<i64>>("values")
+            .with_hint((0..=10).into())
+            .with_ref_getter(|this: &Self, _owner: TRef<Reference>| &this.values)
+            .with_setter(|this: &mut Self, _owner: TRef<Reference>, v| this.values = v)
+            .done();
+
+        builder
+            .add_property::<NodePath>("target")
+            .with_hint(NodePathHint::ValidTypes(vec![
+                "Spatial".into(),
+                "Node2D".into(),
+            ]))
+            .with_ref_getter(|this: &Self, _owner: TRef<Reference>| &this.target)
+            .with_setter(|this: &mut Self, _owner: TRef<Reference>, v| this.target = v)
+            .done();
+
+        builder
+            .add_property::<Dictionary>("weights")
+            .with_hint(DictionaryHint::with_types::<String, f64>(
+                None,
+                Some((0.0..=1.0).into()),
+            ))
+            .with_ref_getter(|this: &Self, _owner: TRef<Reference>| &this.weights)
+            .with_setter(|this: &mut Self, _owner: TRef<Reference>, v| this.weights = v)
+            .done();
+    }
+}
+
+#[methods]
+impl RegisterPropertyHints {}
+
+fn test_register_property_hints() -> bool {
+    println!(" -- test_register_property_hints");
+
+    let ok = std::panic::catch_unwind(|| {
+        let obj = RegisterPropertyHints::new_instance();
+        let base = obj.into_base();
+
+        let find = |name: &str| {
+            base.get_property_list()
+                .iter()
+                .filter_map(|info| info.try_to_dictionary())
+                .find(|info| info.get("name").try_to_string().as_deref() == Some(name))
+                .expect("property should be listed")
+        };
+
+        let values = find("values");
+        assert_eq!(
+            Some(VariantType::VariantArray as i64),
+            values.get("type").try_to_i64()
+        );
+        assert_eq!(
+            Some(format!("{}/1:0,10", VariantType::I64 as i64)),
+            values.get("hint_string").try_to_string()
+        );
+
+        let target = find("target");
+        assert_eq!(
+            Some("Spatial,Node2D".to_string()),
+            target.get("hint_string").try_to_string()
+        );
+
+        let weights = find("weights");
+        assert_eq!(
+            Some(format!(
+                "{}/0:;{}/1:0,1",
+                VariantType::GodotString as i64,
+                VariantType::F64 as i64
+            )),
+            weights.get("hint_string").try_to_string()
+        );
+
+        base.set("values", vec![1i64, 2, 3].to_variant());
+        assert_eq!(
+            Some(vec![1, 2, 3]),
+            Vec::<i64>::from_variant(&base.get("values")).ok()
+        );
+    })
+    .is_ok();
+
+    if !ok {
+        gdnative::godot_error!("   !! Test test_register_property_hints failed");
+    }
+
+    ok
+}
@@ -0,0 +1,76 @@
+use gdnative::nativescript::reload;
+use gdnative::prelude::*;
+
+pub(crate) fn run_tests() -> bool {
+    let mut status = true;
+
+    status &= test_reload_restores_state();
+
+    status
+}
+
+pub(crate) fn register(handle: InitHandle) {
+    handle.add_reloadable_class::<ReloadCounter>();
+}
+
+#[derive(NativeClass)]
+#[inherit(Reference)]
+struct ReloadCounter {
+    count: i64,
+}
+
+#[methods]
+impl ReloadCounter {
+    fn new(_owner: &Reference) -> Self {
+        ReloadCounter { count: 0 }
+    }
+}
+
+impl HotReload for ReloadCounter {
+    type State = i64;
+
+    fn save_state(&self, _owner: TRef<Reference>) -> i64 {
+        self.count
+    }
+
+    fn restore_state(_owner: TRef<Reference>, count: i64) -> Self {
+        ReloadCounter { count }
+    }
+}
+
+// The library can't be reloaded while the tests are running, so this only saves and restores
+// the states as `gdnative_terminate` and `nativescript_init` would.
+fn test_reload_restores_state() -> bool {
+    println!(" -- test_reload_restores_state");
+
+    let ok = std::panic::catch_unwind(|| {
+        let instance = ReloadCounter::new_instance();
+        instance
+            .map_mut(|counter, _| counter.count = 3)
+            .expect("counter should not be locked");
+
+        let base = instance.into_base();
+        let script = base.get_script().expect("script should be attached");
+
+        reload::test_reload();
+
+        let instance = Instance::<ReloadCounter, _>::try_from_base(base)
+            .expect("script should be attached again");
+        assert_eq!(Ok(3), instance.map(|counter, _| counter.count));
+
+        // States are only restored once.
+        let base = instance.into_base();
+        base.set_script(Reference::null());
+        base.set_script(script);
+        let instance = Instance::<ReloadCounter, _>::try_from_base(base)
+            .expect("script should be attached again");
+        assert_eq!(Ok(0), instance.map(|counter, _| counter.count));
+    })
+    .is_ok();
+
+    if !ok {
+        gdnative::godot_error!("   !! Test test_reload_restores_state failed");
+    }
+
+    ok
+}
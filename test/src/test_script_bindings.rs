@@ -0,0 +1,68 @@
+use gdnative::api::object::ConnectFlags;
+use gdnative::api::utils::load;
+use gdnative::api::Script;
+use gdnative::prelude::*;
+
+// Wrappers for `project/script_bindings.gd`, generated by the build script.
+include!(concat!(env!("OUT_DIR"), "/script_bindings.rs"));
+
+pub(crate) fn run_tests() -> bool {
+    let mut status = true;
+
+    status &= test_script_bindings();
+
+    status
+}
+
+pub(crate) fn register(_handle: InitHandle) {}
+
+fn test_script_bindings() -> bool {
+    println!(" -- test_script_bindings");
+
+    let ok = std::panic::catch_unwind(|| {
+        let token = MainThreadToken::try_get().expect("tests should run on the main thread");
+        let script = load::<Script>(ScriptBindings::PATH).expect("should load");
+
+        let node = Node::new();
+        node.set_script(script);
+
+        let node = node.into_shared();
+        let other = Node::new().into_shared();
+        let node = unsafe { node.assume_safe_on_main_thread(token) };
+        let other = unsafe { other.assume_safe_on_main_thread(token) };
+
+        assert!(ScriptBindings::cast(other).is_none());
+        let wrapper = ScriptBindings::cast(node).expect("script should be attached");
+        assert_eq!("ScriptBindings", ScriptBindings::CLASS_NAME);
+
+        unsafe {
+            assert_eq!(Ok(100), wrapper.health());
+            assert_eq!(Ok(90), wrapper.take_damage(10));
+
+            wrapper.set_health(50);
+            assert_eq!(Ok(50), wrapper.health());
+
+            wrapper.set_target(other);
+            assert_eq!(Some(other.claim()), wrapper.target().unwrap());
+
+            assert_eq!("damaged", ScriptBindings::SIGNAL_DAMAGED);
+            wrapper
+                .connect_damaged(node, "_on_damaged", ConnectFlags::empty())
+                .expect("should connect");
+            wrapper.emit_damaged(5);
+            assert_eq!(Ok(5), wrapper.last_damage());
+        }
+
+        unsafe {
+            node.claim().assume_unique().free();
+            other.claim().assume_unique().free();
+        }
+    })
+    .is_ok();
+
+    if !ok {
+        gdnative::godot_error!("   !! Test test_script_bindings failed");
+    }
+
+    ok
+}
@@ -0,0 +1,58 @@
+use gdnative::api::{Engine, VisualServer};
+use gdnative::prelude::*;
+
+pub(crate) fn run_tests() -> bool {
+    let mut status = true;
+
+    status &= test_main_thread_token();
+    status &= test_main_thread_methods();
+
+    status
+}
+
+pub(crate) fn register(_handle: InitHandle) {}
+
+fn test_main_thread_token() -> bool {
+    println!(" -- test_main_thread_token");
+
+    let ok = std::panic::catch_unwind(|| {
+        assert!(MainThreadToken::try_get().is_some());
+
+        let other_thread = std::thread::spawn(|| MainThreadToken::try_get().is_some());
+        assert!(!other_thread.join().unwrap());
+    })
+    .is_ok();
+
+    if !ok {
+        gdnative::godot_error!("   !! Test test_main_thread_token failed");
+    }
+
+    ok
+}
+
+fn test_main_thread_methods() -> bool {
+    println!(" -- test_main_thread_methods");
+
+    let ok = std::panic::catch_unwind(|| {
+        let token = MainThreadToken::try_get().expect("tests should run on the main thread");
+
+        let main_loop = Engine::godot_singleton()
+            .get_main_loop(token)
+            .expect("main loop should exist");
+        let main_loop = unsafe { main_loop.assume_safe_on_main_thread(token) };
+        assert!(main_loop.cast::<SceneTree>().is_some());
+
+        let visual_server = VisualServer::godot_singleton_main_thread(token);
+        assert!(visual_server
+            .get_class()
+            .to_string()
+            .contains("VisualServer"));
+    })
+    .is_ok();
+
+    if !ok {
+        gdnative::godot_error!("   !! Test test_main_thread_methods failed");
+    }
+
+    ok
+}
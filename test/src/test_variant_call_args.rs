@@ -47,6 +47,16 @@ impl VariantCallArgs {
     fn three(&mut self, _owner: &Reference, a: i32, b: i32, c: i32) -> i32 {
         a * 42 + b * c
     }
+
+    #[export]
+    fn static_zero() -> i32 {
+        42
+    }
+
+    #[export]
+    fn static_two(a: i32, #[opt] b: i32) -> i32 {
+        a * 42 + b
+    }
 }
 
 fn test_variant_call_args() -> bool {
@@ -86,6 +96,28 @@ fn test_variant_call_args() -> bool {
             .unwrap()
             .try_to_i64()
         );
+
+        assert_eq!(
+            Some(42),
+            base.call("static_zero", &[]).unwrap().try_to_i64()
+        );
+
+        assert_eq!(
+            Some(-42),
+            base.call("static_two", &[Variant::from_i64(-1)])
+                .unwrap()
+                .try_to_i64()
+        );
+
+        assert_eq!(
+            Some(-10),
+            base.call(
+                "static_two",
+                &[Variant::from_i64(-1), Variant::from_i64(32)]
+            )
+            .unwrap()
+            .try_to_i64()
+        );
     })
     .is_ok();
 
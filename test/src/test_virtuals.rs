@@ -0,0 +1,66 @@
+use gdnative::prelude::*;
+
+pub(crate) fn run_tests() -> bool {
+    let mut status = true;
+
+    status &= test_virtual_vec_return();
+
+    status
+}
+
+pub(crate) fn register(handle: InitHandle) {
+    handle.add_class::<VirtualReturns>();
+}
+
+/// Overrides a virtual method declared to return an `Array` with a `Vec` return type, which
+/// must pass the compile-time signature check.
+#[derive(NativeClass)]
+#[inherit(Reference)]
+struct VirtualReturns;
+
+impl VirtualReturns {
+    fn new(_owner: &Reference) -> Self {
+        VirtualReturns
+    }
+}
+
+#[methods]
+impl VirtualReturns {
+    #[export]
+    fn _get_property_list(&self, _owner: &Reference) -> Vec<Dictionary> {
+        let property = Dictionary::new();
+        property.insert("name", "virtual_property");
+        property.insert("type", VariantType::I64 as i64);
+        vec![property.into_shared()]
+    }
+}
+
+fn test_virtual_vec_return() -> bool {
+    println!(" -- test_virtual_vec_return");
+
+    let ok = std::panic::catch_unwind(|| {
+        let obj = VirtualReturns::new_instance();
+        let base = obj.into_base();
+
+        let list = unsafe { base.call("_get_property_list", &[]) }
+            .try_to_array()
+            .expect("should return an array");
+        assert_eq!(1, list.len());
+
+        let property = list
+            .get(0)
+            .try_to_dictionary()
+            .expect("should be a dictionary");
+        assert_eq!(
+            Some(GodotString::from("virtual_property")),
+            property.get("name").try_to_godot_string()
+        );
+    })
+    .is_ok();
+
+    if !ok {
+        gdnative::godot_error!("   !! Test test_virtual_vec_return failed");
+    }
+
+    ok
+}
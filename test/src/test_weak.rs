@@ -0,0 +1,188 @@
+use gdnative::prelude::*;
+
+pub(crate) fn run_tests() -> bool {
+    let mut status = true;
+
+    status &= test_weak_ref_freed();
+    status &= test_weak_ref_queue_freed();
+    status &= test_weak_instance_rescripted();
+
+    status
+}
+
+pub(crate) fn register(handle: InitHandle) {
+    handle.add_class::<WeakProbe>();
+    handle.add_class::<OtherWeakProbe>();
+    handle.add_class::<WeakTracker>();
+}
+
+struct WeakProbe;
+
+impl NativeClass for WeakProbe {
+    type Base = Node;
+    type UserData = user_data::LocalCellData<WeakProbe>;
+    fn class_name() -> &'static str {
+        "WeakProbe"
+    }
+    fn init(_owner: TRef<Node>) -> WeakProbe {
+        WeakProbe
+    }
+    fn register_properties(_builder: &ClassBuilder<Self>) {}
+}
+
+#[methods]
+impl WeakProbe {}
+
+struct OtherWeakProbe;
+
+impl NativeClass for OtherWeakProbe {
+    type Base = Node;
+    type UserData = user_data::LocalCellData<OtherWeakProbe>;
+    fn class_name() -> &'static str {
+        "OtherWeakProbe"
+    }
+    fn init(_owner: TRef<Node>) -> OtherWeakProbe {
+        OtherWeakProbe
+    }
+    fn register_properties(_builder: &ClassBuilder<Self>) {}
+}
+
+#[methods]
+impl OtherWeakProbe {}
+
+/// Keeps a weak reference to a node for `_test_weak_ref_queue_free` in `main.gd`, which checks
+/// that it's gone after the frame the node is deleted in.
+struct WeakTracker {
+    node: Option<WeakObject<Node>>,
+}
+
+impl NativeClass for WeakTracker {
+    type Base = Reference;
+    type UserData = user_data::LocalCellData<WeakTracker>;
+    fn class_name() -> &'static str {
+        "WeakTracker"
+    }
+    fn init(_owner: TRef<Reference>) -> WeakTracker {
+        WeakTracker { node: None }
+    }
+    fn register_properties(_builder: &ClassBuilder<Self>) {}
+}
+
+#[methods]
+impl WeakTracker {
+    #[export]
+    fn track(&mut self, _owner: TRef<Reference>, node: Ref<Node>) {
+        let token = MainThreadToken::try_get().expect("tests should run on the main thread");
+        self.node = Some(unsafe { node.assume_safe_on_main_thread(token) }.downgrade());
+    }
+
+    #[export]
+    fn is_alive(&self, _owner: TRef<Reference>) -> bool {
+        let token = MainThreadToken::try_get().expect("tests should run on the main thread");
+        self.node.map_or(false, |node| {
+            unsafe { node.upgrade_on_main_thread(token) }.is_some()
+        })
+    }
+}
+
+fn test_weak_ref_freed() -> bool {
+    println!(" -- test_weak_ref_freed");
+
+    let ok = std::panic::catch_unwind(|| {
+        let token = MainThreadToken::try_get().expect("tests should run on the main thread");
+        let node = Node::new();
+        node.set_name("foo");
+
+        let weak = node.as_ref().downgrade();
+        assert_eq!(node.get_instance_id(), weak.instance_id());
+        assert!(weak.is_alive());
+
+        let upgraded = unsafe { weak.upgrade_on_main_thread(token) }.expect("node should be alive");
+        assert_eq!("foo", upgraded.name().to_string());
+
+        // The class is checked when upgrading.
+        let as_reference = WeakObject::<Reference>::from_instance_id(weak.instance_id());
+        assert!(unsafe { as_reference.upgrade() }.is_none());
+
+        node.free();
+
+        assert!(!weak.is_alive());
+        assert!(unsafe { weak.upgrade_on_main_thread(token) }.is_none());
+    })
+    .is_ok();
+
+    if !ok {
+        gdnative::godot_error!("   !! Test test_weak_ref_freed failed");
+    }
+
+    ok
+}
+
+fn test_weak_ref_queue_freed() -> bool {
+    println!(" -- test_weak_ref_queue_freed");
+
+    let ok = std::panic::catch_unwind(|| {
+        let token = MainThreadToken::try_get().expect("tests should run on the main thread");
+        let node = Node::new().into_shared();
+        let node = unsafe { node.assume_safe_on_main_thread(token) };
+        let weak = node.downgrade();
+
+        node.queue_free();
+
+        // The node is only deleted at the end of the frame, which is checked in `main.gd`.
+        let upgraded = unsafe { weak.upgrade_on_main_thread(token) }
+            .expect("node should be alive until deleted");
+        assert!(upgraded.is_queued_for_deletion());
+    })
+    .is_ok();
+
+    if !ok {
+        gdnative::godot_error!("   !! Test test_weak_ref_queue_freed failed");
+    }
+
+    ok
+}
+
+fn test_weak_instance_rescripted() -> bool {
+    println!(" -- test_weak_instance_rescripted");
+
+    let ok = std::panic::catch_unwind(|| {
+        let token = MainThreadToken::try_get().expect("tests should run on the main thread");
+        let instance = WeakProbe::new_instance().into_shared();
+        let instance = unsafe { instance.assume_safe_on_main_thread(token) };
+        let weak = instance.downgrade();
+
+        let base = instance.base();
+        assert!(unsafe { weak.upgrade_on_main_thread(token) }.is_some());
+
+        let script = base.get_script().expect("script should be attached");
+        drop(instance);
+
+        // Detaching the script only invalidates the `WeakInstance`.
+        base.set_script(Reference::null());
+        assert!(unsafe { weak.upgrade_on_main_thread(token) }.is_none());
+        assert!(unsafe { weak.base().upgrade_on_main_thread(token) }.is_some());
+
+        // Scripts of other classes are rejected.
+        let other = OtherWeakProbe::new_instance().into_shared();
+        let other = unsafe { other.assume_safe_on_main_thread(token) };
+        base.set_script(other.base().get_script().unwrap());
+        assert!(unsafe { weak.upgrade_on_main_thread(token) }.is_none());
+        unsafe { other.base().claim().assume_unique().free() };
+
+        // A new instance of the same class is accepted.
+        base.set_script(script);
+        assert!(unsafe { weak.upgrade_on_main_thread(token) }.is_some());
+
+        unsafe { base.claim().assume_unique().free() };
+        assert!(unsafe { weak.upgrade_on_main_thread(token) }.is_none());
+        assert!(!weak.base().is_alive());
+    })
+    .is_ok();
+
+    if !ok {
+        gdnative::godot_error!("   !! Test test_weak_instance_rescripted failed");
+    }
+
+    ok
+}